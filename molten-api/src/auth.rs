@@ -0,0 +1,103 @@
+//! Resolves the `Actor` performing a request.
+//!
+//! Molten does not issue credentials itself. Authentication is expected to be
+//! terminated in front of the API (e.g., by an identity-aware reverse proxy),
+//! which forwards the authenticated user and their roles in request headers.
+//! This module provides the `CurrentActor` extractor that reads those headers.
+//!
+//! Anyone able to reach the API can set these headers, so they are only trusted
+//! when the proxy also presents the secret configured in `AuthSettings`. Trust is
+//! opt-in: without a configured secret, every request is rejected as unauthenticated.
+use crate::{error::ApiError, state::AppState};
+use axum::{
+    extract::{FromRequestParts, OptionalFromRequestParts},
+    http::request::Parts,
+};
+use molten_core::actor::Actor;
+
+/// Header carrying the authenticated actor's unique identifier.
+pub const ACTOR_HEADER: &str = "x-molten-actor";
+
+/// Header carrying the authenticated actor's roles, separated by commas.
+pub const ROLES_HEADER: &str = "x-molten-roles";
+
+/// Header carrying the secret shared with the reverse proxy.
+pub const PROXY_SECRET_HEADER: &str = "x-molten-proxy-secret";
+
+/// Extractor for the `Actor` performing the current request.
+///
+/// Rejects the request with `401 Unauthorized` if the proxy secret is missing or wrong,
+/// or if no actor header is present.
+#[derive(Debug, Clone)]
+pub struct CurrentActor(pub Actor);
+
+impl FromRequestParts<AppState> for CurrentActor {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let secret = parts
+            .headers
+            .get(PROXY_SECRET_HEADER)
+            .and_then(|v| v.to_str().ok());
+        if !state.auth.trusts(secret) {
+            return Err(ApiError::Unauthorized(format!(
+                "Actor headers are only accepted with a valid '{}' header",
+                PROXY_SECRET_HEADER
+            )));
+        }
+
+        let id = parts
+            .headers
+            .get(ACTOR_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| ApiError::Unauthorized(format!("Missing '{}' header", ACTOR_HEADER)))?;
+
+        let roles = parts
+            .headers
+            .get(ROLES_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|r| !r.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(CurrentActor(Actor {
+            id: id.to_string(),
+            roles,
+        }))
+    }
+}
+
+/// Extracts `None` when a request carries neither actor nor proxy secret headers, for
+/// endpoints that fall back to the system actor.
+///
+/// A request carrying any of these headers must still authenticate: forged or partial
+/// headers are rejected rather than treated as anonymous.
+impl OptionalFromRequestParts<AppState> for CurrentActor {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        let headers = &parts.headers;
+        if [ACTOR_HEADER, ROLES_HEADER, PROXY_SECRET_HEADER]
+            .iter()
+            .all(|h| !headers.contains_key(*h))
+        {
+            return Ok(None);
+        }
+        <Self as FromRequestParts<AppState>>::from_request_parts(parts, state)
+            .await
+            .map(Some)
+    }
+}
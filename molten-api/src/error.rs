@@ -12,6 +12,7 @@ use axum::{
 use molten_config::ConfigError;
 use molten_service::ServiceError;
 use molten_storage_seaorm::sea_orm::DbErr;
+use molten_workflow::WorkflowError;
 use serde_json::json;
 use thiserror::Error;
 
@@ -24,6 +25,12 @@ pub enum ApiError {
     /// Errors generated from calling molten-config functions
     #[error("molten-config error: {0:?}")]
     Config(#[from] ConfigError),
+    /// The request itself was malformed (e.g. an invalid query parameter)
+    #[error("bad request: {0}")]
+    BadRequest(String),
+    /// The request did not identify an authenticated actor
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
 }
 
 impl IntoResponse for ApiError {
@@ -37,6 +44,18 @@ impl IntoResponse for ApiError {
                 StatusCode::NOT_FOUND,
                 format!("Workflow '{}' not found", id),
             ),
+            ApiError::Service(ServiceError::DocumentNotFound(id)) => (
+                StatusCode::NOT_FOUND,
+                format!("Document '{}' not found", id),
+            ),
+            ApiError::Service(ServiceError::AttachmentNotFound(id)) => (
+                StatusCode::NOT_FOUND,
+                format!("Attachment '{}' not found", id),
+            ),
+            ApiError::Service(ServiceError::JobNotFound(id)) => (
+                StatusCode::NOT_FOUND,
+                format!("Revalidation job '{}' not found", id),
+            ),
 
             // 400 Bad Request (Validation)
             ApiError::Service(ServiceError::DocumentValidationErrors(errs)) => {
@@ -56,10 +75,35 @@ impl IntoResponse for ApiError {
             ApiError::Config(ConfigError::ValidationErrors(e)) => {
                 (StatusCode::BAD_REQUEST, e.to_string())
             }
-            ApiError::Service(ServiceError::WorkflowRuleViolation(e)) => {
+            ApiError::Service(ServiceError::WorkflowRuleViolation(e)) => match e {
+                // 403 Forbidden
+                WorkflowError::Forbidden { .. } => (StatusCode::FORBIDDEN, e.to_string()),
+                _ => (StatusCode::BAD_REQUEST, e.to_string()),
+            },
+            ApiError::Config(ConfigError::JsonError(e)) => (StatusCode::BAD_REQUEST, e.to_string()),
+            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            ApiError::Service(ServiceError::InvalidQuery(e)) => {
                 (StatusCode::BAD_REQUEST, e.to_string())
             }
-            ApiError::Config(ConfigError::JsonError(e)) => (StatusCode::BAD_REQUEST, e.to_string()),
+
+            // 409 Conflict
+            ApiError::Service(ServiceError::Conflict(msg)) => (StatusCode::CONFLICT, msg.clone()),
+            ApiError::Service(ServiceError::BreakingFormChange(report)) => {
+                // Return the changes that would invalidate existing documents
+                return (
+                    StatusCode::CONFLICT,
+                    Json(json!({ "error": "Breaking Form Change", "details": report })),
+                )
+                    .into_response();
+            }
+
+            // 412 Precondition Failed
+            ApiError::Service(ServiceError::PreconditionFailed(msg)) => {
+                (StatusCode::PRECONDITION_FAILED, msg.clone())
+            }
+
+            // 401 Unauthorized
+            ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
 
             // 500 Internal Server Error
             ApiError::Service(ServiceError::DatabaseError(e)) => {
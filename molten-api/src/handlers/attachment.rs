@@ -0,0 +1,156 @@
+//! This module provides the API handlers for uploading and downloading attachments.
+//!
+//! Files are uploaded on their own, and the returned attachment ID is then set as the
+//! value of an `Attachment` field of a document.
+use crate::{auth::CurrentActor, error::ApiError, state::AppState};
+use axum::{
+    Json,
+    extract::{Multipart, Path, State},
+    http::{HeaderMap, HeaderValue, header},
+};
+use molten_core::attachment::Attachment;
+
+/// The multipart field carrying the uploaded file.
+const FILE_FIELD: &str = "file";
+
+/// The uploaded types that downloads are served with. Browsers may render or run other
+/// types (such as `text/html` or `image/svg+xml`), so those are served as
+/// `application/octet-stream` instead.
+const SERVED_MIME_TYPES: [&str; 12] = [
+    "application/json",
+    "application/pdf",
+    "application/zip",
+    "audio/mpeg",
+    "image/gif",
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "text/csv",
+    "text/plain",
+    "video/mp4",
+    "video/webm",
+];
+
+/// Upload a file, to be attached to documents.
+///
+/// Accepts a `multipart/form-data` body whose `file` part carries the file. Its name and
+/// content type are recorded with the attachment, the type defaulting to
+/// `application/octet-stream`. The returned ID can then be set as the value of an
+/// `Attachment` field, whose size and type constraints are checked when the document
+/// is saved.
+///
+/// # Route
+/// `POST /attachments`
+///
+/// # Errors
+/// - Returns an error if the request does not identify an actor.
+/// - Returns an error if the body is not multipart, has no `file` part, or exceeds the
+///   configured upload size.
+/// - Returns an error if storing the file fails.
+pub async fn upload_attachment(
+    State(state): State<AppState>,
+    CurrentActor(actor): CurrentActor,
+    mut multipart: Multipart,
+) -> Result<Json<Attachment>, ApiError> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::BadRequest(e.body_text()))?
+    {
+        if field.name() != Some(FILE_FIELD) {
+            continue;
+        }
+        let file_name = field.file_name().unwrap_or("attachment").to_string();
+        let mime_type = field
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let contents = field
+            .bytes()
+            .await
+            .map_err(|e| ApiError::BadRequest(e.body_text()))?;
+
+        let attachment = state
+            .attachment_service
+            .upload(&file_name, &mime_type, &contents, &actor)
+            .await?;
+        return Ok(Json(attachment));
+    }
+
+    Err(ApiError::BadRequest(format!(
+        "Missing '{}' part in multipart body",
+        FILE_FIELD
+    )))
+}
+
+/// Retrieve the metadata of an attachment: its file name, type, size, SHA-256 digest,
+/// uploader and upload time.
+///
+/// # Route
+/// `GET /attachments/{id}`
+///
+/// # Errors
+/// - Returns an error if the attachment does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn get_attachment(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Attachment>, ApiError> {
+    let attachment = state.attachment_service.get_attachment(&id).await?;
+    Ok(Json(attachment))
+}
+
+/// Download the file of an attachment.
+///
+/// The response carries the file's type in `Content-Type`, its name in
+/// `Content-Disposition`, and its SHA-256 digest as an `ETag`. Since the type is the one
+/// the uploader claimed, only types on an allow-list are sent back, others being served
+/// as `application/octet-stream`, and browsers are told not to sniff the type.
+///
+/// # Route
+/// `GET /attachments/{id}/content`
+///
+/// # Errors
+/// - Returns an error if the attachment does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<(HeaderMap, Vec<u8>), ApiError> {
+    let (attachment, contents) = state.attachment_service.download(&id).await?;
+
+    let mut headers = HeaderMap::new();
+    let content_type = Some(attachment.mime_type.as_str())
+        .filter(|mime_type| is_served(mime_type))
+        .and_then(|mime_type| HeaderValue::from_str(mime_type).ok())
+        .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+    headers.insert(header::CONTENT_TYPE, content_type);
+    headers.insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+    // Keep the name within a quoted string, dropping characters that would end it
+    let file_name: String = attachment
+        .file_name
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"' && *c != '\\')
+        .collect();
+    if let Ok(disposition) =
+        HeaderValue::from_str(&format!("attachment; filename=\"{}\"", file_name))
+    {
+        headers.insert(header::CONTENT_DISPOSITION, disposition);
+    }
+    if let Ok(etag) = HeaderValue::from_str(&format!("\"{}\"", attachment.sha256)) {
+        headers.insert(header::ETAG, etag);
+    }
+    Ok((headers, contents))
+}
+
+/// Returns true if downloads may be served with `mime_type`, ignoring its parameters
+/// (such as `charset`).
+fn is_served(mime_type: &str) -> bool {
+    let essence = mime_type.split(';').next().unwrap_or_default().trim();
+    SERVED_MIME_TYPES
+        .iter()
+        .any(|served| served.eq_ignore_ascii_case(essence))
+}
@@ -1,15 +1,25 @@
 //! This module provides the API handlers for Document entity operations.
 //!
-//! It includes functions for creating new documents and retrieving existing ones,
-//! serving as the entry point for interactions with the document service layer.
-use crate::{error::ApiError, state::AppState};
+//! It includes functions for creating new documents, listing, retrieving and updating
+//! existing ones and moving them through their workflow, serving as the entry point for interactions
+//! with the document service layer.
+use crate::{auth::CurrentActor, error::ApiError, state::AppState};
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, RawQuery, State},
+    http::{HeaderMap, HeaderValue, header},
 };
-use molten_core::document::Document;
+use chrono::{DateTime, Utc};
+use molten_core::actor::Actor;
+use molten_core::document::{Document, DocumentReference};
+use molten_core::history::TransitionRecord;
+use molten_core::query::{
+    Cursor, DEFAULT_PAGE_SIZE, DataFilter, DocumentPage, DocumentQuery, QueryError,
+};
+use molten_core::workflow::Transition;
+use molten_service::ServiceError;
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 
 /// Request payload for creating a new document.
@@ -36,24 +46,29 @@ pub struct CreateDocumentRequest {
 /// If validation succeeds, the document is
 /// persisted and the stored document is returned.
 ///
+/// The caller is recorded as the actor of the first entry in the document's history.
+/// Requests without actor headers are attributed to the system actor.
+///
 /// # Route
 /// `POST /documents`
 ///
 /// # Errors
+/// - Returns an error if the request carries actor headers that are not trusted.
 /// - Returns an error if the document definition fails validation.
 /// - Returns an error if persistence fails.
 ///
 /// # Notes
 /// This endpoint is intended only for creating new documents.
-/// Updates to existing documents should be handled via a separate endpoint
-/// to allow different validation and lifecycle rules.
+/// Updates to existing documents are handled by `PUT` and `PATCH /documents/{id}`.
 pub async fn create_document(
     State(state): State<AppState>,
+    actor: Option<CurrentActor>,
     Json(payload): Json<CreateDocumentRequest>,
 ) -> Result<Json<Document>, ApiError> {
+    let actor = actor.map_or_else(Actor::system, |CurrentActor(actor)| actor);
     let doc = state
         .document_service
-        .create_document(&payload.form_id, &payload.workflow_id, payload.data)
+        .create_document(&payload.form_id, &payload.workflow_id, payload.data, &actor)
         .await?;
 
     Ok(Json(doc))
@@ -61,6 +76,9 @@ pub async fn create_document(
 
 /// Retrieve a document definition by id.
 ///
+/// The response carries an `ETag` header holding the document's version, which can
+/// be sent back in `If-Match` when updating the document.
+///
 /// # Route
 /// `GET /documents/{id}`
 ///
@@ -70,9 +88,429 @@ pub async fn create_document(
 pub async fn get_document(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> Result<Json<Document>, ApiError> {
+) -> Result<(HeaderMap, Json<Document>), ApiError> {
     let doc = state.document_service.get_document(&id).await?;
-    Ok(Json(doc))
+    Ok(with_etag(doc))
+}
+
+/// Query parameters for listing documents.
+///
+/// Timestamps are RFC 3339 strings. Ranges are half-open: `*_from` is inclusive and
+/// `*_to` is exclusive.
+///
+/// Filters on the document data are passed as repeated `filter=field:op:value`
+/// parameters, which are read from the raw query string.
+#[derive(Deserialize)]
+pub struct ListDocumentsParams {
+    /// Only list documents of this form.
+    pub form_id: Option<String>,
+    /// Only list documents created with this version of the form. Data filters are
+    /// typed by this version, or by the latest version if not given.
+    pub form_version: Option<u32>,
+    /// Only list documents governed by this workflow.
+    pub workflow_id: Option<String>,
+    /// Only list documents currently in this phase.
+    pub current_phase: Option<String>,
+    /// Only list documents created at or after this time.
+    pub created_from: Option<DateTime<Utc>>,
+    /// Only list documents created before this time.
+    pub created_to: Option<DateTime<Utc>>,
+    /// Only list documents last modified at or after this time.
+    pub updated_from: Option<DateTime<Utc>>,
+    /// Only list documents last modified before this time.
+    pub updated_to: Option<DateTime<Utc>>,
+    /// Sort order: `created_at`, `updated_at`, or either prefixed with `-` for newest
+    /// first. Defaults to `-created_at`.
+    pub sort: Option<String>,
+    /// Maximum number of documents per page. Defaults to 50, at most 200.
+    pub limit: Option<u64>,
+    /// The `next_cursor` returned with the previous page.
+    pub cursor: Option<String>,
 }
 
-// TODO: POST /documents/{id} for Updates
+/// List documents, one page at a time.
+///
+/// Pages are linked by opaque cursors: pass the `next_cursor` of a page as `cursor`
+/// (with the same filters and sort) to fetch the following one. The last page has no
+/// `next_cursor`. Documents created while paging never cause entries to be skipped
+/// or repeated.
+///
+/// Each `filter=field:op:value` parameter restricts the listing to documents whose
+/// data matches it, e.g. `filter=severity:gte:4&filter=tags:contains:safety`. The
+/// operators are `eq`, `ne`, `lt`, `lte`, `gt`, `gte` (numbers and date-times),
+/// `contains` (multi-selects), `prefix` and `ilike` (text). Filters are typed by the
+/// form, so `form_id` is required when filtering.
+///
+/// # Route
+/// `GET /documents`
+///
+/// # Errors
+/// - Returns an error if the sort order, limit or cursor is invalid.
+/// - Returns an error if a filter is malformed, refers to a field the form does not
+///   have, or cannot apply to the field's type.
+/// - Returns an error if the form being filtered on does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn list_documents(
+    State(state): State<AppState>,
+    Query(params): Query<ListDocumentsParams>,
+    RawQuery(raw_query): RawQuery,
+) -> Result<Json<DocumentPage>, ApiError> {
+    let filters: Vec<String> =
+        serde_urlencoded::from_str::<Vec<(String, String)>>(raw_query.as_deref().unwrap_or(""))
+            .map_err(|e| ApiError::BadRequest(e.to_string()))?
+            .into_iter()
+            .filter(|(key, _)| key == "filter")
+            .map(|(_, value)| value)
+            .collect();
+
+    // Data filters are typed by the form, so resolve them against it
+    let data = match (&params.form_id, filters.is_empty()) {
+        (_, true) => Vec::new(),
+        (None, false) => return Err(ServiceError::from(QueryError::FilterRequiresForm).into()),
+        (Some(form_id), false) => {
+            let form = match params.form_version {
+                Some(version) => {
+                    state
+                        .form_service
+                        .get_form_version(form_id, version)
+                        .await?
+                }
+                None => state.form_service.get_form(form_id).await?,
+            };
+            filters
+                .iter()
+                .map(|f| DataFilter::parse(f, &form))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(ServiceError::from)?
+        }
+    };
+
+    let query = DocumentQuery {
+        form_id: params.form_id,
+        form_version: params.form_version,
+        workflow_id: params.workflow_id,
+        current_phase: params.current_phase,
+        created_from: params.created_from,
+        created_to: params.created_to,
+        updated_from: params.updated_from,
+        updated_to: params.updated_to,
+        data,
+        sort: params
+            .sort
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(ServiceError::from)?
+            .unwrap_or_default(),
+        limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        after: params
+            .cursor
+            .as_deref()
+            .map(Cursor::decode)
+            .transpose()
+            .map_err(ServiceError::from)?,
+    };
+
+    let page = state.document_service.list_documents(&query).await?;
+    Ok(Json(page))
+}
+
+/// Request payload for replacing the data of an existing document.
+#[derive(Deserialize)]
+pub struct UpdateDocumentRequest {
+    /// The new field values for the document, replacing the existing ones entirely.
+    ///
+    /// Validated against the document's form definition exactly as on creation.
+    pub data: HashMap<String, Value>,
+}
+
+/// Replace the data of an existing document.
+///
+/// The new data is revalidated against the document's form definition. On success
+/// the updated document is persisted and returned.
+///
+/// If an `If-Match` header is sent, the update only applies if the document's `ETag`
+/// still matches one of its strong entity tags. The response carries the new `ETag`.
+///
+/// # Route
+/// `PUT /documents/{id}`
+///
+/// # Errors
+/// - Returns an error if the `If-Match` header is malformed.
+/// - Returns an error if the document does not match `If-Match` (412 Precondition
+///   Failed), or was modified concurrently while no `If-Match` was sent (409 Conflict).
+/// - Returns an error if the document or its form does not exist.
+/// - Returns an error if the new data fails validation, with the same detailed body
+///   as `POST /documents`.
+/// - Returns an error if persistence fails.
+pub async fn update_document(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<UpdateDocumentRequest>,
+) -> Result<(HeaderMap, Json<Document>), ApiError> {
+    let expected_versions = if_match(&headers)?;
+    let doc = state
+        .document_service
+        .update_document(&id, payload.data, expected_versions.as_deref())
+        .await?;
+    Ok(with_etag(doc))
+}
+
+/// Partially update the data of an existing document.
+///
+/// The request body is a JSON Merge Patch (RFC 7396) applied to the document's field
+/// values, sent as `application/merge-patch+json`: fields present in the patch are
+/// replaced, nested objects are merged, and fields set to `null` are removed. The
+/// patched data is revalidated against the document's form definition. On success
+/// the updated document is persisted and returned.
+///
+/// If an `If-Match` header is sent, the update only applies if the document's `ETag`
+/// still matches one of its strong entity tags. The response carries the new `ETag`.
+///
+/// # Route
+/// `PATCH /documents/{id}`
+///
+/// # Errors
+/// - Returns an error if the body is not a JSON object.
+/// - Returns an error if the `If-Match` header is malformed.
+/// - Returns an error if the document does not match `If-Match` (412 Precondition
+///   Failed), or was modified concurrently while no `If-Match` was sent (409 Conflict).
+/// - Returns an error if the document or its form does not exist.
+/// - Returns an error if the patched data fails validation, with the same detailed body
+///   as `POST /documents`.
+/// - Returns an error if persistence fails.
+pub async fn patch_document(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Json(patch): Json<Map<String, Value>>,
+) -> Result<(HeaderMap, Json<Document>), ApiError> {
+    let expected_versions = if_match(&headers)?;
+    let doc = state
+        .document_service
+        .patch_document(&id, &patch, expected_versions.as_deref())
+        .await?;
+    Ok(with_etag(doc))
+}
+
+/// Request payload for moving a document to another phase of its workflow.
+#[derive(Deserialize)]
+pub struct TransitionDocumentRequest {
+    /// Unique identifier of the phase the document should move to
+    pub target_phase: String,
+    /// Optional comment recorded in the document's history
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// Transition a document to another phase.
+///
+/// The move is validated against the workflow that governs the document.
+/// If the workflow allows it, the updated document is persisted and returned.
+/// The response carries the new `ETag`.
+///
+/// # Route
+/// `POST /documents/{id}/transition`
+///
+/// # Errors
+/// - Returns an error if the request does not identify an actor.
+/// - Returns an error if the document or its workflow does not exist.
+/// - Returns an error if the workflow does not allow the transition, or the actor
+///   lacks the role required to perform it.
+/// - Returns an error if persistence fails.
+pub async fn transition_document(
+    State(state): State<AppState>,
+    CurrentActor(actor): CurrentActor,
+    Path(id): Path<String>,
+    Json(payload): Json<TransitionDocumentRequest>,
+) -> Result<(HeaderMap, Json<Document>), ApiError> {
+    let doc = state
+        .document_service
+        .transition_document(
+            &id,
+            &payload.target_phase,
+            &actor,
+            payload.comment.as_deref(),
+        )
+        .await?;
+    Ok(with_etag(doc))
+}
+
+/// List the transitions the caller can fire from a document's current phase.
+///
+/// # Route
+/// `GET /documents/{id}/transitions`
+///
+/// # Errors
+/// - Returns an error if the request does not identify an actor.
+/// - Returns an error if the document or its workflow does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn list_transitions(
+    State(state): State<AppState>,
+    CurrentActor(actor): CurrentActor,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Transition>>, ApiError> {
+    let transitions = state
+        .document_service
+        .available_transitions(&id, &actor)
+        .await?;
+    Ok(Json(transitions))
+}
+
+/// Optional request payload for firing a named transition.
+#[derive(Deserialize, Default)]
+pub struct FireTransitionRequest {
+    /// Optional comment recorded in the document's history
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// Fire a named transition (e.g. "approve") on a document.
+///
+/// The request body is optional; when present it may carry a comment. The response
+/// carries the document's new `ETag`.
+///
+/// # Route
+/// `POST /documents/{id}/transitions/{name}`
+///
+/// # Errors
+/// - Returns an error if the request does not identify an actor.
+/// - Returns an error if the document or its workflow does not exist.
+/// - Returns an error if no transition with that name leaves the current phase.
+/// - Returns an error if the actor lacks the role required to fire it.
+/// - Returns an error if persistence fails.
+pub async fn fire_transition(
+    State(state): State<AppState>,
+    CurrentActor(actor): CurrentActor,
+    Path((id, name)): Path<(String, String)>,
+    payload: Option<Json<FireTransitionRequest>>,
+) -> Result<(HeaderMap, Json<Document>), ApiError> {
+    let Json(payload) = payload.unwrap_or_default();
+    let doc = state
+        .document_service
+        .fire_transition(&id, &name, &actor, payload.comment.as_deref())
+        .await?;
+    Ok(with_etag(doc))
+}
+
+/// Retrieve the transition history of a document, oldest entry first.
+///
+/// Each entry records the phase left and entered, the transition taken, the actor,
+/// an optional comment and a timestamp.
+///
+/// # Route
+/// `GET /documents/{id}/history`
+///
+/// # Errors
+/// - Returns an error if the document does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn get_history(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<TransitionRecord>>, ApiError> {
+    let history = state.document_service.get_history(&id).await?;
+    Ok(Json(history))
+}
+
+/// Retrieve the documents a document references through its `Reference` fields.
+///
+/// Each entry names the referencing field (as a path, e.g. `actions/0/procedure` for a
+/// field within a group), the referenced document and its form.
+///
+/// # Route
+/// `GET /documents/{id}/references`
+///
+/// # Errors
+/// - Returns an error if the document does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn get_references(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<DocumentReference>>, ApiError> {
+    let references = state.document_service.get_references(&id).await?;
+    Ok(Json(references))
+}
+
+/// Retrieve the references to a document held by other documents.
+///
+/// Each entry names the referencing document and the field holding the reference.
+///
+/// # Route
+/// `GET /documents/{id}/referenced-by`
+///
+/// # Errors
+/// - Returns an error if the document does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn get_referenced_by(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<DocumentReference>>, ApiError> {
+    let references = state.document_service.get_referenced_by(&id).await?;
+    Ok(Json(references))
+}
+
+/// Retrieve the caller's work queue: the documents assigned to them in their current
+/// phase, least recently updated first.
+///
+/// A document is assigned to the users held by the `User` field its current phase
+/// names as its `assignee`.
+///
+/// # Route
+/// `GET /me/queue`
+///
+/// # Errors
+/// - Returns an error if the request does not identify an actor.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn get_my_queue(
+    State(state): State<AppState>,
+    CurrentActor(actor): CurrentActor,
+) -> Result<Json<Vec<Document>>, ApiError> {
+    let queue = state.document_service.get_queue(&actor).await?;
+    Ok(Json(queue))
+}
+
+/// Builds the response headers for a document, exposing its version as an `ETag`.
+fn with_etag(doc: Document) -> (HeaderMap, Json<Document>) {
+    let mut headers = HeaderMap::new();
+    if let Ok(etag) = HeaderValue::from_str(&format!("\"{}\"", doc.version)) {
+        headers.insert(header::ETAG, etag);
+    }
+    (headers, Json(doc))
+}
+
+/// Reads the document versions a client expects from its `If-Match` header.
+///
+/// Returns `None` when the header is absent or `*`, since any version then matches.
+/// Otherwise the header is a comma-separated list of entity tags, and the document must
+/// be at one of their versions. `If-Match` uses strong comparison (RFC 9110 §13.1.1), so
+/// weak tags (`W/"3"`) never match and are left out of the list, as are tags that are no
+/// document version (`"abc"`). A header that is not a list of entity tags is a bad request.
+fn if_match(headers: &HeaderMap) -> Result<Option<Vec<i32>>, ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let invalid = || ApiError::BadRequest("Invalid If-Match header".to_string());
+
+    let value = value.to_str().map_err(|_| invalid())?.trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    let mut versions = Vec::new();
+    for tag in value.split(',').map(str::trim) {
+        let (weak, tag) = match tag.strip_prefix("W/") {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let opaque = tag
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .ok_or_else(invalid)?;
+        // Tags that are not document versions are well formed, they just never match
+        if let (false, Ok(version)) = (weak, opaque.parse()) {
+            versions.push(version);
+        }
+    }
+    Ok(Some(versions))
+}
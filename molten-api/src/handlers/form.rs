@@ -1,14 +1,16 @@
 //! This module provides the API handlers for Form entity operations.
 //!
-//! It includes functions for creating new forms and retrieving existing ones,
-//! serving as the entry point for interactions with the form service layer.
+//! It includes functions for creating new forms, publishing new versions of them and
+//! retrieving existing ones, serving as the entry point for interactions with the form
+//! service layer.
 use crate::{error::ApiError, state::AppState};
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
 };
-use molten_core::{FormBuilder, FormDefinition};
+use molten_core::{CompatibilityReport, FormBuilder, FormDefinition};
 use molten_service::ServiceError;
+use serde::Deserialize;
 
 /// Create a new form definition.
 ///
@@ -21,12 +23,13 @@ use molten_service::ServiceError;
 ///
 /// # Errors
 /// - Returns an error if the form definition fails validation.
+/// - Returns an error if a form with the same id already exists.
 /// - Returns an error if persistence fails.
 ///
 /// # Notes
 /// This endpoint is intended only for creating new forms.
-/// Updates to existing forms should be handled via a separate endpoint
-/// to allow different validation and lifecycle rules.
+/// Changes to existing forms are published as new versions via
+/// `POST /forms/{id}/versions`.
 pub async fn create_form(
     State(state): State<AppState>,
     Json(builder): Json<FormBuilder>,
@@ -40,7 +43,7 @@ pub async fn create_form(
     Ok(Json(form))
 }
 
-/// Retrieve a form definition by id.
+/// Retrieve the latest version of a form definition by id.
 ///
 /// # Route
 /// `GET /forms/{id}`
@@ -56,4 +59,121 @@ pub async fn get_form(
     Ok(Json(form))
 }
 
-// TODO: POST /forms/{id} for Updates
+/// Query parameters for publishing a new form version.
+#[derive(Deserialize)]
+pub struct PublishFormVersionParams {
+    /// Publish the version even if it contains breaking changes.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Publish a new version of an existing form definition.
+///
+/// Accepts a [`FormBuilder`] describing the new version. Its `id` and `version`
+/// are taken from the path and the latest published version respectively, so the
+/// new version always directly follows the latest one. Previously published
+/// versions are kept unchanged, and existing documents remain valid against the
+/// version they were created with.
+///
+/// A version with breaking changes (such as a new required field or a removed
+/// `Select` option) is refused unless `?force=true` is given.
+///
+/// # Route
+/// `POST /forms/{id}/versions`
+///
+/// # Errors
+/// - Returns an error if the form does not exist.
+/// - Returns an error if the new version fails validation.
+/// - Returns a `409 Conflict` listing the changes if the new version is breaking
+///   and was not forced.
+/// - Returns an error if another version was published concurrently.
+/// - Returns an error if persistence fails.
+pub async fn publish_form_version(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<PublishFormVersionParams>,
+    Json(builder): Json<FormBuilder>,
+) -> Result<Json<FormDefinition>, ApiError> {
+    let form_def = next_version(&state, id, builder).await?;
+
+    let form = state
+        .form_service
+        .publish_version(form_def, params.force)
+        .await?;
+
+    Ok(Json(form))
+}
+
+/// Compare a proposed form version against the latest published one.
+///
+/// Accepts the same [`FormBuilder`] as `POST /forms/{id}/versions`, but only
+/// reports which changes are compatible and which are breaking, without
+/// publishing anything.
+///
+/// # Route
+/// `POST /forms/{id}/compatibility`
+///
+/// # Errors
+/// - Returns an error if the form does not exist.
+/// - Returns an error if the proposed version fails validation.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn check_form_compatibility(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(builder): Json<FormBuilder>,
+) -> Result<Json<CompatibilityReport>, ApiError> {
+    let form_def = next_version(&state, id, builder).await?;
+
+    let report = state.form_service.check_compatibility(&form_def).await?;
+
+    Ok(Json(report))
+}
+
+/// Builds the version of a form that would follow its latest published one.
+async fn next_version(
+    state: &AppState,
+    id: String,
+    mut builder: FormBuilder,
+) -> Result<FormDefinition, ApiError> {
+    let latest = state.form_service.get_form(&id).await?;
+
+    builder.id = id;
+    builder.version = latest.version() + 1;
+    let form_def = builder
+        .build()
+        .map_err(ServiceError::FormValidationErrors)?;
+
+    Ok(form_def)
+}
+
+/// List every published version of a form definition, oldest first.
+///
+/// # Route
+/// `GET /forms/{id}/versions`
+///
+/// # Errors
+/// - Returns an error if the form does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn list_form_versions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<FormDefinition>>, ApiError> {
+    let versions = state.form_service.list_versions(&id).await?;
+    Ok(Json(versions))
+}
+
+/// Retrieve a specific version of a form definition.
+///
+/// # Route
+/// `GET /forms/{id}/versions/{version}`
+///
+/// # Errors
+/// - Returns an error if the form or version does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn get_form_version(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(String, u32)>,
+) -> Result<Json<FormDefinition>, ApiError> {
+    let form = state.form_service.get_form_version(&id, version).await?;
+    Ok(Json(form))
+}
@@ -2,13 +2,26 @@
 //!
 //! It provides a consolidated place to access handlers for Document, Form, and Workflow entities,
 //! simplifying imports and promoting a cleaner API surface for routing.
+/// API Handler for uploading and downloading attachments
+pub mod attachment;
 /// API Handler for CRUD operations on the Document entity
 pub mod document;
 /// API Handler for CRUD operations on the Form entity
 pub mod form;
+/// API Handler for revalidating stored documents against their form
+pub mod revalidation;
 /// API Handler for CRUD operations on the Workflow entity
 pub mod workflow;
 
-pub use document::{create_document, get_document};
-pub use form::{create_form, get_form};
-pub use workflow::{create_workflow, get_workflow};
+pub use attachment::{download_attachment, get_attachment, upload_attachment};
+pub use document::{
+    create_document, fire_transition, get_document, get_history, get_my_queue, get_referenced_by,
+    get_references, list_documents, list_transitions, patch_document, transition_document,
+    update_document,
+};
+pub use form::{
+    check_form_compatibility, create_form, get_form, get_form_version, list_form_versions,
+    publish_form_version,
+};
+pub use revalidation::{get_revalidation, start_revalidation};
+pub use workflow::{create_workflow, get_workflow, get_workflow_diagram};
@@ -0,0 +1,78 @@
+//! This module provides the API handlers for revalidating stored documents.
+//!
+//! It includes functions for starting a revalidation job over the documents of a form
+//! and polling it for progress, serving as the entry point for interactions with the
+//! revalidation service layer.
+use crate::{error::ApiError, state::AppState};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use molten_service::services::revalidation::{DEFAULT_BATCH_SIZE, RevalidationJob};
+use serde::Deserialize;
+
+/// Request payload for starting a revalidation job.
+#[derive(Deserialize, Default)]
+pub struct StartRevalidationRequest {
+    /// The form version to validate against. Defaults to the latest version.
+    #[serde(default)]
+    pub form_version: Option<u32>,
+    /// The number of documents loaded and validated at a time.
+    #[serde(default)]
+    pub batch_size: Option<u64>,
+}
+
+/// Start revalidating every stored document of a form.
+///
+/// The documents are scanned in batches in the background. The returned job is
+/// polled via `GET /revalidations/{job_id}` for progress and for the ids of the
+/// documents that no longer pass validation, together with their errors.
+///
+/// # Route
+/// `POST /forms/{id}/revalidations`
+///
+/// # Errors
+/// - Returns an error if the form or requested version does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn start_revalidation(
+    State(state): State<AppState>,
+    Path(form_id): Path<String>,
+    body: Option<Json<StartRevalidationRequest>>,
+) -> Result<(StatusCode, Json<RevalidationJob>), ApiError> {
+    let Json(payload) = body.unwrap_or_default();
+
+    let job = state
+        .revalidation_service
+        .start_job(
+            &form_id,
+            payload.form_version,
+            payload.batch_size.unwrap_or(DEFAULT_BATCH_SIZE),
+        )
+        .await?;
+
+    let service = state.revalidation_service.clone();
+    let job_id = job.id.clone();
+    tokio::spawn(async move {
+        if let Err(e) = service.run_job(&job_id).await {
+            tracing::error!("Revalidation job '{}' could not run: {:?}", job_id, e);
+        }
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// Retrieve the progress and findings of a revalidation job.
+///
+/// # Route
+/// `GET /revalidations/{job_id}`
+///
+/// # Errors
+/// - Returns an error if the job does not exist.
+pub async fn get_revalidation(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> Result<Json<RevalidationJob>, ApiError> {
+    let job = state.revalidation_service.get_job(&job_id)?;
+    Ok(Json(job))
+}
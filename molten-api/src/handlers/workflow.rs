@@ -1,25 +1,34 @@
 //! This module provides the API handlers for Workflow entity operations.
 //!
-//! It includes functions for creating new workflows and retrieving existing ones,
-//! serving as the entry point for interactions with the workflow service layer.
+//! It includes functions for creating new workflows, retrieving existing ones and
+//! rendering them as diagrams, serving as the entry point for interactions with the
+//! workflow service layer.
 use crate::{error::ApiError, state::AppState};
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
 };
 use molten_core::{WorkflowBuilder, WorkflowDefinition};
 use molten_service::ServiceError;
+use molten_workflow::DiagramFormat;
+use serde::Deserialize;
 
 /// Create a new workflow definition.
 ///
 /// Accepts a [`WorkflowBuilder`] and validates it into a finalized
-/// [`WorkflowDefinition`]. If validation succeeds, the workflow is
-/// persisted and the stored definition is returned.
+/// [`WorkflowDefinition`]. A workflow whose body names a form with `form_id` is bound to
+/// that form: its transition guards (required fields and conditions) and phase assignees
+/// are checked against the form's fields, and its documents must use that form. Unbound
+/// workflows only have their conditions checked for syntax. If validation succeeds, the
+/// workflow is persisted and the stored definition is returned.
 ///
 /// # Route
 /// `POST /workflows`
 ///
 /// # Errors
+/// - Returns an error if the referenced form does not exist.
 /// - Returns an error if the workflow definition fails validation.
 /// - Returns an error if persistence fails.
 ///
@@ -29,8 +38,13 @@ use molten_service::ServiceError;
 /// to allow different validation and lifecycle rules.
 pub async fn create_workflow(
     State(state): State<AppState>,
-    Json(builder): Json<WorkflowBuilder>,
+    Json(mut builder): Json<WorkflowBuilder>,
 ) -> Result<Json<WorkflowDefinition>, ApiError> {
+    if let Some(form_id) = builder.form_id.clone() {
+        let form = state.form_service.get_form(&form_id).await?;
+        builder = builder.for_form(&form);
+    }
+
     let workflow_def: WorkflowDefinition = builder
         .build()
         .map_err(ServiceError::WorkflowValidationErrors)?;
@@ -56,4 +70,44 @@ pub async fn get_workflow(
     Ok(Json(workflow))
 }
 
+/// Query parameters for rendering a workflow diagram.
+#[derive(Deserialize)]
+pub struct DiagramParams {
+    /// The diagram format, either `mermaid` (the default) or `dot`.
+    pub format: Option<String>,
+}
+
+/// Render a workflow definition as a diagram.
+///
+/// Mermaid output is served as `text/plain`, DOT output as `text/vnd.graphviz`.
+///
+/// # Route
+/// `GET /workflows/{id}/diagram?format=mermaid|dot`
+///
+/// # Errors
+/// - Returns an error if the format is not recognised.
+/// - Returns an error if the workflow does not exist.
+/// - Returns an error if the underlying storage operation fails.
+pub async fn get_workflow_diagram(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<DiagramParams>,
+) -> Result<impl IntoResponse, ApiError> {
+    let format = match params.format.as_deref() {
+        Some(format) => format.parse().map_err(ApiError::BadRequest)?,
+        None => DiagramFormat::default(),
+    };
+
+    let workflow = state.workflow_service.get_workflow(&id).await?;
+
+    let content_type = match format {
+        DiagramFormat::Mermaid => "text/plain; charset=utf-8",
+        DiagramFormat::Dot => "text/vnd.graphviz; charset=utf-8",
+    };
+    Ok((
+        [(header::CONTENT_TYPE, content_type)],
+        molten_workflow::render(&workflow, format),
+    ))
+}
+
 // TODO: POST /workflows/{id} for Updates
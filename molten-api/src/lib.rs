@@ -7,6 +7,7 @@
 //! If this crate has been abandoned, please message me and we can discuss ownership transfer.
 #![warn(missing_docs)]
 
+pub mod auth;
 pub mod error;
 pub mod handlers;
 pub mod startup;
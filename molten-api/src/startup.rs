@@ -8,11 +8,14 @@ use crate::handlers;
 use crate::{error::BuildError, state::AppState};
 use axum::{
     Router,
+    extract::DefaultBodyLimit,
     http::StatusCode,
     routing::{get, post},
 };
 use molten_config::settings_parser::Settings;
+use molten_storage_seaorm::blob::LocalBlobStore;
 use molten_storage_seaorm::sea_orm::{Database, DatabaseConnection, DbErr};
+use std::sync::Arc;
 
 /// Represents the Molten API application, encapsulating the server's network listener,
 /// application-wide state, and the port it is bound to.
@@ -20,11 +23,13 @@ pub struct Application {
     listener: TcpListener,
     state: AppState,
     port: u16,
+    max_upload_size: usize,
 }
 
 impl Application {
     /// Builds a new `Application` instance by connecting to the database,
-    /// running pending migrations, and setting up the TCP listener.
+    /// running pending migrations, opening the attachment store, and setting up the
+    /// TCP listener.
     ///
     /// # Arguments
     /// * `config` - The application settings loaded from configuration.
@@ -37,7 +42,14 @@ impl Application {
         let db: DatabaseConnection = Self::get_db_connection(&config).await?;
         tracing::info!("Connected to database: {}", &config.database.database_name);
 
-        let state = AppState::new(db);
+        let blobs = Arc::new(LocalBlobStore::new(&config.attachments.path));
+        tracing::info!("Storing attachments in: {}", &config.attachments.path);
+
+        if config.auth.proxy_secret.is_none() {
+            tracing::warn!("No proxy secret configured; actor headers will not be trusted");
+        }
+
+        let state = AppState::new(db, blobs, config.auth.clone());
         let addr = format!("{}:{}", config.application.host, config.application.port);
         tracing::info!("Listening on {}", addr);
         let listener = TcpListener::bind(addr).await?;
@@ -48,6 +60,7 @@ impl Application {
             listener,
             state,
             port,
+            max_upload_size: config.attachments.max_upload_size,
         })
     }
 
@@ -73,21 +86,77 @@ impl Application {
     /// Creates the Axum router with all routes and state attached.
     ///
     /// # Arguments
-    /// * `db` - The database connection
+    /// * `state` - The shared application state
+    /// * `max_upload_size` - The largest accepted attachment upload, in bytes
     ///
     /// # Returns
     /// An axum Router
-    fn define_router(db: DatabaseConnection) -> Router {
-        let state = AppState::new(db);
-
+    fn define_router(state: AppState, max_upload_size: usize) -> Router {
         Router::new()
             .route("/health", get(|| async { StatusCode::OK }))
-            .route("/documents", post(handlers::create_document))
-            .route("/documents/{id}", get(handlers::get_document))
+            .route(
+                "/attachments",
+                post(handlers::upload_attachment).layer(DefaultBodyLimit::max(max_upload_size)),
+            )
+            .route("/attachments/{id}", get(handlers::get_attachment))
+            .route(
+                "/attachments/{id}/content",
+                get(handlers::download_attachment),
+            )
+            .route(
+                "/documents",
+                get(handlers::list_documents).post(handlers::create_document),
+            )
+            .route(
+                "/documents/{id}",
+                get(handlers::get_document)
+                    .put(handlers::update_document)
+                    .patch(handlers::patch_document),
+            )
+            .route(
+                "/documents/{id}/transition",
+                post(handlers::transition_document),
+            )
+            .route(
+                "/documents/{id}/transitions",
+                get(handlers::list_transitions),
+            )
+            .route(
+                "/documents/{id}/transitions/{name}",
+                post(handlers::fire_transition),
+            )
+            .route("/documents/{id}/history", get(handlers::get_history))
+            .route("/documents/{id}/references", get(handlers::get_references))
+            .route(
+                "/documents/{id}/referenced-by",
+                get(handlers::get_referenced_by),
+            )
+            .route("/me/queue", get(handlers::get_my_queue))
             .route("/forms", post(handlers::create_form))
             .route("/forms/{id}", get(handlers::get_form))
+            .route(
+                "/forms/{id}/versions",
+                get(handlers::list_form_versions).post(handlers::publish_form_version),
+            )
+            .route(
+                "/forms/{id}/versions/{version}",
+                get(handlers::get_form_version),
+            )
+            .route(
+                "/forms/{id}/compatibility",
+                post(handlers::check_form_compatibility),
+            )
+            .route(
+                "/forms/{id}/revalidations",
+                post(handlers::start_revalidation),
+            )
+            .route("/revalidations/{job_id}", get(handlers::get_revalidation))
             .route("/workflows", post(handlers::create_workflow))
             .route("/workflows/{id}", get(handlers::get_workflow))
+            .route(
+                "/workflows/{id}/diagram",
+                get(handlers::get_workflow_diagram),
+            )
             .with_state(state)
     }
 
@@ -97,8 +166,9 @@ impl Application {
             listener,
             state,
             port: _,
+            max_upload_size,
         } = self;
-        let router = Self::define_router(state.db);
+        let router = Self::define_router(state, max_upload_size);
         axum::serve(listener, router.into_make_service()).await
     }
 }
@@ -3,7 +3,11 @@
 //! This module provides the `AppState` struct, which holds common resources
 //! such as the database connection and service clients, making them
 //! accessible to all request handlers.
-use molten_service::{DocumentService, FormService, WorkflowService};
+use molten_config::settings_parser::AuthSettings;
+use molten_core::attachment::BlobStore;
+use molten_service::{
+    AttachmentService, DocumentService, FormService, RevalidationService, WorkflowService,
+};
 use molten_storage_seaorm::sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
@@ -13,31 +17,44 @@ use std::sync::Arc;
 pub struct AppState {
     /// Database Connection
     pub db: DatabaseConnection,
+    /// Settings deciding which requests' actor headers are trusted
+    pub auth: AuthSettings,
+    /// Smart pointer to attachment orchestration service
+    pub attachment_service: Arc<AttachmentService>,
     /// Smart pointer to document orchestration service
     pub document_service: Arc<DocumentService>,
     /// Smart pointer to form orchestration service
     pub form_service: Arc<FormService>,
+    /// Smart pointer to document revalidation service
+    pub revalidation_service: Arc<RevalidationService>,
     /// Smart pointer to workflow orchestration service
     pub workflow_service: Arc<WorkflowService>,
 }
 
 impl AppState {
     /// Creates a new instance of `AppState`, initializing all shared services
-    /// with the provided database connection.
+    /// with the provided database connection and blob store.
     ///
     /// # Arguments
     /// * `db` - A `DatabaseConnection` to be used by the services.
+    /// * `blobs` - The `BlobStore` holding the contents of attachments.
+    /// * `auth` - The settings deciding which requests' actor headers are trusted.
     ///
     /// # Returns
     /// A new `AppState` instance.
-    pub fn new(db: DatabaseConnection) -> Self {
+    pub fn new(db: DatabaseConnection, blobs: Arc<dyn BlobStore>, auth: AuthSettings) -> Self {
+        let attachment_service = AttachmentService::new(db.clone(), blobs);
         let document_service = DocumentService::new(db.clone());
         let form_service = FormService::new(db.clone());
+        let revalidation_service = RevalidationService::new(db.clone());
         let workflow_service = WorkflowService::new(db.clone());
         Self {
             db,
+            auth,
+            attachment_service: Arc::new(attachment_service),
             document_service: Arc::new(document_service),
             form_service: Arc::new(form_service),
+            revalidation_service: Arc::new(revalidation_service),
             workflow_service: Arc::new(workflow_service),
         }
     }
@@ -82,6 +82,7 @@ where
 mod tests {
     use super::*;
     use molten_core::form::FormDefinition; // Just to ensure types exist
+    use molten_core::workflow::WorkflowDefinition;
 
     // A sample valid YAML form
     const VALID_YAML_FORM: &str = r#"
@@ -130,6 +131,36 @@ fields:
         assert_eq!(form.id(), "incident_report");
     }
 
+    #[test]
+    fn test_parse_yaml_workflow_with_condition() {
+        let yaml = r#"
+id: incident_flow
+name: Incident Flow
+phases:
+  - id: triage
+    label: Triage
+    type: start
+  - id: escalated
+    label: Escalated
+    type: end
+transitions:
+  - name: escalate
+    from: triage
+    to: escalated
+    condition: severity >= 4 && category == "Safety"
+"#;
+        let wf: WorkflowDefinition =
+            parse_content(yaml, ConfigFormat::Yaml).expect("Should parse valid YAML");
+        let condition = wf.transitions()[0].condition.as_ref().unwrap();
+        assert_eq!(condition.fields(), vec!["category", "severity"]);
+
+        // Malformed conditions are rejected while parsing
+        let broken = yaml.replace("severity >= 4", "severity >=");
+        let res: Result<WorkflowDefinition, ConfigError> =
+            parse_content(&broken, ConfigFormat::Yaml);
+        assert!(res.is_err());
+    }
+
     #[test]
     fn test_validation_trigger() {
         // Invalid Form (ID has space, which violates regex/validation rules in Core)
@@ -9,6 +9,7 @@ use molten_storage_seaorm::sea_orm::ConnectOptions;
 /// Runtime configuration parser
 use secrecy::{ExposeSecret, SecretString};
 use serde_aux::field_attributes::deserialize_number_from_string;
+use std::path::Path;
 
 /// Structure for all config settings
 #[derive(serde::Deserialize, Clone)]
@@ -17,6 +18,12 @@ pub struct Settings {
     pub application: AppSettings,
     /// Config settings for database
     pub database: DatabaseSettings,
+    /// Config settings for attachment storage
+    #[serde(default)]
+    pub attachments: AttachmentSettings,
+    /// Config settings for request authentication
+    #[serde(default)]
+    pub auth: AuthSettings,
 }
 
 /// Application configuration settings
@@ -29,6 +36,59 @@ pub struct AppSettings {
     pub port: u16,
 }
 
+/// Attachment storage configuration settings
+#[derive(serde::Deserialize, Clone)]
+#[serde(default)]
+pub struct AttachmentSettings {
+    /// Directory holding the contents of uploaded files
+    pub path: String,
+    /// Largest accepted upload, in bytes
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_upload_size: usize,
+}
+
+impl Default for AttachmentSettings {
+    fn default() -> Self {
+        Self {
+            path: "data/attachments".to_string(),
+            max_upload_size: 25 * 1024 * 1024,
+        }
+    }
+}
+
+/// Request authentication configuration settings
+///
+/// Molten trusts the actor headers set by an authenticating reverse proxy only when a
+/// shared secret is configured here and the proxy presents it with each request. Without
+/// a secret, actor headers are never trusted.
+#[derive(serde::Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AuthSettings {
+    /// Secret shared with the reverse proxy that sets the actor headers
+    pub proxy_secret: Option<SecretString>,
+}
+
+impl AuthSettings {
+    /// Returns true if `presented` matches the configured proxy secret.
+    ///
+    /// Always false when no secret is configured, so that trusting actor headers is
+    /// opt-in. The comparison takes the same time wherever the first mismatch is.
+    pub fn trusts(&self, presented: Option<&str>) -> bool {
+        let (Some(secret), Some(presented)) = (&self.proxy_secret, presented) else {
+            return false;
+        };
+        let secret = secret.expose_secret().as_bytes();
+        let presented = presented.as_bytes();
+        !secret.is_empty()
+            && secret.len() == presented.len()
+            && secret
+                .iter()
+                .zip(presented)
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
 /// Config struct to parse and store database configuration
 #[derive(serde::Deserialize, Clone)]
 pub struct DatabaseSettings {
@@ -75,8 +135,22 @@ pub fn get_configuration() -> Result<Settings, ConfigError> {
     // Set base path
     let base_path = std::env::current_dir().expect("Failed to determine the current directory");
     let config_dir = std::env::var("MOLTEN_CONFIG_DIR").unwrap_or_else(|_| "config".to_string());
-    let config_dir = base_path.join(config_dir);
+    get_configuration_from(&base_path.join(config_dir))
+}
 
+/// Loads and parses application settings from the `app.yaml` file in `config_dir` and
+/// environment variables.
+///
+/// Behaves like [`get_configuration`], but reads the given directory instead of the one
+/// named by `MOLTEN_CONFIG_DIR`.
+///
+/// # Arguments
+/// * `config_dir` - The directory holding the `app.{yaml|toml|json}` file.
+///
+/// # Returns
+/// A `Result` which is `Ok` with the `Settings` instance if successful,
+/// or `Err` with a `ConfigError` if configuration loading or deserialization fails.
+pub fn get_configuration_from(config_dir: &Path) -> Result<Settings, ConfigError> {
     let settings = config::Config::builder()
         // Read app.{yaml|toml|json} file in config directory
         .add_source(config::File::from(config_dir.join("app")).required(true))
@@ -137,6 +211,8 @@ database:
 
             // Assert
             assert_eq!(settings.application.port, 8000);
+            assert_eq!(settings.attachments.path, "data/attachments");
+            assert!(settings.auth.proxy_secret.is_none());
         });
     }
 
@@ -152,6 +228,8 @@ database:
                     Some(config_dir.path().to_str().unwrap()),
                 ),
                 ("MOLTEN_APPLICATION__PORT", Some("1234")),
+                ("MOLTEN_ATTACHMENTS__MAX_UPLOAD_SIZE", Some("1048576")),
+                ("MOLTEN_AUTH__PROXY_SECRET", Some("s3cret")),
             ],
             || {
                 // Act
@@ -159,7 +237,29 @@ database:
 
                 // Assert
                 assert_eq!(settings.application.port, 1234);
+                assert_eq!(settings.attachments.max_upload_size, 1048576);
+                assert!(settings.auth.trusts(Some("s3cret")));
             },
         )
     }
+
+    #[test]
+    fn auth_trusts_only_the_configured_secret() {
+        let unconfigured = AuthSettings::default();
+        assert!(!unconfigured.trusts(Some("anything")));
+        assert!(!unconfigured.trusts(None));
+
+        let configured = AuthSettings {
+            proxy_secret: Some(SecretString::from("s3cret")),
+        };
+        assert!(configured.trusts(Some("s3cret")));
+        assert!(!configured.trusts(Some("s3creT")));
+        assert!(!configured.trusts(Some("s3cret-and-more")));
+        assert!(!configured.trusts(None));
+
+        let empty = AuthSettings {
+            proxy_secret: Some(SecretString::from("")),
+        };
+        assert!(!empty.trusts(Some("")));
+    }
 }
@@ -0,0 +1,70 @@
+//! This module defines the `Actor` struct, which identifies who is performing
+//! an operation on a document.
+//!
+//! An `Actor` is resolved by the calling layer (e.g., from an authenticated API
+//! request) and passed down to the service and workflow engine, which use its
+//! roles to decide whether an operation such as a transition is permitted.
+use serde::{Deserialize, Serialize};
+
+/// The user (or system process) performing an operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Actor {
+    /// Unique identifier of the actor (e.g., a username or subject claim).
+    pub id: String,
+
+    /// The roles held by this actor (e.g., "author", "qa_manager").
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// The ID of the actor standing in for requests made without an authenticated user.
+pub const SYSTEM_ACTOR_ID: &str = "system";
+
+impl Actor {
+    /// Creates a new `Actor` with the given roles.
+    pub fn new(id: &str, roles: &[&str]) -> Self {
+        Self {
+            id: id.to_string(),
+            roles: roles.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    /// Creates the system actor, which holds no roles.
+    ///
+    /// Operations that predate authenticated requests, such as creating a document, are
+    /// attributed to it when no user is known.
+    pub fn system() -> Self {
+        Self::new(SYSTEM_ACTOR_ID, &[])
+    }
+
+    /// Returns true if the actor holds the given role.
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    /// Returns true if the actor holds at least one of the given roles.
+    ///
+    /// An empty list of roles means the operation is unrestricted, so this returns true.
+    pub fn has_any_role(&self, roles: &[String]) -> bool {
+        roles.is_empty() || roles.iter().any(|r| self.has_role(r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_any_role() {
+        let actor = Actor::new("alice", &["author"]);
+
+        assert!(actor.has_role("author"));
+        assert!(actor.has_any_role(&[]));
+        assert!(actor.has_any_role(&["reviewer".into(), "author".into()]));
+        assert!(!actor.has_any_role(&["reviewer".into()]));
+
+        let system = Actor::system();
+        assert_eq!(system.id, SYSTEM_ACTOR_ID);
+        assert!(!system.has_any_role(&["author".into()]));
+    }
+}
@@ -0,0 +1,114 @@
+//! This module defines `Attachment`, the metadata of an uploaded file, and the
+//! `BlobStore` trait through which the file contents are stored.
+//!
+//! Files are uploaded on their own, and documents refer to them by ID from their
+//! `Attachment` fields. The metadata is kept alongside documents, while the contents
+//! are kept by a `BlobStore` under their SHA-256 digest, so that a file uploaded twice
+//! is only stored once.
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The metadata of an uploaded file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// Unique identifier of the attachment (usually a UUID).
+    pub id: String,
+
+    /// The name of the file, as uploaded (e.g., "pump-seal.jpg").
+    pub file_name: String,
+
+    /// The MIME type of the file (e.g., "image/jpeg").
+    pub mime_type: String,
+
+    /// The size of the file, in bytes.
+    pub size: u64,
+
+    /// The SHA-256 digest of the file contents, as lowercase hex. Also the key of the
+    /// contents in the `BlobStore`.
+    pub sha256: String,
+
+    /// The ID of the actor who uploaded the file.
+    pub uploaded_by: String,
+
+    /// Timestamp of the upload.
+    pub created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    /// Returns true if the attachment's MIME type is one of `allowed`.
+    ///
+    /// Entries of `allowed` may name a family of types (e.g., `image/*`), and an empty
+    /// list allows any type. MIME types are compared case-insensitively.
+    ///
+    /// # Examples
+    /// ```
+    /// # use molten_core::attachment::Attachment;
+    /// # let attachment = Attachment {
+    /// #     id: "att-1".into(),
+    /// #     file_name: "seal.jpg".into(),
+    /// #     mime_type: "image/jpeg".into(),
+    /// #     size: 1024,
+    /// #     sha256: String::new(),
+    /// #     uploaded_by: "alice".into(),
+    /// #     created_at: chrono::Utc::now(),
+    /// # };
+    /// assert!(attachment.has_mime_type(&["image/*".into()]));
+    /// assert!(!attachment.has_mime_type(&["application/pdf".into()]));
+    /// ```
+    pub fn has_mime_type(&self, allowed: &[String]) -> bool {
+        let mime_type = self.mime_type.to_ascii_lowercase();
+        allowed.is_empty()
+            || allowed.iter().any(|pattern| {
+                let pattern = pattern.to_ascii_lowercase();
+                match pattern.strip_suffix("/*") {
+                    Some(kind) => mime_type
+                        .split_once('/')
+                        .is_some_and(|(actual, _)| actual == kind),
+                    None => mime_type == pattern,
+                }
+            })
+    }
+}
+
+/// Storage for the contents of uploaded files.
+///
+/// Contents are addressed by key, which is the SHA-256 digest of the contents as
+/// lowercase hex. Storing the same key twice keeps a single copy.
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Stores the contents under `key`, replacing any contents already stored.
+    async fn put(&self, key: &str, contents: &[u8]) -> std::io::Result<()>;
+
+    /// Retrieves the contents stored under `key`.
+    ///
+    /// Fails with `std::io::ErrorKind::NotFound` if nothing is stored under `key`.
+    async fn get(&self, key: &str) -> std::io::Result<Vec<u8>>;
+
+    /// Returns true if contents are stored under `key`.
+    async fn exists(&self, key: &str) -> std::io::Result<bool>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_mime_type() {
+        let attachment = Attachment {
+            id: "att-1".into(),
+            file_name: "report.PDF".into(),
+            mime_type: "Application/PDF".into(),
+            size: 2048,
+            sha256: String::new(),
+            uploaded_by: "alice".into(),
+            created_at: Utc::now(),
+        };
+
+        assert!(attachment.has_mime_type(&[]));
+        assert!(attachment.has_mime_type(&["application/pdf".into()]));
+        assert!(attachment.has_mime_type(&["image/*".into(), "application/*".into()]));
+        assert!(!attachment.has_mime_type(&["image/*".into()]));
+        assert!(!attachment.has_mime_type(&["application/pdfx".into()]));
+    }
+}
@@ -0,0 +1,1127 @@
+//! This module compares two versions of a `FormDefinition` and classifies the
+//! differences between them.
+//!
+//! Every difference is reported as a `FormChange`, which is either compatible
+//! (documents valid against the old version stay valid against the new one) or
+//! breaking (some existing documents may fail validation, or lose data, once the
+//! new version is used).
+use crate::expression::Expression;
+use crate::field::{FieldDefinition, FieldType};
+use crate::form::FormDefinition;
+use crate::temporal::{DateBound, IsoDuration, TimeBound};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Whether a change keeps existing documents valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compatibility {
+    /// Every document valid against the old version is valid against the new one.
+    Compatible,
+    /// Some documents valid against the old version may be invalid against the new one.
+    Breaking,
+}
+
+/// A single difference between two versions of a form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum FormChange {
+    /// A field was added. Compatible if it is optional.
+    FieldAdded {
+        /// The ID of the new field.
+        field_id: String,
+        /// Whether the new field is required.
+        required: bool,
+    },
+
+    /// A field was removed, orphaning any data stored under it. Breaking.
+    FieldRemoved {
+        /// The ID of the removed field.
+        field_id: String,
+    },
+
+    /// An optional field became required. Breaking.
+    FieldMadeRequired {
+        /// The ID of the field.
+        field_id: String,
+    },
+
+    /// A required field became optional. Compatible.
+    FieldMadeOptional {
+        /// The ID of the field.
+        field_id: String,
+    },
+
+    /// The `required_when` or `visible_when` condition of a field changed. Compatible if
+    /// the condition was removed, as the field is then never required (or always
+    /// visible) by condition.
+    FieldConditionChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// Which condition changed: `required_when` or `visible_when`.
+        condition: String,
+        /// The old condition.
+        from: Option<Expression>,
+        /// The new condition.
+        to: Option<Expression>,
+    },
+
+    /// The type of a field, or a part of its configuration not covered by a more
+    /// specific change, was changed. Breaking.
+    FieldTypeChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old field type.
+        from: FieldType,
+        /// The new field type.
+        to: FieldType,
+    },
+
+    /// The allowed range of a `Number` or `Integer` field changed. Breaking if it was
+    /// narrowed.
+    NumberRangeChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old `(min, max)` bounds.
+        from: (Option<f64>, Option<f64>),
+        /// The new `(min, max)` bounds.
+        to: (Option<f64>, Option<f64>),
+    },
+
+    /// The precision or scale of a `Decimal` field changed. Breaking if it allows fewer
+    /// digits before or after the decimal point.
+    DecimalPrecisionChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old `(precision, scale)`.
+        from: (u32, u32),
+        /// The new `(precision, scale)`.
+        to: (u32, u32),
+    },
+
+    /// The allowed range of a `Decimal` field changed. Breaking if it was narrowed.
+    DecimalRangeChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old `(min, max)` bounds.
+        from: (Option<Decimal>, Option<Decimal>),
+        /// The new `(min, max)` bounds.
+        to: (Option<Decimal>, Option<Decimal>),
+    },
+
+    /// The allowed range of a `Date` field changed. Breaking if it was narrowed, or if a
+    /// fixed bound was replaced by a relative one (or the other way around).
+    DateRangeChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old `(min, max)` bounds.
+        from: (Option<DateBound>, Option<DateBound>),
+        /// The new `(min, max)` bounds.
+        to: (Option<DateBound>, Option<DateBound>),
+    },
+
+    /// The allowed range of a `Time` field changed. Breaking if it was narrowed, or if a
+    /// fixed bound was replaced by `now` (or the other way around).
+    TimeRangeChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old `(min, max)` bounds.
+        from: (Option<TimeBound>, Option<TimeBound>),
+        /// The new `(min, max)` bounds.
+        to: (Option<TimeBound>, Option<TimeBound>),
+    },
+
+    /// The allowed range of a `Duration` field changed. Breaking if it was narrowed.
+    DurationRangeChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old `(min, max)` bounds.
+        from: (Option<IsoDuration>, Option<IsoDuration>),
+        /// The new `(min, max)` bounds.
+        to: (Option<IsoDuration>, Option<IsoDuration>),
+    },
+
+    /// The allowed length of a `Text` or `TextArea` field changed. Breaking if it was
+    /// narrowed.
+    TextLengthChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old `(min_length, max_length)` bounds.
+        from: (Option<usize>, Option<usize>),
+        /// The new `(min_length, max_length)` bounds.
+        to: (Option<usize>, Option<usize>),
+    },
+
+    /// The pattern of a `Text` or `TextArea` field changed. Compatible if it was removed,
+    /// as no pattern can be shown to accept every value another one does.
+    TextPatternChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old pattern.
+        from: Option<String>,
+        /// The new pattern.
+        to: Option<String>,
+    },
+
+    /// The largest allowed file size of an `Attachment` field changed. Breaking if it was
+    /// lowered.
+    AttachmentSizeChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old `max_size`.
+        from: Option<u64>,
+        /// The new `max_size`.
+        to: Option<u64>,
+    },
+
+    /// The allowed MIME types of an `Attachment` field changed. Breaking unless every
+    /// type allowed before still is.
+    AttachmentTypesChanged {
+        /// The ID of the field.
+        field_id: String,
+        /// The old `allowed_mime_types`.
+        from: Vec<String>,
+        /// The new `allowed_mime_types`.
+        to: Vec<String>,
+    },
+
+    /// Options were removed from a `Select` field. Breaking.
+    SelectOptionsRemoved {
+        /// The ID of the field.
+        field_id: String,
+        /// The options that are no longer available.
+        options: Vec<String>,
+    },
+
+    /// Options were added to a `Select` field. Compatible.
+    SelectOptionsAdded {
+        /// The ID of the field.
+        field_id: String,
+        /// The new options.
+        options: Vec<String>,
+    },
+
+    /// A cross-field rule was added. Breaking.
+    RuleAdded {
+        /// The ID of the new rule.
+        rule_id: String,
+    },
+
+    /// A cross-field rule was removed. Compatible.
+    RuleRemoved {
+        /// The ID of the removed rule.
+        rule_id: String,
+    },
+
+    /// A cross-field rule was changed. Breaking, as the new rule may be stricter.
+    RuleChanged {
+        /// The ID of the rule.
+        rule_id: String,
+    },
+}
+
+impl FormChange {
+    /// Classifies the change as compatible or breaking.
+    pub fn compatibility(&self) -> Compatibility {
+        let breaking = match self {
+            FormChange::FieldAdded { required, .. } => *required,
+            FormChange::FieldRemoved { .. } => true,
+            FormChange::FieldMadeRequired { .. } => true,
+            FormChange::FieldMadeOptional { .. } => false,
+            FormChange::FieldConditionChanged { to, .. } => to.is_some(),
+            FormChange::FieldTypeChanged { .. } => true,
+            FormChange::NumberRangeChanged { from, to, .. } => {
+                narrowed_min(from.0, to.0) || narrowed_max(from.1, to.1)
+            }
+            FormChange::DecimalPrecisionChanged { from, to, .. } => {
+                let integer_digits =
+                    |(precision, scale): (u32, u32)| precision.saturating_sub(scale);
+                to.1 < from.1 || integer_digits(*to) < integer_digits(*from)
+            }
+            FormChange::DecimalRangeChanged { from, to, .. } => {
+                narrowed_min(from.0, to.0) || narrowed_max(from.1, to.1)
+            }
+            FormChange::DateRangeChanged { from, to, .. } => {
+                narrowed_min(from.0, to.0) || narrowed_max(from.1, to.1)
+            }
+            FormChange::TimeRangeChanged { from, to, .. } => {
+                narrowed_min(from.0, to.0) || narrowed_max(from.1, to.1)
+            }
+            FormChange::DurationRangeChanged { from, to, .. } => {
+                narrowed_min(from.0, to.0) || narrowed_max(from.1, to.1)
+            }
+            FormChange::TextLengthChanged { from, to, .. } => {
+                narrowed_min(from.0, to.0) || narrowed_max(from.1, to.1)
+            }
+            FormChange::TextPatternChanged { to, .. } => to.is_some(),
+            FormChange::AttachmentSizeChanged { from, to, .. } => narrowed_max(*from, *to),
+            FormChange::AttachmentTypesChanged { from, to, .. } => !allows_all_of(to, from),
+            FormChange::SelectOptionsRemoved { .. } => true,
+            FormChange::SelectOptionsAdded { .. } => false,
+            FormChange::RuleAdded { .. } => true,
+            FormChange::RuleRemoved { .. } => false,
+            FormChange::RuleChanged { .. } => true,
+        };
+
+        if breaking {
+            Compatibility::Breaking
+        } else {
+            Compatibility::Compatible
+        }
+    }
+
+    /// Returns true if the change may invalidate existing documents.
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility() == Compatibility::Breaking
+    }
+}
+
+/// A change together with its classification, as reported by [`compare_forms`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassifiedChange {
+    /// The change itself.
+    #[serde(flatten)]
+    pub change: FormChange,
+    /// Whether the change keeps existing documents valid.
+    pub compatibility: Compatibility,
+}
+
+/// The differences between two versions of a form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    /// The ID of the compared form.
+    pub form_id: String,
+    /// The version compared from.
+    pub from_version: u32,
+    /// The version compared to.
+    pub to_version: u32,
+    /// Whether any change is breaking.
+    pub breaking: bool,
+    /// Every change found, in field order.
+    pub changes: Vec<ClassifiedChange>,
+}
+
+impl CompatibilityReport {
+    /// Returns the breaking changes only.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &FormChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.compatibility == Compatibility::Breaking)
+            .map(|c| &c.change)
+    }
+}
+
+/// Compares two versions of a form and classifies every difference.
+///
+/// Fields are matched by ID. Changes to labels and descriptions are not reported,
+/// as they do not affect which documents are valid.
+///
+/// # Arguments
+/// * `old` - The currently published version.
+/// * `new` - The proposed version.
+///
+/// # Returns
+/// A `CompatibilityReport` listing changes to existing fields (in the old form's field
+/// order), followed by added fields, then changed, removed and added rules.
+pub fn compare_forms(old: &FormDefinition, new: &FormDefinition) -> CompatibilityReport {
+    let mut changes = Vec::new();
+
+    for old_field in old.fields() {
+        match new.fields().iter().find(|f| f.id() == old_field.id()) {
+            Some(new_field) => compare_fields(old_field, new_field, &mut changes),
+            None => changes.push(FormChange::FieldRemoved {
+                field_id: old_field.id().to_string(),
+            }),
+        }
+    }
+
+    for new_field in new.fields() {
+        if !old.fields().iter().any(|f| f.id() == new_field.id()) {
+            changes.push(FormChange::FieldAdded {
+                field_id: new_field.id().to_string(),
+                required: new_field.is_required(),
+            });
+        }
+    }
+
+    for old_rule in old.rules() {
+        match new.rules().iter().find(|r| r.id == old_rule.id) {
+            Some(new_rule) if new_rule != old_rule => changes.push(FormChange::RuleChanged {
+                rule_id: old_rule.id.clone(),
+            }),
+            Some(_) => {}
+            None => changes.push(FormChange::RuleRemoved {
+                rule_id: old_rule.id.clone(),
+            }),
+        }
+    }
+
+    for new_rule in new.rules() {
+        if !old.rules().iter().any(|r| r.id == new_rule.id) {
+            changes.push(FormChange::RuleAdded {
+                rule_id: new_rule.id.clone(),
+            });
+        }
+    }
+
+    let changes: Vec<ClassifiedChange> = changes
+        .into_iter()
+        .map(|change| ClassifiedChange {
+            compatibility: change.compatibility(),
+            change,
+        })
+        .collect();
+
+    CompatibilityReport {
+        form_id: new.id().to_string(),
+        from_version: old.version(),
+        to_version: new.version(),
+        breaking: changes
+            .iter()
+            .any(|c| c.compatibility == Compatibility::Breaking),
+        changes,
+    }
+}
+
+/// Compares two versions of the same field.
+fn compare_fields(old: &FieldDefinition, new: &FieldDefinition, changes: &mut Vec<FormChange>) {
+    let field_id = old.id().to_string();
+
+    match (old.is_required(), new.is_required()) {
+        (false, true) => changes.push(FormChange::FieldMadeRequired {
+            field_id: field_id.clone(),
+        }),
+        (true, false) => changes.push(FormChange::FieldMadeOptional {
+            field_id: field_id.clone(),
+        }),
+        _ => {}
+    }
+
+    let conditions = [
+        ("required_when", old.required_when(), new.required_when()),
+        ("visible_when", old.visible_when(), new.visible_when()),
+    ];
+    for (condition, from, to) in conditions {
+        if from != to {
+            changes.push(FormChange::FieldConditionChanged {
+                field_id: field_id.clone(),
+                condition: condition.to_string(),
+                from: from.cloned(),
+                to: to.cloned(),
+            });
+        }
+    }
+
+    match (old.field_type(), new.field_type()) {
+        (
+            FieldType::Text {
+                min_length: old_min,
+                max_length: old_max,
+                pattern: old_pattern,
+            },
+            FieldType::Text {
+                min_length: new_min,
+                max_length: new_max,
+                pattern: new_pattern,
+            },
+        )
+        | (
+            FieldType::TextArea {
+                min_length: old_min,
+                max_length: old_max,
+                pattern: old_pattern,
+            },
+            FieldType::TextArea {
+                min_length: new_min,
+                max_length: new_max,
+                pattern: new_pattern,
+            },
+        ) => {
+            if (old_min, old_max) != (new_min, new_max) {
+                changes.push(FormChange::TextLengthChanged {
+                    field_id: field_id.clone(),
+                    from: (*old_min, *old_max),
+                    to: (*new_min, *new_max),
+                });
+            }
+            if old_pattern != new_pattern {
+                changes.push(FormChange::TextPatternChanged {
+                    field_id,
+                    from: old_pattern.clone(),
+                    to: new_pattern.clone(),
+                });
+            }
+        }
+        (
+            FieldType::Number {
+                min: old_min,
+                max: old_max,
+            },
+            FieldType::Number {
+                min: new_min,
+                max: new_max,
+            },
+        ) => {
+            if (old_min, old_max) != (new_min, new_max) {
+                changes.push(FormChange::NumberRangeChanged {
+                    field_id,
+                    from: (*old_min, *old_max),
+                    to: (*new_min, *new_max),
+                });
+            }
+        }
+        (
+            FieldType::Integer {
+                min: old_min,
+                max: old_max,
+            },
+            FieldType::Integer {
+                min: new_min,
+                max: new_max,
+            },
+        ) => {
+            if (old_min, old_max) != (new_min, new_max) {
+                let bound = |b: &Option<i64>| b.map(|b| b as f64);
+                changes.push(FormChange::NumberRangeChanged {
+                    field_id,
+                    from: (bound(old_min), bound(old_max)),
+                    to: (bound(new_min), bound(new_max)),
+                });
+            }
+        }
+        (
+            FieldType::Decimal {
+                precision: old_precision,
+                scale: old_scale,
+                min: old_min,
+                max: old_max,
+            },
+            FieldType::Decimal {
+                precision: new_precision,
+                scale: new_scale,
+                min: new_min,
+                max: new_max,
+            },
+        ) => {
+            if (old_precision, old_scale) != (new_precision, new_scale) {
+                changes.push(FormChange::DecimalPrecisionChanged {
+                    field_id: field_id.clone(),
+                    from: (*old_precision, *old_scale),
+                    to: (*new_precision, *new_scale),
+                });
+            }
+            if (old_min, old_max) != (new_min, new_max) {
+                changes.push(FormChange::DecimalRangeChanged {
+                    field_id,
+                    from: (*old_min, *old_max),
+                    to: (*new_min, *new_max),
+                });
+            }
+        }
+        (
+            FieldType::Date {
+                min: old_min,
+                max: old_max,
+            },
+            FieldType::Date {
+                min: new_min,
+                max: new_max,
+            },
+        ) => {
+            if (old_min, old_max) != (new_min, new_max) {
+                changes.push(FormChange::DateRangeChanged {
+                    field_id,
+                    from: (*old_min, *old_max),
+                    to: (*new_min, *new_max),
+                });
+            }
+        }
+        (
+            FieldType::Time {
+                min: old_min,
+                max: old_max,
+            },
+            FieldType::Time {
+                min: new_min,
+                max: new_max,
+            },
+        ) => {
+            if (old_min, old_max) != (new_min, new_max) {
+                changes.push(FormChange::TimeRangeChanged {
+                    field_id,
+                    from: (*old_min, *old_max),
+                    to: (*new_min, *new_max),
+                });
+            }
+        }
+        (
+            FieldType::Duration {
+                min: old_min,
+                max: old_max,
+            },
+            FieldType::Duration {
+                min: new_min,
+                max: new_max,
+            },
+        ) => {
+            if (old_min, old_max) != (new_min, new_max) {
+                changes.push(FormChange::DurationRangeChanged {
+                    field_id,
+                    from: (*old_min, *old_max),
+                    to: (*new_min, *new_max),
+                });
+            }
+        }
+        (
+            FieldType::Select {
+                options: old_options,
+                allow_multiple: old_multiple,
+            },
+            FieldType::Select {
+                options: new_options,
+                allow_multiple: new_multiple,
+            },
+        ) if old_multiple == new_multiple => {
+            let removed: Vec<String> = old_options
+                .iter()
+                .filter(|o| !new_options.contains(o))
+                .cloned()
+                .collect();
+            let added: Vec<String> = new_options
+                .iter()
+                .filter(|o| !old_options.contains(o))
+                .cloned()
+                .collect();
+
+            if !removed.is_empty() {
+                changes.push(FormChange::SelectOptionsRemoved {
+                    field_id: field_id.clone(),
+                    options: removed,
+                });
+            }
+            if !added.is_empty() {
+                changes.push(FormChange::SelectOptionsAdded {
+                    field_id,
+                    options: added,
+                });
+            }
+        }
+        (
+            FieldType::Attachment {
+                max_size: old_size,
+                allowed_mime_types: old_types,
+                allow_multiple: old_multiple,
+            },
+            FieldType::Attachment {
+                max_size: new_size,
+                allowed_mime_types: new_types,
+                allow_multiple: new_multiple,
+            },
+        ) if old_multiple == new_multiple => {
+            if old_size != new_size {
+                changes.push(FormChange::AttachmentSizeChanged {
+                    field_id: field_id.clone(),
+                    from: *old_size,
+                    to: *new_size,
+                });
+            }
+            if old_types != new_types {
+                changes.push(FormChange::AttachmentTypesChanged {
+                    field_id,
+                    from: old_types.clone(),
+                    to: new_types.clone(),
+                });
+            }
+        }
+        (from, to) => {
+            if from != to {
+                changes.push(FormChange::FieldTypeChanged {
+                    field_id,
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Returns true if the lower bound moved up (or appeared), or cannot be compared with
+/// the old one.
+fn narrowed_min<T: PartialOrd>(old: Option<T>, new: Option<T>) -> bool {
+    match (old, new) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(old), Some(new)) => !matches!(
+            new.partial_cmp(&old),
+            Some(Ordering::Less | Ordering::Equal)
+        ),
+    }
+}
+
+/// Returns true if the upper bound moved down (or appeared), or cannot be compared with
+/// the old one.
+fn narrowed_max<T: PartialOrd>(old: Option<T>, new: Option<T>) -> bool {
+    match (old, new) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(old), Some(new)) => !matches!(
+            new.partial_cmp(&old),
+            Some(Ordering::Greater | Ordering::Equal)
+        ),
+    }
+}
+
+/// Returns true if every MIME type the `old` list allows is allowed by the `new` one.
+///
+/// As for `Attachment::has_mime_type`, an empty list allows any type, a family such as
+/// `image/*` allows every type of the family, and types are compared case-insensitively.
+fn allows_all_of(new: &[String], old: &[String]) -> bool {
+    match (new.is_empty(), old.is_empty()) {
+        (true, _) => return true,
+        (false, true) => return false,
+        _ => {}
+    }
+    old.iter().map(|t| t.to_ascii_lowercase()).all(|old_type| {
+        new.iter().map(|t| t.to_ascii_lowercase()).any(|new_type| {
+            new_type == old_type
+                || new_type.strip_suffix("/*").is_some_and(|family| {
+                    old_type
+                        .split_once('/')
+                        .is_some_and(|(kind, _)| kind == family)
+                })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::FieldBuilder;
+    use crate::form::FormBuilder;
+
+    fn field(id: &str, field_type: FieldType, required: bool) -> FieldDefinition {
+        FieldBuilder::new(id, id, field_type)
+            .required(required)
+            .build()
+            .unwrap()
+    }
+
+    fn text() -> FieldType {
+        FieldType::Text {
+            min_length: None,
+            max_length: None,
+            pattern: None,
+        }
+    }
+
+    fn text_area() -> FieldType {
+        FieldType::TextArea {
+            min_length: None,
+            max_length: None,
+            pattern: None,
+        }
+    }
+
+    fn number(min: Option<f64>, max: Option<f64>) -> FieldType {
+        FieldType::Number { min, max }
+    }
+
+    fn select(options: &[&str]) -> FieldType {
+        FieldType::Select {
+            options: options.iter().map(|o| o.to_string()).collect(),
+            allow_multiple: false,
+        }
+    }
+
+    fn form(version: u32, fields: Vec<FieldDefinition>) -> FormDefinition {
+        FormBuilder::new("incident", "Incident")
+            .version(version)
+            .with_fields(fields)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_identical_forms() {
+        let v1 = form(1, vec![field("title", text(), true)]);
+        let report = compare_forms(&v1, &v1);
+        assert!(!report.breaking);
+        assert!(report.changes.is_empty());
+    }
+
+    #[test]
+    fn test_compatible_changes() {
+        let v1 = form(
+            1,
+            vec![
+                field("title", text(), true),
+                field("severity", number(Some(1.0), Some(5.0)), false),
+                field("area", select(&["north", "south"]), false),
+            ],
+        );
+        let v2 = form(
+            2,
+            vec![
+                field("title", text(), false),
+                field("severity", number(Some(0.0), None), false),
+                field("area", select(&["north", "south", "east"]), false),
+                field("notes", text_area(), false),
+            ],
+        );
+
+        let report = compare_forms(&v1, &v2);
+        assert!(!report.breaking);
+        assert_eq!(report.changes.len(), 4);
+        assert_eq!(
+            report.changes[3].change,
+            FormChange::FieldAdded {
+                field_id: "notes".into(),
+                required: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_breaking_changes() {
+        let v1 = form(
+            1,
+            vec![
+                field("title", text(), true),
+                field("severity", number(Some(1.0), Some(5.0)), false),
+                field("area", select(&["north", "south"]), false),
+                field("legacy", FieldType::Boolean, false),
+                field("owner", text(), false),
+            ],
+        );
+        let v2 = form(
+            2,
+            vec![
+                field("title", text_area(), true),
+                field("severity", number(Some(1.0), Some(3.0)), false),
+                field("area", select(&["north"]), false),
+                field("owner", text(), true),
+                field("site", text(), true),
+            ],
+        );
+
+        let report = compare_forms(&v1, &v2);
+        assert!(report.breaking);
+        let kinds: Vec<&str> = report
+            .breaking_changes()
+            .map(|c| match c {
+                FormChange::FieldTypeChanged { .. } => "type",
+                FormChange::NumberRangeChanged { .. } => "range",
+                FormChange::SelectOptionsRemoved { .. } => "options",
+                FormChange::FieldRemoved { .. } => "removed",
+                FormChange::FieldMadeRequired { .. } => "required",
+                FormChange::FieldAdded { .. } => "added",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec!["type", "range", "options", "removed", "required", "added"]
+        );
+    }
+
+    #[test]
+    fn test_text_constraint_changes() {
+        let constrained = |min_length, max_length, pattern: Option<&str>| FieldType::Text {
+            min_length,
+            max_length,
+            pattern: pattern.map(str::to_string),
+        };
+        let v1 = form(
+            1,
+            vec![field(
+                "part_number",
+                constrained(Some(8), Some(8), Some(r"QA-\d{5}")),
+                false,
+            )],
+        );
+
+        // Allowing shorter and longer values, and dropping the pattern, is compatible
+        let v2 = form(
+            2,
+            vec![field(
+                "part_number",
+                constrained(Some(4), None, None),
+                false,
+            )],
+        );
+        let report = compare_forms(&v1, &v2);
+        assert!(!report.breaking);
+        assert_eq!(
+            report.changes.iter().map(|c| &c.change).collect::<Vec<_>>(),
+            vec![
+                &FormChange::TextLengthChanged {
+                    field_id: "part_number".into(),
+                    from: (Some(8), Some(8)),
+                    to: (Some(4), None),
+                },
+                &FormChange::TextPatternChanged {
+                    field_id: "part_number".into(),
+                    from: Some(r"QA-\d{5}".into()),
+                    to: None,
+                },
+            ]
+        );
+
+        // The reverse narrows the length and adds a pattern
+        let report = compare_forms(&v2, &v1);
+        assert_eq!(report.breaking_changes().count(), 2);
+
+        // A changed pattern is breaking, even if it looks wider
+        let v3 = form(
+            3,
+            vec![field(
+                "part_number",
+                constrained(Some(8), Some(8), Some(r"QA-\d+")),
+                false,
+            )],
+        );
+        assert!(compare_forms(&v1, &v3).breaking);
+    }
+
+    #[test]
+    fn test_decimal_changes() {
+        let amount = |precision, scale, max: Option<&str>| FieldType::Decimal {
+            precision,
+            scale,
+            min: Some(Decimal::ZERO),
+            max: max.map(|m| m.parse().unwrap()),
+        };
+        let v1 = form(1, vec![field("amount", amount(6, 2, Some("1000")), false)]);
+
+        // More digits on both sides of the point, and a higher maximum, are compatible
+        let v2 = form(
+            2,
+            vec![field("amount", amount(9, 3, Some("5000.5")), false)],
+        );
+        let report = compare_forms(&v1, &v2);
+        assert!(!report.breaking);
+        assert_eq!(report.changes.len(), 2);
+        assert!(matches!(
+            report.changes[0].change,
+            FormChange::DecimalPrecisionChanged {
+                from: (6, 2),
+                to: (9, 3),
+                ..
+            }
+        ));
+        assert!(compare_forms(&v2, &v1).breaking);
+
+        // A larger scale at the same precision leaves fewer digits before the point
+        let v3 = form(3, vec![field("amount", amount(6, 3, Some("1000")), false)]);
+        let report = compare_forms(&v1, &v3);
+        assert!(report.breaking);
+        assert_eq!(report.changes.len(), 1);
+
+        let v4 = form(4, vec![field("amount", amount(6, 2, None), false)]);
+        assert!(!compare_forms(&v1, &v4).breaking);
+        assert!(compare_forms(&v4, &v1).breaking);
+    }
+
+    #[test]
+    fn test_temporal_range_changes() {
+        let date = |min: Option<&str>, max: Option<&str>| FieldType::Date {
+            min: min.map(|b| b.parse().unwrap()),
+            max: max.map(|b| b.parse().unwrap()),
+        };
+        let time = |min: &str, max: &str| FieldType::Time {
+            min: Some(min.parse().unwrap()),
+            max: Some(max.parse().unwrap()),
+        };
+        let duration = |max: &str| FieldType::Duration {
+            min: None,
+            max: Some(max.parse().unwrap()),
+        };
+        let v1 = form(
+            1,
+            vec![
+                field("occurred_on", date(Some("today-30d"), Some("today")), false),
+                field("shift_start", time("06:00", "10:00"), false),
+                field("downtime", duration("PT8H"), false),
+            ],
+        );
+
+        let v2 = form(
+            2,
+            vec![
+                field("occurred_on", date(Some("today-90d"), None), false),
+                field("shift_start", time("05:00", "10:00"), false),
+                field("downtime", duration("P1D"), false),
+            ],
+        );
+        let report = compare_forms(&v1, &v2);
+        assert!(!report.breaking);
+        assert_eq!(report.changes.len(), 3);
+        assert!(matches!(
+            report.changes[2].change,
+            FormChange::DurationRangeChanged { .. }
+        ));
+        assert_eq!(compare_forms(&v2, &v1).breaking_changes().count(), 3);
+
+        // Fixed and relative bounds cannot be compared, so swapping one for the other is
+        // breaking even when it looks wider today
+        let v3 = form(
+            3,
+            vec![
+                field(
+                    "occurred_on",
+                    date(Some("2000-01-01"), Some("today")),
+                    false,
+                ),
+                field("shift_start", time("06:00", "now"), false),
+                field("downtime", duration("PT8H"), false),
+            ],
+        );
+        let report = compare_forms(&v1, &v3);
+        assert_eq!(report.breaking_changes().count(), 2);
+    }
+
+    #[test]
+    fn test_attachment_changes() {
+        let photos = |max_size, types: &[&str]| FieldType::Attachment {
+            max_size,
+            allowed_mime_types: types.iter().map(|t| t.to_string()).collect(),
+            allow_multiple: true,
+        };
+        let v1 = form(
+            1,
+            vec![field("photos", photos(Some(1024), &["image/png"]), false)],
+        );
+
+        // A larger limit and a family covering the old type are compatible
+        let v2 = form(
+            2,
+            vec![field(
+                "photos",
+                photos(None, &["image/*", "application/pdf"]),
+                false,
+            )],
+        );
+        let report = compare_forms(&v1, &v2);
+        assert!(!report.breaking);
+        assert_eq!(
+            report.changes.iter().map(|c| &c.change).collect::<Vec<_>>(),
+            vec![
+                &FormChange::AttachmentSizeChanged {
+                    field_id: "photos".into(),
+                    from: Some(1024),
+                    to: None,
+                },
+                &FormChange::AttachmentTypesChanged {
+                    field_id: "photos".into(),
+                    from: vec!["image/png".into()],
+                    to: vec!["image/*".into(), "application/pdf".into()],
+                },
+            ]
+        );
+        assert_eq!(compare_forms(&v2, &v1).breaking_changes().count(), 2);
+
+        // Any type is allowed without a list, so adding one is breaking
+        let v3 = form(3, vec![field("photos", photos(None, &[]), false)]);
+        assert!(!compare_forms(&v2, &v3).breaking);
+        assert!(compare_forms(&v3, &v2).breaking);
+    }
+
+    #[test]
+    fn test_condition_changes() {
+        use crate::expression::Expression;
+
+        let injury = |required_when: Option<&str>| {
+            let builder = FieldBuilder::new("injury_description", "Injury", text());
+            match required_when {
+                Some(c) => builder.required_when(Expression::parse(c).unwrap()),
+                None => builder,
+            }
+            .build()
+            .unwrap()
+        };
+        let occurred = field("injury_occurred", FieldType::Boolean, false);
+
+        let v1 = form(1, vec![occurred.clone(), injury(None)]);
+        let v2 = form(2, vec![occurred, injury(Some("injury_occurred == true"))]);
+
+        let report = compare_forms(&v1, &v2);
+        assert!(report.breaking);
+        assert!(matches!(
+            &report.changes[0].change,
+            FormChange::FieldConditionChanged { condition, from: None, .. } if condition == "required_when"
+        ));
+        assert!(!compare_forms(&v2, &v1).breaking);
+    }
+
+    #[test]
+    fn test_rule_changes() {
+        use crate::expression::Expression;
+        use crate::rule::{FormRule, RuleKind};
+
+        let fields = vec![field("email", text(), false), field("phone", text(), false)];
+        let contact = |fields: &[&str]| {
+            FormRule::new(
+                "contact",
+                "Provide a way to contact you",
+                RuleKind::AtLeastOneOf {
+                    fields: fields.iter().map(|f| f.to_string()).collect(),
+                },
+            )
+        };
+        let with_rules = |version: u32, rules: Vec<FormRule>| {
+            rules
+                .into_iter()
+                .fold(
+                    FormBuilder::new("incident", "Incident")
+                        .version(version)
+                        .with_fields(fields.clone()),
+                    FormBuilder::add_rule,
+                )
+                .build()
+                .unwrap()
+        };
+        let distinct = FormRule::new(
+            "distinct_contacts",
+            "Email and phone must differ",
+            RuleKind::Condition {
+                condition: Expression::parse("email != phone").unwrap(),
+            },
+        );
+
+        let v1 = with_rules(1, vec![contact(&["email", "phone"]), distinct]);
+        let v2 = with_rules(2, vec![contact(&["email"])]);
+        let report = compare_forms(&v1, &v2);
+        assert!(report.breaking);
+        assert_eq!(
+            report.changes.iter().map(|c| &c.change).collect::<Vec<_>>(),
+            vec![
+                &FormChange::RuleChanged {
+                    rule_id: "contact".into()
+                },
+                &FormChange::RuleRemoved {
+                    rule_id: "distinct_contacts".into()
+                },
+            ]
+        );
+
+        let report = compare_forms(&v2, &with_rules(3, vec![]));
+        assert!(!report.breaking);
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let v1 = form(1, vec![field("legacy", FieldType::Boolean, false)]);
+        let v2 = form(2, vec![]);
+
+        let json = serde_json::to_value(compare_forms(&v1, &v2)).unwrap();
+        assert_eq!(
+            json["changes"][0],
+            serde_json::json!({
+                "change": "field_removed",
+                "field_id": "legacy",
+                "compatibility": "breaking"
+            })
+        );
+    }
+}
@@ -25,6 +25,12 @@ pub struct Document {
     #[validate(length(min = 1, max = 64))]
     pub form_id: String,
 
+    /// The version of the Form Definition this document was created with.
+    ///
+    /// The document is always validated against this version, so that publishing a new
+    /// version of a form never invalidates existing documents.
+    pub form_version: u32,
+
     /// Links this document to a specific Workflow Definition
     #[validate(length(min = 1, max = 64))]
     pub workflow_id: String,
@@ -40,6 +46,12 @@ pub struct Document {
     /// That requires the `FormDefinition` and happens in the `molten-document` crate.
     pub data: HashMap<String, Value>,
 
+    /// Metadata: The revision of this document, starting at 1.
+    ///
+    /// Incremented every time the document is saved, so that concurrent edits
+    /// can be detected (optimistic concurrency control).
+    pub version: i32,
+
     /// Metadata: When this document was created.
     pub created_at: DateTime<Utc>,
 
@@ -49,14 +61,19 @@ pub struct Document {
 
 impl Document {
     /// Creates a new, empty document for a specific form.
+    ///
+    /// The document is pinned to version 1 of the form; set `form_version` when
+    /// creating documents against a later version.
     pub fn new(id: &str, form_id: &str, workflow_id: &str) -> Self {
         let now = Utc::now();
         Self {
             id: id.to_string(),
             form_id: form_id.to_string(),
+            form_version: 1,
             workflow_id: workflow_id.to_string(),
             current_phase: "".to_string(),
             data: HashMap::new(),
+            version: 1,
             created_at: now,
             updated_at: now,
         }
@@ -74,6 +91,20 @@ impl Document {
     }
 }
 
+/// A link from a `Reference` field of one document to another document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentReference {
+    /// The ID of the referencing document.
+    pub document_id: String,
+    /// The path of the referencing field within the document's data, e.g. `incident`,
+    /// or `parts/0/supplier` for a field within a group.
+    pub field_id: String,
+    /// The ID of the referenced document.
+    pub target_id: String,
+    /// The ID of the form of the referenced document.
+    pub target_form_id: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,1318 @@
+//! This module defines a small, sandboxed expression language used to declare
+//! conditions over document data (e.g., `severity >= 4 && category == "Safety"`).
+//!
+//! Expressions are deliberately limited: they can only read field values, compare
+//! them with literals or other fields, and combine the results with boolean logic.
+//! There are no function calls, assignments, loops or arithmetic, and both the source
+//! length and nesting depth are bounded, so evaluating an untrusted expression is
+//! always cheap and side-effect free.
+//!
+//! # Grammar
+//! ```text
+//! expr    := or
+//! or      := and ( "||" and )*
+//! and     := not ( "&&" not )*
+//! not     := "!" not | compare
+//! compare := primary ( ( "==" | "!=" | "<" | "<=" | ">" | ">=" | "in" ) primary )?
+//! primary := number | string | "true" | "false" | "null" | field
+//!          | "(" expr ")" | "[" ( expr ( "," expr )* )? "]"
+//! ```
+//!
+//! Field references are bare identifiers matching `FieldDefinition.id`. Expressions are
+//! typed against a [`FormDefinition`]: [`Expression::check`] rejects unknown fields and
+//! impossible comparisons, and [`Expression::evaluate`] uses the field types to compare
+//! values (e.g., `DateTime`, `Date` and `Time` fields compare chronologically, and
+//! `Duration` fields by length, rather than as text).
+use crate::field::FieldType;
+use crate::form::FormDefinition;
+use crate::temporal::{IsoDuration, parse_date, parse_time};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use thiserror::Error;
+
+/// Maximum accepted length of an expression's source text, in bytes.
+pub const MAX_EXPRESSION_LENGTH: usize = 1024;
+
+/// Maximum nesting depth of an expression (parentheses, lists and `!`).
+pub const MAX_EXPRESSION_DEPTH: usize = 32;
+
+// -----------------------------------------------------------------------------
+// Errors
+// -----------------------------------------------------------------------------
+
+/// Represents errors raised while parsing, type checking or evaluating an expression.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ExpressionError {
+    /// The source text is not a well-formed expression.
+    #[error("Syntax error at position {position}: {message}")]
+    Syntax {
+        /// Byte offset in the source where the problem was detected.
+        position: usize,
+        /// Description of the problem.
+        message: String,
+    },
+
+    /// The expression exceeds the length or nesting limits.
+    #[error("Expression is too complex: {0}")]
+    TooComplex(String),
+
+    /// The expression references a field that the form does not define.
+    #[error("Unknown field '{0}'")]
+    UnknownField(String),
+
+    /// The expression combines values of incompatible types.
+    #[error("Type error: {0}")]
+    Type(String),
+}
+
+// -----------------------------------------------------------------------------
+// Syntax Tree
+// -----------------------------------------------------------------------------
+
+/// A binary operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    /// Logical and (`&&`).
+    And,
+    /// Logical or (`||`).
+    Or,
+    /// Equality (`==`).
+    Eq,
+    /// Inequality (`!=`).
+    Ne,
+    /// Less than (`<`).
+    Lt,
+    /// Less than or equal (`<=`).
+    Le,
+    /// Greater than (`>`).
+    Gt,
+    /// Greater than or equal (`>=`).
+    Ge,
+    /// Membership (`in`): the left value is an element of the right list.
+    In,
+}
+
+impl BinaryOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::In => "in",
+        }
+    }
+}
+
+/// A node of a parsed expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// A literal value (number, string, boolean or null).
+    Literal(Value),
+    /// A reference to a document field by its ID.
+    Field(String),
+    /// A list of expressions (`[a, b, c]`), used with `in`.
+    List(Vec<Expr>),
+    /// Logical negation (`!expr`).
+    Not(Box<Expr>),
+    /// A binary operation.
+    Binary {
+        /// The operator.
+        op: BinaryOp,
+        /// The left-hand operand.
+        lhs: Box<Expr>,
+        /// The right-hand operand.
+        rhs: Box<Expr>,
+    },
+}
+
+impl Expr {
+    fn collect_fields<'a>(&'a self, out: &mut BTreeSet<&'a str>) {
+        match self {
+            Expr::Literal(_) => {}
+            Expr::Field(id) => {
+                out.insert(id);
+            }
+            Expr::List(items) => items.iter().for_each(|e| e.collect_fields(out)),
+            Expr::Not(inner) => inner.collect_fields(out),
+            Expr::Binary { lhs, rhs, .. } => {
+                lhs.collect_fields(out);
+                rhs.collect_fields(out);
+            }
+        }
+    }
+}
+
+/// A parsed expression together with its original source text.
+///
+/// Expressions serialize as their source string, and parse (failing on syntax errors)
+/// when deserialized.
+///
+/// # Examples
+/// ```
+/// use molten_core::expression::Expression;
+///
+/// let expr = Expression::parse(r#"severity >= 4 && category == "Safety""#).unwrap();
+/// assert_eq!(expr.fields(), vec!["category", "severity"]);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Expression {
+    source: String,
+    ast: Expr,
+}
+
+impl PartialEq for Expression {
+    fn eq(&self, other: &Self) -> bool {
+        self.ast == other.ast
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl TryFrom<String> for Expression {
+    type Error = ExpressionError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Expression::parse(&source)
+    }
+}
+
+impl From<Expression> for String {
+    fn from(expr: Expression) -> Self {
+        expr.source
+    }
+}
+
+impl Expression {
+    /// Parses an expression from its source text.
+    ///
+    /// # Returns
+    /// The parsed `Expression`, or an `ExpressionError` if the text is malformed or
+    /// exceeds [`MAX_EXPRESSION_LENGTH`] / [`MAX_EXPRESSION_DEPTH`].
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        if source.len() > MAX_EXPRESSION_LENGTH {
+            return Err(ExpressionError::TooComplex(format!(
+                "source is longer than {} bytes",
+                MAX_EXPRESSION_LENGTH
+            )));
+        }
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            depth: 0,
+            end: source.len(),
+        };
+        let ast = parser.parse_expr()?;
+        if let Some(tok) = parser.peek() {
+            return Err(ExpressionError::Syntax {
+                position: tok.position,
+                message: format!("unexpected {}", tok.kind),
+            });
+        }
+        Ok(Self {
+            source: source.to_string(),
+            ast,
+        })
+    }
+
+    /// Returns the original source text.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the parsed syntax tree.
+    pub fn ast(&self) -> &Expr {
+        &self.ast
+    }
+
+    /// Returns the IDs of every field referenced by the expression, sorted and deduplicated.
+    pub fn fields(&self) -> Vec<&str> {
+        let mut out = BTreeSet::new();
+        self.ast.collect_fields(&mut out);
+        out.into_iter().collect()
+    }
+
+    /// Type checks the expression against a form.
+    ///
+    /// Verifies that every referenced field exists in `form`, that compared values have
+    /// compatible types, and that the expression as a whole yields a boolean.
+    pub fn check(&self, form: &FormDefinition) -> Result<(), ExpressionError> {
+        match type_of(&self.ast, form)? {
+            ExprType::Boolean => Ok(()),
+            other => Err(ExpressionError::Type(format!(
+                "expression must be a condition (boolean), but it yields {}",
+                other
+            ))),
+        }
+    }
+
+    /// Evaluates the expression against document data.
+    ///
+    /// Missing fields evaluate to `null`. Ordering comparisons (`<`, `<=`, `>`, `>=`)
+    /// involving `null` are false. Field values are interpreted according to their type
+    /// in `form`.
+    ///
+    /// # Returns
+    /// `Ok(bool)` with the result, or an `ExpressionError` if a referenced field is unknown
+    /// or the data holds a value of the wrong type.
+    pub fn evaluate(
+        &self,
+        data: &HashMap<String, Value>,
+        form: &FormDefinition,
+    ) -> Result<bool, ExpressionError> {
+        match eval(&self.ast, data, form)? {
+            Val::Bool(b) => Ok(b),
+            other => Err(ExpressionError::Type(format!(
+                "expression must yield a boolean, but it yielded {}",
+                other.type_name()
+            ))),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Lexer
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    True,
+    False,
+    Null,
+    In,
+    And,
+    Or,
+    Not,
+    Op(BinaryOp),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Number(n) => write!(f, "number {}", n),
+            TokenKind::Str(s) => write!(f, "string \"{}\"", s),
+            TokenKind::Ident(s) => write!(f, "field '{}'", s),
+            TokenKind::True => f.write_str("'true'"),
+            TokenKind::False => f.write_str("'false'"),
+            TokenKind::Null => f.write_str("'null'"),
+            TokenKind::In => f.write_str("'in'"),
+            TokenKind::And => f.write_str("'&&'"),
+            TokenKind::Or => f.write_str("'||'"),
+            TokenKind::Not => f.write_str("'!'"),
+            TokenKind::Op(op) => write!(f, "'{}'", op.symbol()),
+            TokenKind::LParen => f.write_str("'('"),
+            TokenKind::RParen => f.write_str("')'"),
+            TokenKind::LBracket => f.write_str("'['"),
+            TokenKind::RBracket => f.write_str("']'"),
+            TokenKind::Comma => f.write_str("','"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ExpressionError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    let syntax = |position: usize, message: &str| ExpressionError::Syntax {
+        position,
+        message: message.to_string(),
+    };
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        let kind = match c {
+            b' ' | b'\t' | b'\n' | b'\r' => {
+                i += 1;
+                continue;
+            }
+            b'(' => {
+                i += 1;
+                TokenKind::LParen
+            }
+            b')' => {
+                i += 1;
+                TokenKind::RParen
+            }
+            b'[' => {
+                i += 1;
+                TokenKind::LBracket
+            }
+            b']' => {
+                i += 1;
+                TokenKind::RBracket
+            }
+            b',' => {
+                i += 1;
+                TokenKind::Comma
+            }
+            b'&' | b'|' => {
+                if bytes.get(i + 1) != Some(&c) {
+                    return Err(syntax(i, &format!("expected '{0}{0}'", c as char)));
+                }
+                i += 2;
+                if c == b'&' {
+                    TokenKind::And
+                } else {
+                    TokenKind::Or
+                }
+            }
+            b'=' => {
+                if bytes.get(i + 1) != Some(&b'=') {
+                    return Err(syntax(i, "expected '==' (assignment is not supported)"));
+                }
+                i += 2;
+                TokenKind::Op(BinaryOp::Eq)
+            }
+            b'!' => {
+                if bytes.get(i + 1) == Some(&b'=') {
+                    i += 2;
+                    TokenKind::Op(BinaryOp::Ne)
+                } else {
+                    i += 1;
+                    TokenKind::Not
+                }
+            }
+            b'<' | b'>' => {
+                let or_equal = bytes.get(i + 1) == Some(&b'=');
+                i += if or_equal { 2 } else { 1 };
+                TokenKind::Op(match (c, or_equal) {
+                    (b'<', false) => BinaryOp::Lt,
+                    (b'<', true) => BinaryOp::Le,
+                    (_, false) => BinaryOp::Gt,
+                    (_, true) => BinaryOp::Ge,
+                })
+            }
+            b'"' | b'\'' => {
+                let quote = c;
+                i += 1;
+                let mut out = String::new();
+                loop {
+                    let Some(ch) = source[i..].chars().next() else {
+                        return Err(syntax(start, "unterminated string"));
+                    };
+                    i += ch.len_utf8();
+                    match ch {
+                        '\\' => {
+                            let Some(esc) = source[i..].chars().next() else {
+                                return Err(syntax(start, "unterminated string"));
+                            };
+                            i += esc.len_utf8();
+                            out.push(match esc {
+                                'n' => '\n',
+                                't' => '\t',
+                                other => other,
+                            });
+                        }
+                        ch if ch as u32 == quote as u32 => break,
+                        ch => out.push(ch),
+                    }
+                }
+                TokenKind::Str(out)
+            }
+            b'-' | b'0'..=b'9' => {
+                i += 1;
+                while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                    i += 1;
+                }
+                let text = &source[start..i];
+                let n: f64 = text
+                    .parse()
+                    .map_err(|_| syntax(start, &format!("invalid number '{}'", text)))?;
+                TokenKind::Number(n)
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'-')
+                {
+                    i += 1;
+                }
+                match &source[start..i] {
+                    "true" => TokenKind::True,
+                    "false" => TokenKind::False,
+                    "null" => TokenKind::Null,
+                    "in" => TokenKind::In,
+                    ident => TokenKind::Ident(ident.to_string()),
+                }
+            }
+            _ => {
+                let ch = source[i..].chars().next().unwrap_or('?');
+                return Err(syntax(i, &format!("unexpected character '{}'", ch)));
+            }
+        };
+        tokens.push(Token {
+            kind,
+            position: start,
+        });
+    }
+
+    Ok(tokens)
+}
+
+// -----------------------------------------------------------------------------
+// Parser
+// -----------------------------------------------------------------------------
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.peek().map(|t| &t.kind) == Some(kind) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<(), ExpressionError> {
+        if self.eat(&kind) {
+            return Ok(());
+        }
+        Err(self.unexpected(&format!("expected {}", kind)))
+    }
+
+    fn unexpected(&self, message: &str) -> ExpressionError {
+        match self.peek() {
+            Some(tok) => ExpressionError::Syntax {
+                position: tok.position,
+                message: format!("{}, found {}", message, tok.kind),
+            },
+            None => ExpressionError::Syntax {
+                position: self.end,
+                message: format!("{}, found end of expression", message),
+            },
+        }
+    }
+
+    fn descend(&mut self) -> Result<(), ExpressionError> {
+        self.depth += 1;
+        if self.depth > MAX_EXPRESSION_DEPTH {
+            return Err(ExpressionError::TooComplex(format!(
+                "nesting is deeper than {} levels",
+                MAX_EXPRESSION_DEPTH
+            )));
+        }
+        Ok(())
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, ExpressionError> {
+        self.parse_or()
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ExpressionError> {
+        let mut lhs = self.parse_and()?;
+        while self.eat(&TokenKind::Or) {
+            let rhs = self.parse_and()?;
+            lhs = binary(BinaryOp::Or, lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ExpressionError> {
+        let mut lhs = self.parse_not()?;
+        while self.eat(&TokenKind::And) {
+            let rhs = self.parse_not()?;
+            lhs = binary(BinaryOp::And, lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, ExpressionError> {
+        if self.eat(&TokenKind::Not) {
+            self.descend()?;
+            let inner = self.parse_not()?;
+            self.depth -= 1;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_compare()
+    }
+
+    fn parse_compare(&mut self) -> Result<Expr, ExpressionError> {
+        let lhs = self.parse_primary()?;
+        let op = match self.peek().map(|t| &t.kind) {
+            Some(TokenKind::Op(op)) => *op,
+            Some(TokenKind::In) => BinaryOp::In,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        let rhs = self.parse_primary()?;
+        Ok(binary(op, lhs, rhs))
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ExpressionError> {
+        let Some(tok) = self.next() else {
+            self.pos -= 1;
+            return Err(self.unexpected("expected a value"));
+        };
+        Ok(match tok.kind {
+            TokenKind::Number(n) => Expr::Literal(Value::from(n)),
+            TokenKind::Str(s) => Expr::Literal(Value::String(s)),
+            TokenKind::True => Expr::Literal(Value::Bool(true)),
+            TokenKind::False => Expr::Literal(Value::Bool(false)),
+            TokenKind::Null => Expr::Literal(Value::Null),
+            TokenKind::Ident(id) => Expr::Field(id),
+            TokenKind::LParen => {
+                self.descend()?;
+                let inner = self.parse_expr()?;
+                self.expect(TokenKind::RParen)?;
+                self.depth -= 1;
+                inner
+            }
+            TokenKind::LBracket => {
+                self.descend()?;
+                let mut items = Vec::new();
+                if !self.eat(&TokenKind::RBracket) {
+                    loop {
+                        items.push(self.parse_expr()?);
+                        if self.eat(&TokenKind::RBracket) {
+                            break;
+                        }
+                        self.expect(TokenKind::Comma)?;
+                    }
+                }
+                self.depth -= 1;
+                Expr::List(items)
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.unexpected("expected a value"));
+            }
+        })
+    }
+}
+
+fn binary(op: BinaryOp, lhs: Expr, rhs: Expr) -> Expr {
+    Expr::Binary {
+        op,
+        lhs: Box::new(lhs),
+        rhs: Box::new(rhs),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Type Checking
+// -----------------------------------------------------------------------------
+
+/// The static type of an expression node.
+#[derive(Debug, Clone, PartialEq)]
+enum ExprType {
+    Null,
+    Boolean,
+    Number,
+    Text,
+    DateTime,
+    Date,
+    Time,
+    Duration,
+    List(Box<ExprType>),
+}
+
+impl ExprType {
+    /// Returns true for the types whose values are written as text, but compare as
+    /// moments or lengths of time.
+    fn is_temporal(&self) -> bool {
+        matches!(
+            self,
+            ExprType::DateTime | ExprType::Date | ExprType::Time | ExprType::Duration
+        )
+    }
+
+    /// Describes the text a value of a temporal type is written as.
+    fn notation(&self) -> &'static str {
+        match self {
+            ExprType::DateTime => "an RFC 3339 timestamp",
+            ExprType::Date => "an ISO 8601 date",
+            ExprType::Time => "an ISO 8601 time",
+            ExprType::Duration => "an ISO 8601 duration",
+            _ => "text",
+        }
+    }
+}
+
+impl fmt::Display for ExprType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprType::Null => f.write_str("null"),
+            ExprType::Boolean => f.write_str("boolean"),
+            ExprType::Number => f.write_str("number"),
+            ExprType::Text => f.write_str("text"),
+            ExprType::DateTime => f.write_str("date-time"),
+            ExprType::Date => f.write_str("date"),
+            ExprType::Time => f.write_str("time"),
+            ExprType::Duration => f.write_str("duration"),
+            ExprType::List(inner) => write!(f, "list of {}", inner),
+        }
+    }
+}
+
+/// Maps a field's declared type to the type it has inside an expression.
+fn field_expr_type(field_type: &FieldType) -> ExprType {
+    match field_type {
+        FieldType::Text { .. } | FieldType::TextArea { .. } => ExprType::Text,
+        FieldType::Number { .. } | FieldType::Integer { .. } | FieldType::Decimal { .. } => {
+            ExprType::Number
+        }
+        FieldType::Boolean => ExprType::Boolean,
+        FieldType::DateTime => ExprType::DateTime,
+        FieldType::Date { .. } => ExprType::Date,
+        FieldType::Time { .. } => ExprType::Time,
+        FieldType::Duration { .. } => ExprType::Duration,
+        FieldType::Select { allow_multiple, .. }
+        | FieldType::User { allow_multiple }
+        | FieldType::Reference { allow_multiple, .. }
+        | FieldType::Attachment { allow_multiple, .. } => {
+            if *allow_multiple {
+                ExprType::List(Box::new(ExprType::Text))
+            } else {
+                ExprType::Text
+            }
+        }
+        // Never reached, as `lookup_field` rejects groups
+        FieldType::Group { .. } => ExprType::Null,
+    }
+}
+
+fn lookup_field<'a>(
+    form: &'a FormDefinition,
+    field_id: &str,
+) -> Result<&'a FieldType, ExpressionError> {
+    let field_type = form
+        .fields()
+        .iter()
+        .find(|f| f.id() == field_id)
+        .map(|f| f.field_type())
+        .ok_or_else(|| ExpressionError::UnknownField(field_id.to_string()))?;
+    if let FieldType::Group { .. } = field_type {
+        return Err(ExpressionError::Type(format!(
+            "group field '{}' cannot be used in expressions",
+            field_id
+        )));
+    }
+    Ok(field_type)
+}
+
+/// Returns true if a value of type `a` can be compared for equality with type `b`.
+fn comparable(a: &ExprType, b: &ExprType) -> bool {
+    match (a, b) {
+        (ExprType::Null, _) | (_, ExprType::Null) => true,
+        (ExprType::List(x), ExprType::List(y)) => comparable(x, y),
+        _ => a == b,
+    }
+}
+
+fn type_of(expr: &Expr, form: &FormDefinition) -> Result<ExprType, ExpressionError> {
+    match expr {
+        Expr::Literal(v) => Ok(match v {
+            Value::Bool(_) => ExprType::Boolean,
+            Value::Number(_) => ExprType::Number,
+            Value::String(_) => ExprType::Text,
+            _ => ExprType::Null,
+        }),
+        Expr::Field(id) => lookup_field(form, id).map(field_expr_type),
+        Expr::List(items) => {
+            let mut elem = ExprType::Null;
+            for item in items {
+                let t = type_of(item, form)?;
+                if matches!(t, ExprType::List(_)) {
+                    return Err(ExpressionError::Type("lists cannot be nested".into()));
+                }
+                if elem == ExprType::Null {
+                    elem = t;
+                } else if !comparable(&elem, &t) {
+                    return Err(ExpressionError::Type(format!(
+                        "list mixes {} and {} values",
+                        elem, t
+                    )));
+                }
+            }
+            Ok(ExprType::List(Box::new(elem)))
+        }
+        Expr::Not(inner) => match type_of(inner, form)? {
+            ExprType::Boolean => Ok(ExprType::Boolean),
+            other => Err(ExpressionError::Type(format!(
+                "'!' expects a boolean, but got {}",
+                other
+            ))),
+        },
+        Expr::Binary { op, lhs, rhs } => {
+            let (lt, rt) = (
+                typed_operand(lhs, rhs, form)?,
+                typed_operand(rhs, lhs, form)?,
+            );
+            let mismatch = || {
+                ExpressionError::Type(format!(
+                    "cannot apply '{}' to {} and {}",
+                    op.symbol(),
+                    lt,
+                    rt
+                ))
+            };
+            match op {
+                BinaryOp::And | BinaryOp::Or => {
+                    if lt != ExprType::Boolean || rt != ExprType::Boolean {
+                        return Err(mismatch());
+                    }
+                }
+                BinaryOp::Eq | BinaryOp::Ne => {
+                    if !comparable(&lt, &rt) {
+                        return Err(mismatch());
+                    }
+                }
+                BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+                    let ordered = lt == rt
+                        && (matches!(lt, ExprType::Number | ExprType::Text) || lt.is_temporal());
+                    if !ordered {
+                        return Err(mismatch());
+                    }
+                }
+                BinaryOp::In => match &rt {
+                    ExprType::List(elem) if comparable(&lt, elem) => {}
+                    _ => return Err(mismatch()),
+                },
+            }
+            Ok(ExprType::Boolean)
+        }
+    }
+}
+
+/// Types an operand of a binary operation.
+///
+/// Text literals compared against a temporal operand (e.g. a `Duration` field), or listed
+/// as its candidates with `in`, take its type. They must therefore be written in its
+/// notation (e.g. `PT10M`).
+fn typed_operand(
+    operand: &Expr,
+    other: &Expr,
+    form: &FormDefinition,
+) -> Result<ExprType, ExpressionError> {
+    let t = type_of(operand, form)?;
+    let mut literals = Vec::new();
+    match operand {
+        Expr::Literal(Value::String(s)) => literals.push(s),
+        Expr::List(items) => {
+            for item in items {
+                match item {
+                    Expr::Literal(Value::String(s)) => literals.push(s),
+                    Expr::Literal(Value::Null) => {}
+                    _ => return Ok(t),
+                }
+            }
+        }
+        _ => return Ok(t),
+    }
+    let wanted = match type_of(other, form)? {
+        ExprType::List(inner) => *inner,
+        other_t => other_t,
+    };
+    if literals.is_empty() || !wanted.is_temporal() {
+        return Ok(t);
+    }
+
+    if let Some(s) = literals
+        .iter()
+        .find(|s| parse_temporal(s, &wanted).is_none())
+    {
+        return Err(ExpressionError::Type(format!(
+            "'{}' is compared with a {} but is not {}",
+            s,
+            wanted,
+            wanted.notation()
+        )));
+    }
+    Ok(match operand {
+        Expr::List(_) => ExprType::List(Box::new(wanted)),
+        _ => wanted,
+    })
+}
+
+/// Reads text as a value of a temporal type, or returns `None` if it is not written in
+/// the type's notation.
+fn parse_temporal(text: &str, ty: &ExprType) -> Option<Val> {
+    match ty {
+        ExprType::DateTime => DateTime::parse_from_rfc3339(text).ok().map(Val::DateTime),
+        ExprType::Date => parse_date(text).map(Val::Date),
+        ExprType::Time => parse_time(text).map(Val::Time),
+        ExprType::Duration => text.parse().ok().map(|d: IsoDuration| Val::Duration(d.0)),
+        _ => None,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Evaluation
+// -----------------------------------------------------------------------------
+
+/// A runtime value.
+#[derive(Debug, Clone, PartialEq)]
+enum Val {
+    Null,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    DateTime(DateTime<FixedOffset>),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Duration(TimeDelta),
+    List(Vec<Val>),
+}
+
+impl Val {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Val::Null => "null",
+            Val::Bool(_) => "boolean",
+            Val::Num(_) => "number",
+            Val::Str(_) => "text",
+            Val::DateTime(_) => "date-time",
+            Val::Date(_) => "date",
+            Val::Time(_) => "time",
+            Val::Duration(_) => "duration",
+            Val::List(_) => "list",
+        }
+    }
+
+    fn from_literal(v: &Value) -> Val {
+        match v {
+            Value::Bool(b) => Val::Bool(*b),
+            Value::Number(n) => Val::Num(n.as_f64().unwrap_or(f64::NAN)),
+            Value::String(s) => Val::Str(s.clone()),
+            _ => Val::Null,
+        }
+    }
+
+    /// Interprets a raw document value according to the field's expression type.
+    fn from_field(
+        field_id: &str,
+        v: Option<&Value>,
+        ty: &ExprType,
+    ) -> Result<Val, ExpressionError> {
+        let bad = |v: &Value| {
+            ExpressionError::Type(format!(
+                "field '{}' holds {} which is not a valid {}",
+                field_id, v, ty
+            ))
+        };
+        let Some(v) = v.filter(|v| !v.is_null()) else {
+            return Ok(Val::Null);
+        };
+        Ok(match ty {
+            ExprType::Null => Val::Null,
+            ExprType::Boolean => Val::Bool(v.as_bool().ok_or_else(|| bad(v))?),
+            // Decimal fields may hold their value as a string
+            ExprType::Number => Val::Num(
+                v.as_f64()
+                    .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+                    .ok_or_else(|| bad(v))?,
+            ),
+            ExprType::Text => Val::Str(v.as_str().ok_or_else(|| bad(v))?.to_string()),
+            ExprType::DateTime | ExprType::Date | ExprType::Time | ExprType::Duration => v
+                .as_str()
+                .and_then(|s| parse_temporal(s, ty))
+                .ok_or_else(|| bad(v))?,
+            ExprType::List(inner) => Val::List(
+                v.as_array()
+                    .ok_or_else(|| bad(v))?
+                    .iter()
+                    .map(|item| Val::from_field(field_id, Some(item), inner))
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+
+    /// Converts text to a temporal value when it is compared against one.
+    fn coerce_to(self, other: &Val) -> Val {
+        let ty = match other {
+            Val::DateTime(_) => ExprType::DateTime,
+            Val::Date(_) => ExprType::Date,
+            Val::Time(_) => ExprType::Time,
+            Val::Duration(_) => ExprType::Duration,
+            _ => return self,
+        };
+        match self {
+            Val::Str(s) => parse_temporal(&s, &ty).unwrap_or(Val::Str(s)),
+            v => v,
+        }
+    }
+
+    fn partial_cmp(&self, other: &Val) -> Option<Ordering> {
+        match (self, other) {
+            (Val::Num(a), Val::Num(b)) => a.partial_cmp(b),
+            (Val::Str(a), Val::Str(b)) => Some(a.cmp(b)),
+            (Val::DateTime(a), Val::DateTime(b)) => Some(a.cmp(b)),
+            (Val::Date(a), Val::Date(b)) => Some(a.cmp(b)),
+            (Val::Time(a), Val::Time(b)) => Some(a.cmp(b)),
+            (Val::Duration(a), Val::Duration(b)) => Some(a.cmp(b)),
+            (Val::Bool(a), Val::Bool(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    fn equals(&self, other: &Val) -> bool {
+        match (self, other) {
+            (Val::List(a), Val::List(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.equals(y))
+            }
+            (Val::Null, Val::Null) => true,
+            _ => self.partial_cmp(other) == Some(Ordering::Equal),
+        }
+    }
+}
+
+fn eval(
+    expr: &Expr,
+    data: &HashMap<String, Value>,
+    form: &FormDefinition,
+) -> Result<Val, ExpressionError> {
+    match expr {
+        Expr::Literal(v) => Ok(Val::from_literal(v)),
+        Expr::Field(id) => {
+            let ty = field_expr_type(lookup_field(form, id)?);
+            Val::from_field(id, data.get(id), &ty)
+        }
+        Expr::List(items) => Ok(Val::List(
+            items
+                .iter()
+                .map(|e| eval(e, data, form))
+                .collect::<Result<_, _>>()?,
+        )),
+        Expr::Not(inner) => match eval(inner, data, form)? {
+            Val::Bool(b) => Ok(Val::Bool(!b)),
+            other => Err(ExpressionError::Type(format!(
+                "'!' expects a boolean, but got {}",
+                other.type_name()
+            ))),
+        },
+        Expr::Binary { op, lhs, rhs } => {
+            // Short-circuit boolean logic
+            if matches!(op, BinaryOp::And | BinaryOp::Or) {
+                let l = expect_bool(eval(lhs, data, form)?, op)?;
+                if (*op == BinaryOp::And && !l) || (*op == BinaryOp::Or && l) {
+                    return Ok(Val::Bool(l));
+                }
+                return Ok(Val::Bool(expect_bool(eval(rhs, data, form)?, op)?));
+            }
+
+            let l = eval(lhs, data, form)?;
+            let r = eval(rhs, data, form)?;
+            // At most one side is text to coerce to the other's temporal type
+            let r = r.coerce_to(&l);
+            let l = l.coerce_to(&r);
+            let ordering = || l.partial_cmp(&r);
+            Ok(Val::Bool(match op {
+                BinaryOp::Eq => l.equals(&r),
+                BinaryOp::Ne => !l.equals(&r),
+                BinaryOp::Lt => ordering() == Some(Ordering::Less),
+                BinaryOp::Le => matches!(ordering(), Some(Ordering::Less | Ordering::Equal)),
+                BinaryOp::Gt => ordering() == Some(Ordering::Greater),
+                BinaryOp::Ge => matches!(ordering(), Some(Ordering::Greater | Ordering::Equal)),
+                BinaryOp::In => match r {
+                    Val::List(items) => items.iter().any(|item| {
+                        let item = item.clone().coerce_to(&l);
+                        l.equals(&item)
+                    }),
+                    Val::Null => false,
+                    other => {
+                        return Err(ExpressionError::Type(format!(
+                            "'in' expects a list, but got {}",
+                            other.type_name()
+                        )));
+                    }
+                },
+                BinaryOp::And | BinaryOp::Or => unreachable!("handled above"),
+            }))
+        }
+    }
+}
+
+fn expect_bool(v: Val, op: &BinaryOp) -> Result<bool, ExpressionError> {
+    match v {
+        Val::Bool(b) => Ok(b),
+        other => Err(ExpressionError::Type(format!(
+            "'{}' expects booleans, but got {}",
+            op.symbol(),
+            other.type_name()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::FieldBuilder;
+    use crate::form::FormBuilder;
+    use serde_json::json;
+
+    fn create_test_form() -> FormDefinition {
+        FormBuilder::new("incident", "Incident")
+            .add_field(
+                FieldBuilder::new(
+                    "severity",
+                    "Severity",
+                    FieldType::Number {
+                        min: Some(1.0),
+                        max: Some(5.0),
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "category",
+                    "Category",
+                    FieldType::Select {
+                        options: vec!["Safety".into(), "Quality".into()],
+                        allow_multiple: false,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "tags",
+                    "Tags",
+                    FieldType::Select {
+                        options: vec!["urgent".into(), "external".into()],
+                        allow_multiple: true,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new("occurred_at", "Occurred At", FieldType::DateTime)
+                    .build()
+                    .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new("injury", "Injury", FieldType::Boolean)
+                    .build()
+                    .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "reported_on",
+                    "Reported On",
+                    FieldType::Date {
+                        min: None,
+                        max: None,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "shift_start",
+                    "Shift Start",
+                    FieldType::Time {
+                        min: None,
+                        max: None,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "response_time",
+                    "Response Time",
+                    FieldType::Duration {
+                        min: None,
+                        max: None,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn data(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_evaluate() {
+        let form = create_test_form();
+        let expr = Expression::parse(r#"severity >= 4 && category == "Safety""#).unwrap();
+        assert!(expr.check(&form).is_ok());
+
+        let d = data(json!({ "severity": 4, "category": "Safety" }));
+        assert!(expr.evaluate(&d, &form).unwrap());
+
+        let d = data(json!({ "severity": 2, "category": "Safety" }));
+        assert!(!expr.evaluate(&d, &form).unwrap());
+
+        // Missing fields are null, and ordering against null is false
+        let d = data(json!({ "category": "Safety" }));
+        assert!(!expr.evaluate(&d, &form).unwrap());
+    }
+
+    #[test]
+    fn test_operators() {
+        let form = create_test_form();
+        let d = data(json!({
+            "severity": 3,
+            "tags": ["urgent"],
+            "occurred_at": "2024-05-01T10:00:00+02:00",
+            "injury": false
+        }));
+        let cases = [
+            (r#""urgent" in tags"#, true),
+            (r#"category in ["Safety", "Quality"]"#, false),
+            ("!injury || severity > 4", true),
+            ("(severity < 3 || severity == 3) && injury != true", true),
+            ("category == null", true),
+            (r#"occurred_at < "2024-05-01T09:00:00Z""#, true),
+        ];
+        for (src, expected) in cases {
+            let expr = Expression::parse(src).unwrap();
+            assert!(expr.check(&form).is_ok(), "{} should type check", src);
+            assert_eq!(expr.evaluate(&d, &form).unwrap(), expected, "{}", src);
+        }
+    }
+
+    #[test]
+    fn test_temporal_comparisons() {
+        let form = create_test_form();
+        let d = data(json!({
+            "reported_on": "2024-05-01",
+            "shift_start": "08:30",
+            "response_time": "PT9M"
+        }));
+        let cases = [
+            // Compared as text, each of these would come out the other way
+            (r#"response_time < "PT10M""#, true),
+            (r#"response_time > "PT1H""#, false),
+            (r#"shift_start == "08:30:00""#, true),
+            (r#"shift_start > "8:15""#, true),
+            (r#"reported_on > "2024-4-30""#, true),
+            (r#"response_time in ["PT540S", "PT10M"]"#, true),
+        ];
+        for (src, expected) in cases {
+            let expr = Expression::parse(src).unwrap();
+            assert!(expr.check(&form).is_ok(), "{} should type check", src);
+            assert_eq!(expr.evaluate(&d, &form).unwrap(), expected, "{}", src);
+        }
+
+        // Values of other types cannot be compared with them
+        for src in [
+            r#"response_time < "10 minutes""#,
+            r#"shift_start == "half past eight""#,
+            "response_time < shift_start",
+        ] {
+            let expr = Expression::parse(src).unwrap();
+            assert!(
+                matches!(expr.check(&form), Err(ExpressionError::Type(_))),
+                "{} should not type check",
+                src
+            );
+        }
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        for src in [
+            "severity >",
+            "severity = 4",
+            "(severity > 1",
+            "a && && b",
+            "'open",
+        ] {
+            assert!(
+                matches!(Expression::parse(src), Err(ExpressionError::Syntax { .. })),
+                "{} should not parse",
+                src
+            );
+        }
+
+        let deep = format!("{}injury{}", "(".repeat(40), ")".repeat(40));
+        assert!(matches!(
+            Expression::parse(&deep),
+            Err(ExpressionError::TooComplex(_))
+        ));
+    }
+
+    #[test]
+    fn test_type_errors() {
+        let form = create_test_form();
+
+        let expr = Expression::parse("root_cause == 'x'").unwrap();
+        assert_eq!(
+            expr.check(&form),
+            Err(ExpressionError::UnknownField("root_cause".into()))
+        );
+
+        for src in [
+            r#"severity == "high""#,
+            "severity && injury",
+            "severity",
+            r#"occurred_at > "yesterday""#,
+            "severity in tags",
+        ] {
+            let expr = Expression::parse(src).unwrap();
+            assert!(
+                matches!(expr.check(&form), Err(ExpressionError::Type(_))),
+                "{} should not type check",
+                src
+            );
+        }
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let expr: Expression = serde_json::from_value(json!("severity >= 4")).unwrap();
+        assert_eq!(expr.fields(), vec!["severity"]);
+        assert_eq!(serde_json::to_value(&expr).unwrap(), json!("severity >= 4"));
+
+        let res: Result<Expression, _> = serde_json::from_value(json!("severity >="));
+        assert!(res.is_err());
+    }
+}
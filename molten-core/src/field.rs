@@ -4,8 +4,17 @@
 //! It includes `FieldType` to enumerate the various data types a field can hold,
 //! `FieldDefinition` to describe the metadata and validation rules for a field,
 //! and `FieldBuilder` for constructing `FieldDefinition` instances programmatically.
-use serde::{Deserialize, Serialize};
-use validator::Validate;
+use crate::actor::Actor;
+use crate::expression::Expression;
+use crate::form::FormDefinition;
+use crate::temporal::{DateBound, IsoDuration, TimeBound, parse_date, parse_time};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
+use validator::{Validate, ValidationError, ValidationErrors};
 
 /// The specific data type of a field.
 ///
@@ -27,14 +36,47 @@ use validator::Validate;
 ///   }
 /// }
 /// ```
+///
+/// `text` and `text_area` definitions may omit `config` entirely, as they did before
+/// these kinds gained constraints.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(tag = "kind", content = "config", rename_all = "snake_case")]
+#[serde(
+    remote = "Self",
+    tag = "kind",
+    content = "config",
+    rename_all = "snake_case"
+)]
 pub enum FieldType {
     /// Standard single-line text input.
-    Text,
+    ///
+    /// Optional validation constraints can be applied via `min_length`, `max_length`
+    /// and `pattern`.
+    Text {
+        /// The minimum number of characters (inclusive).
+        #[serde(default)]
+        min_length: Option<usize>,
+        /// The maximum number of characters (inclusive).
+        #[serde(default)]
+        max_length: Option<usize>,
+        /// A regular expression the whole value must match (e.g. `QA-\d{5}`).
+        #[serde(default)]
+        pattern: Option<String>,
+    },
 
     /// Multi-line text area, suitable for descriptions or comments.
-    TextArea,
+    ///
+    /// Accepts the same constraints as [`FieldType::Text`].
+    TextArea {
+        /// The minimum number of characters (inclusive).
+        #[serde(default)]
+        min_length: Option<usize>,
+        /// The maximum number of characters (inclusive).
+        #[serde(default)]
+        max_length: Option<usize>,
+        /// A regular expression the whole value must match.
+        #[serde(default)]
+        pattern: Option<String>,
+    },
 
     /// Numerical input (integer or floating point).
     ///
@@ -48,12 +90,76 @@ pub enum FieldType {
         max: Option<f64>,
     },
 
+    /// Whole-number input, such as a quantity.
+    ///
+    /// Optional validation constraints can be applied via `min` and `max`.
+    Integer {
+        /// The minimum allowed value (inclusive).
+        #[serde(default)]
+        min: Option<i64>,
+        /// The maximum allowed value (inclusive).
+        #[serde(default)]
+        max: Option<i64>,
+    },
+
+    /// Fixed-precision decimal input, such as a currency amount.
+    ///
+    /// Values may be stored as JSON numbers or strings (e.g. `"12.50"`), and are
+    /// validated exactly rather than as floating point.
+    Decimal {
+        /// The maximum total number of digits (at most 28).
+        precision: u32,
+        /// The maximum number of digits after the decimal point.
+        scale: u32,
+        /// The minimum allowed value (inclusive).
+        #[serde(default)]
+        min: Option<Decimal>,
+        /// The maximum allowed value (inclusive).
+        #[serde(default)]
+        max: Option<Decimal>,
+    },
+
     /// A boolean flag (True/False).
     Boolean,
 
     /// A specific date and time.
     DateTime,
 
+    /// A calendar date without a time, such as the date of an incident (`2026-10-16`).
+    ///
+    /// Bounds are fixed dates or relative to the current date, e.g. `max: "today"`
+    /// for a date that cannot be in the future.
+    Date {
+        /// The earliest allowed date (inclusive).
+        #[serde(default)]
+        min: Option<DateBound>,
+        /// The latest allowed date (inclusive).
+        #[serde(default)]
+        max: Option<DateBound>,
+    },
+
+    /// A time of day without a date, such as a shift start time (`08:30`).
+    ///
+    /// Bounds are fixed times or `now`.
+    Time {
+        /// The earliest allowed time (inclusive).
+        #[serde(default)]
+        min: Option<TimeBound>,
+        /// The latest allowed time (inclusive).
+        #[serde(default)]
+        max: Option<TimeBound>,
+    },
+
+    /// A length of time, written as an ISO 8601 duration (`PT8H30M`).
+    Duration {
+        /// The shortest allowed duration (inclusive).
+        #[serde(default)]
+        min: Option<IsoDuration>,
+        /// The longest allowed duration (inclusive).
+        #[serde(default)]
+        max: Option<IsoDuration>,
+    },
+
     /// A selection from a predefined list of options.
     Select {
         /// The list of valid strings a user can choose from.
@@ -62,6 +168,213 @@ pub enum FieldType {
         #[serde(default)]
         allow_multiple: bool,
     },
+
+    /// A user, such as the reviewer of a document, identified by their actor ID.
+    ///
+    /// A phase of the workflow can name a `User` field as its assignee, so that documents
+    /// in that phase show up in the queue of the users the field holds.
+    User {
+        /// If true, the field holds a list of actor IDs.
+        #[serde(default)]
+        allow_multiple: bool,
+    },
+
+    /// A link to other documents, such as the incident a corrective action addresses.
+    ///
+    /// Values are document IDs. Whether the documents exist, and belong to `form_id`,
+    /// is checked when a document is saved.
+    Reference {
+        /// The ID of the form the referenced documents must belong to.
+        form_id: String,
+        /// If true, the field holds a list of document IDs.
+        #[serde(default)]
+        allow_multiple: bool,
+    },
+
+    /// Uploaded files, such as the photos and PDFs of an inspection report.
+    ///
+    /// Values are attachment IDs, as returned when the file was uploaded. Whether the
+    /// attachments exist, and fit `max_size` and `allowed_mime_types`, is checked when a
+    /// document is saved.
+    Attachment {
+        /// The largest allowed file size, in bytes (inclusive).
+        #[serde(default)]
+        max_size: Option<u64>,
+        /// The allowed MIME types (e.g., `application/pdf`), or families of types
+        /// (e.g., `image/*`). Any type is allowed if empty.
+        #[serde(default)]
+        allowed_mime_types: Vec<String>,
+        /// If true, the field holds a list of attachment IDs.
+        #[serde(default)]
+        allow_multiple: bool,
+    },
+
+    /// A group of nested fields, such as the part number, quantity and disposition of
+    /// an affected part.
+    ///
+    /// A repeatable group holds a list of items (a table), each an object keyed by the
+    /// IDs of `fields`. Otherwise, the group holds a single such object. Field IDs must be
+    /// unique within the group, and the conditions and copied defaults of its fields
+    /// refer to the other fields of the same item.
+    Group {
+        /// The fields of each item.
+        fields: Vec<FieldDefinition>,
+        /// The minimum number of items of a repeatable group (inclusive).
+        #[serde(default)]
+        min_items: Option<usize>,
+        /// The maximum number of items of a repeatable group (inclusive).
+        #[serde(default)]
+        max_items: Option<usize>,
+        /// If true, the group holds a list of items rather than a single one.
+        #[serde(default)]
+        repeatable: bool,
+    },
+}
+
+impl FieldType {
+    /// Returns the name of this type, as used for `kind` in serialized definitions.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FieldType::Text { .. } => "text",
+            FieldType::TextArea { .. } => "text_area",
+            FieldType::Number { .. } => "number",
+            FieldType::Integer { .. } => "integer",
+            FieldType::Decimal { .. } => "decimal",
+            FieldType::Boolean => "boolean",
+            FieldType::DateTime => "date_time",
+            FieldType::Date { .. } => "date",
+            FieldType::Time { .. } => "time",
+            FieldType::Duration { .. } => "duration",
+            FieldType::Select { .. } => "select",
+            FieldType::User { .. } => "user",
+            FieldType::Reference { .. } => "reference",
+            FieldType::Attachment { .. } => "attachment",
+            FieldType::Group { .. } => "group",
+        }
+    }
+}
+
+/// The largest precision a `Decimal` field can declare.
+pub const MAX_DECIMAL_PRECISION: u32 = 28;
+
+/// Parses a decimal value exactly, as written in a document or a filter.
+///
+/// Accepts plain (`"-12.50"`) and scientific (`"1.25e3"`) notation. Returns `None` if the
+/// text is not a decimal, or has more digits than can be represented exactly.
+pub fn parse_decimal(text: &str) -> Option<Decimal> {
+    if text.contains(['e', 'E']) {
+        Decimal::from_scientific(text).ok()
+    } else {
+        Decimal::from_str_exact(text).ok()
+    }
+}
+
+/// Counts the digits before the decimal point of a decimal, ignoring its sign.
+///
+/// A `Decimal` field with a given `precision` and `scale` holds at most
+/// `precision - scale` such digits.
+pub fn integer_digits(num: Decimal) -> u32 {
+    let whole = num.trunc().abs();
+    if whole.is_zero() {
+        0
+    } else {
+        whole.to_string().len() as u32
+    }
+}
+
+/// A value filled in automatically when a document is created without one.
+///
+/// # Serde Serialization
+/// This enum is internally tagged by `kind`.
+///
+/// **Example JSON:**
+/// ```json
+/// { "kind": "copy_field", "field": "reported_by" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DynamicDefault {
+    /// The moment the document is created. Applies to `DateTime`, `Date` and `Time` fields.
+    Now,
+    /// The date the document is created. Applies to `Date` fields.
+    Today,
+    /// The ID of the actor creating the document. Applies to text fields and single `User`
+    /// fields.
+    CurrentActor,
+    /// The value of another field of the same type, after that field's own default was
+    /// applied. The source field may not itself copy its default from a field.
+    CopyField {
+        /// The ID of the field to copy from.
+        field: String,
+    },
+}
+
+impl DynamicDefault {
+    /// Returns true if this default can produce a value for the given field type.
+    fn applies_to(&self, field_type: &FieldType) -> bool {
+        match self {
+            DynamicDefault::Now => matches!(
+                field_type,
+                FieldType::DateTime | FieldType::Date { .. } | FieldType::Time { .. }
+            ),
+            DynamicDefault::Today => matches!(field_type, FieldType::Date { .. }),
+            DynamicDefault::CurrentActor => matches!(
+                field_type,
+                FieldType::Text { .. }
+                    | FieldType::TextArea { .. }
+                    | FieldType::User {
+                        allow_multiple: false
+                    }
+            ),
+            // Checked against the source field by the form
+            DynamicDefault::CopyField { .. } => true,
+        }
+    }
+
+    /// Produces the default value for a field, or `None` for `CopyField`, which is resolved
+    /// by the form once every other default has been applied.
+    pub(crate) fn resolve(
+        &self,
+        field_type: &FieldType,
+        actor: &Actor,
+        now: DateTime<Utc>,
+    ) -> Option<Value> {
+        let text = match (self, field_type) {
+            (DynamicDefault::Now, FieldType::Time { .. }) => now.format("%H:%M:%S").to_string(),
+            (DynamicDefault::Now, FieldType::Date { .. }) | (DynamicDefault::Today, _) => {
+                now.format("%Y-%m-%d").to_string()
+            }
+            (DynamicDefault::Now, _) => now.to_rfc3339(),
+            (DynamicDefault::CurrentActor, _) => actor.id.clone(),
+            (DynamicDefault::CopyField { .. }, _) => return None,
+        };
+        Some(Value::String(text))
+    }
+}
+
+impl Serialize for FieldType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FieldType::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+
+        // Text kinds used to be unit variants, stored without any `config`
+        if let Some(obj) = value.as_object_mut()
+            && matches!(
+                obj.get("kind").and_then(|k| k.as_str()),
+                Some("text" | "text_area")
+            )
+            && !obj.contains_key("config")
+        {
+            obj.insert("config".to_string(), serde_json::json!({}));
+        }
+
+        FieldType::deserialize(value).map_err(serde::de::Error::custom)
+    }
 }
 
 /// Defines the validated schema and metadata for a single field in a Form.
@@ -84,6 +397,7 @@ pub struct FieldDefinition {
     label: String,
 
     /// The data type configuration.
+    #[validate(custom(function = "validate_field_type"))]
     field_type: FieldType,
 
     /// If `true`, the document validation will fail if this field is missing or null.
@@ -91,9 +405,198 @@ pub struct FieldDefinition {
     /// transitions, see documentation for [`crate::workflow::Transition`]
     required: bool,
 
+    /// A condition under which the field is required, even if `required` is `false`
+    /// (e.g., `injury_occurred == true`). See [`crate::expression`] for the syntax.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    required_when: Option<Expression>,
+
+    /// A condition under which the field applies. While it does not hold, the field is
+    /// hidden: it must be absent or null, and is never required.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visible_when: Option<Expression>,
+
     /// An optional tooltip or help text to guide the user.
     #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<String>,
+
+    /// A fixed value used when a document is created without this field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default: Option<Value>,
+
+    /// A value computed when a document is created without this field.
+    /// Mutually exclusive with `default`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_from: Option<DynamicDefault>,
+
+    /// The compiled `pattern` of a text field, cached when the field is built.
+    #[serde(skip)]
+    #[validate(skip)]
+    pattern: Option<Regex>,
+
+    /// The form describing one item of a `Group` field, built with the field.
+    #[serde(skip)]
+    #[validate(skip)]
+    group: Option<Box<FormDefinition>>,
+}
+
+/// Fields are equal when their definitions are; cached values are derived from them.
+impl PartialEq for FieldDefinition {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.label == other.label
+            && self.field_type == other.field_type
+            && self.required == other.required
+            && self.required_when == other.required_when
+            && self.visible_when == other.visible_when
+            && self.description == other.description
+            && self.default == other.default
+            && self.default_from == other.default_from
+    }
+}
+
+/// Checks that the constraints configured on a field type are consistent.
+fn validate_field_type(field_type: &FieldType) -> Result<(), ValidationError> {
+    match field_type {
+        FieldType::Text {
+            min_length,
+            max_length,
+            pattern,
+        }
+        | FieldType::TextArea {
+            min_length,
+            max_length,
+            pattern,
+        } => {
+            if let (Some(min), Some(max)) = (min_length, max_length)
+                && min > max
+            {
+                let mut err = ValidationError::new("invalid_length_range");
+                err.add_param("min_length".into(), min);
+                err.add_param("max_length".into(), max);
+                return Err(err);
+            }
+
+            if let Some(pattern) = pattern
+                && let Err(e) = compile_pattern(pattern)
+            {
+                let mut err = ValidationError::new("invalid_pattern");
+                err.add_param("pattern".into(), pattern);
+                err.add_param("reason".into(), &e.to_string());
+                return Err(err);
+            }
+        }
+        FieldType::Integer { min, max } => {
+            if let (Some(min), Some(max)) = (min, max)
+                && min > max
+            {
+                return Err(invalid_range(min, max));
+            }
+        }
+        FieldType::Date {
+            min: Some(DateBound::Date(min)),
+            max: Some(DateBound::Date(max)),
+        } if min > max => return Err(invalid_range(min, max)),
+        FieldType::Time {
+            min: Some(TimeBound::Time(min)),
+            max: Some(TimeBound::Time(max)),
+        } if min > max => return Err(invalid_range(min, max)),
+        FieldType::Duration {
+            min: Some(min),
+            max: Some(max),
+        } if min > max => return Err(invalid_range(min, max)),
+        FieldType::Decimal {
+            precision,
+            scale,
+            min,
+            max,
+        } => {
+            if *precision == 0 || *precision > MAX_DECIMAL_PRECISION || scale > precision {
+                let mut err = ValidationError::new("invalid_decimal_precision");
+                err.add_param("precision".into(), precision);
+                err.add_param("scale".into(), scale);
+                return Err(err);
+            }
+
+            if let (Some(min), Some(max)) = (min, max)
+                && min > max
+            {
+                return Err(invalid_range(min, max));
+            }
+        }
+        FieldType::Reference { form_id, .. } if form_id.is_empty() || form_id.len() > 64 => {
+            let mut err = ValidationError::new("invalid_reference_form");
+            err.add_param("form_id".into(), form_id);
+            return Err(err);
+        }
+        FieldType::Attachment {
+            max_size,
+            allowed_mime_types,
+            ..
+        } => {
+            if *max_size == Some(0) {
+                return Err(ValidationError::new("invalid_max_size"));
+            }
+            if let Some(mime_type) = allowed_mime_types.iter().find(|m| !is_mime_type_pattern(m)) {
+                let mut err = ValidationError::new("invalid_mime_type");
+                err.add_param("mime_type".into(), mime_type);
+                return Err(err);
+            }
+        }
+        FieldType::Group {
+            fields,
+            min_items,
+            max_items,
+            repeatable,
+        } => {
+            if fields.is_empty() {
+                return Err(ValidationError::new("empty_group"));
+            }
+            if !repeatable && (min_items.is_some() || max_items.is_some()) {
+                return Err(ValidationError::new("item_bounds_on_single_group"));
+            }
+            if let (Some(min), Some(max)) = (min_items, max_items)
+                && min > max
+            {
+                return Err(invalid_range(min, max));
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Returns true if `pattern` is a MIME type (`application/pdf`) or a family of types
+/// (`image/*`).
+fn is_mime_type_pattern(pattern: &str) -> bool {
+    let token = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+    };
+    match pattern.split_once('/') {
+        Some((kind, "*")) => token(kind),
+        Some((kind, subtype)) => token(kind) && token(subtype),
+        None => false,
+    }
+}
+
+/// Reports fixed `min` and `max` bounds that exclude every value.
+fn invalid_range(min: impl ToString, max: impl ToString) -> ValidationError {
+    let mut err = ValidationError::new("invalid_range");
+    err.add_param("min".into(), &min.to_string());
+    err.add_param("max".into(), &max.to_string());
+    err
+}
+
+/// Returns true if a value lies within optional inclusive bounds.
+fn within<T: PartialOrd>(value: T, min: Option<T>, max: Option<T>) -> bool {
+    min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+}
+
+/// Compiles a text pattern so that it must match the whole value.
+fn compile_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{})$", pattern))
 }
 
 impl FieldDefinition {
@@ -113,26 +616,239 @@ impl FieldDefinition {
     pub fn is_required(&self) -> bool {
         self.required
     }
+    /// Getter method to obtain the condition under which the field is required
+    pub fn required_when(&self) -> Option<&Expression> {
+        self.required_when.as_ref()
+    }
+    /// Getter method to obtain the condition under which the field is visible
+    pub fn visible_when(&self) -> Option<&Expression> {
+        self.visible_when.as_ref()
+    }
     /// Getter method to obtain Field Description
     pub fn description(&self) -> Option<&str> {
         self.description.as_deref()
     }
+    /// Getter method to obtain the static default value
+    pub fn default_value(&self) -> Option<&Value> {
+        self.default.as_ref()
+    }
+    /// Getter method to obtain the dynamic default
+    pub fn default_from(&self) -> Option<&DynamicDefault> {
+        self.default_from.as_ref()
+    }
+    /// Getter method to obtain the compiled pattern of a text field, if it has one.
+    ///
+    /// The pattern is anchored, so it only matches whole values.
+    pub fn pattern(&self) -> Option<&Regex> {
+        self.pattern.as_ref()
+    }
+    /// Getter method to obtain the form describing one item of a `Group` field.
+    ///
+    /// Its fields are the group's fields, so that items can be defaulted and validated
+    /// like documents.
+    pub fn group(&self) -> Option<&FormDefinition> {
+        self.group.as_deref()
+    }
+
+    /// Returns true if the field applies to the given document data, i.e. it has no
+    /// `visible_when` condition or the condition holds.
+    ///
+    /// A condition that cannot be evaluated (because the data holds a value of the
+    /// wrong type, which validation reports separately) leaves the field visible.
+    pub fn is_visible(&self, data: &HashMap<String, Value>, form: &FormDefinition) -> bool {
+        self.visible_when
+            .as_ref()
+            .is_none_or(|condition| condition.evaluate(data, form).unwrap_or(true))
+    }
+
+    /// Returns true if the field must hold a value in the given document data, i.e. it
+    /// is visible and either `required` or its `required_when` condition holds.
+    pub fn is_required_for(&self, data: &HashMap<String, Value>, form: &FormDefinition) -> bool {
+        let required = self.required
+            || self
+                .required_when
+                .as_ref()
+                .is_some_and(|condition| condition.evaluate(data, form).unwrap_or(false));
+        required && self.is_visible(data, form)
+    }
+}
+
+impl FieldDefinition {
+    /// Checks that the field's defaults can produce valid values.
+    ///
+    /// Runs once the pattern is compiled, as a static default must match it.
+    fn validate_defaults(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(value) = &self.default {
+            if self.default_from.is_some() {
+                errors.add("default", ValidationError::new("conflicting_defaults"));
+            } else if !self.accepts_default(value) {
+                let mut err = ValidationError::new("invalid_default");
+                err.add_param("default".into(), value);
+                err.add_param("kind".into(), &self.field_type.kind());
+                errors.add("default", err);
+            }
+        }
+
+        if let Some(dynamic) = &self.default_from
+            && !dynamic.applies_to(&self.field_type)
+        {
+            let mut err = ValidationError::new("invalid_default_from");
+            err.add_param("default_from".into(), dynamic);
+            err.add_param("kind".into(), &self.field_type.kind());
+            errors.add("default_from", err);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns true if a static default is a valid value for this field.
+    ///
+    /// Relative bounds (such as `today`) are not checked, as they depend on when a
+    /// document is created. `validate_document` still checks them once the default
+    /// has been applied.
+    fn accepts_default(&self, value: &Value) -> bool {
+        match &self.field_type {
+            FieldType::Text {
+                min_length,
+                max_length,
+                ..
+            }
+            | FieldType::TextArea {
+                min_length,
+                max_length,
+                ..
+            } => value.as_str().is_some_and(|s| {
+                within(s.chars().count(), *min_length, *max_length)
+                    && self.pattern.as_ref().is_none_or(|p| p.is_match(s))
+            }),
+            FieldType::Number { min, max } => value.as_f64().is_some_and(|n| within(n, *min, *max)),
+            FieldType::Integer { min, max } => {
+                value.as_i64().is_some_and(|n| within(n, *min, *max))
+            }
+            FieldType::Decimal {
+                precision,
+                scale,
+                min,
+                max,
+            } => {
+                let text = match value {
+                    Value::String(s) => Some(s.trim().to_string()),
+                    Value::Number(n) => Some(n.to_string()),
+                    _ => None,
+                };
+                text.and_then(|t| parse_decimal(&t)).is_some_and(|d| {
+                    let d = d.normalize();
+                    d.scale() <= *scale
+                        && integer_digits(d) <= precision - scale
+                        && within(d, *min, *max)
+                })
+            }
+            FieldType::Boolean => value.is_boolean(),
+            FieldType::DateTime => value
+                .as_str()
+                .is_some_and(|s| DateTime::parse_from_rfc3339(s).is_ok()),
+            FieldType::Date { min, max } => {
+                let fixed = |b: &Option<DateBound>| match b {
+                    Some(DateBound::Date(date)) => Some(*date),
+                    _ => None,
+                };
+                value
+                    .as_str()
+                    .and_then(parse_date)
+                    .is_some_and(|d| within(d, fixed(min), fixed(max)))
+            }
+            FieldType::Time { min, max } => {
+                let fixed = |b: &Option<TimeBound>| match b {
+                    Some(TimeBound::Time(time)) => Some(*time),
+                    _ => None,
+                };
+                value
+                    .as_str()
+                    .and_then(parse_time)
+                    .is_some_and(|t| within(t, fixed(min), fixed(max)))
+            }
+            FieldType::Duration { min, max } => value
+                .as_str()
+                .and_then(|s| s.parse::<IsoDuration>().ok())
+                .is_some_and(|d| within(d, *min, *max)),
+            FieldType::Select {
+                options,
+                allow_multiple,
+            } => {
+                let is_option =
+                    |v: &Value| v.as_str().is_some_and(|s| options.iter().any(|o| o == s));
+                if *allow_multiple {
+                    value
+                        .as_array()
+                        .is_some_and(|items| items.iter().all(is_option))
+                } else {
+                    is_option(value)
+                }
+            }
+            // Whether the documents exist is checked when a document is saved
+            FieldType::User { allow_multiple } | FieldType::Reference { allow_multiple, .. } => {
+                if *allow_multiple {
+                    value
+                        .as_array()
+                        .is_some_and(|items| items.iter().all(Value::is_string))
+                } else {
+                    value.is_string()
+                }
+            }
+            // A default would share one upload between every document
+            FieldType::Attachment { .. } => false,
+            // Defaults are declared on the fields of the group instead
+            FieldType::Group { .. } => false,
+        }
+    }
 }
 
 impl TryFrom<FieldBuilder> for FieldDefinition {
     type Error = validator::ValidationErrors;
 
     fn try_from(builder: FieldBuilder) -> Result<Self, Self::Error> {
-        let def = FieldDefinition {
+        let mut def = FieldDefinition {
             id: builder.id,
             label: builder.label,
             field_type: builder.field_type,
             required: builder.required,
+            required_when: builder.required_when,
+            visible_when: builder.visible_when,
             description: builder.description,
+            default: builder.default,
+            default_from: builder.default_from,
+            pattern: None,
+            group: None,
         };
 
         def.validate()?;
 
+        if let FieldType::Text {
+            pattern: Some(pattern),
+            ..
+        }
+        | FieldType::TextArea {
+            pattern: Some(pattern),
+            ..
+        } = &def.field_type
+        {
+            // Already checked by `validate_field_type`
+            def.pattern = compile_pattern(pattern).ok();
+        }
+
+        def.validate_defaults()?;
+
+        if let FieldType::Group { fields, .. } = &def.field_type {
+            let group = FormDefinition::for_group(&def.id, &def.label, fields.clone())?;
+            def.group = Some(Box::new(group));
+        }
+
         Ok(def)
     }
 }
@@ -168,7 +884,15 @@ pub struct FieldBuilder {
     field_type: FieldType,
     #[serde(default)]
     required: bool,
+    #[serde(default)]
+    required_when: Option<Expression>,
+    #[serde(default)]
+    visible_when: Option<Expression>,
     description: Option<String>,
+    #[serde(default)]
+    default: Option<Value>,
+    #[serde(default)]
+    default_from: Option<DynamicDefault>,
 }
 
 impl FieldBuilder {
@@ -179,7 +903,11 @@ impl FieldBuilder {
             label: label.to_string(),
             field_type,
             required: false,
+            required_when: None,
+            visible_when: None,
             description: None,
+            default: None,
+            default_from: None,
         }
     }
 
@@ -189,12 +917,36 @@ impl FieldBuilder {
         self
     }
 
+    /// Makes the field required whenever the condition holds.
+    pub fn required_when(mut self, condition: Expression) -> Self {
+        self.required_when = Some(condition);
+        self
+    }
+
+    /// Hides the field whenever the condition does not hold.
+    pub fn visible_when(mut self, condition: Expression) -> Self {
+        self.visible_when = Some(condition);
+        self
+    }
+
     /// Adds a description/tooltip.
     pub fn with_description(mut self, description: &str) -> Self {
         self.description = Some(description.to_string());
         self
     }
 
+    /// Sets a fixed value used when a document is created without this field.
+    pub fn with_default(mut self, value: Value) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    /// Sets a value computed when a document is created without this field.
+    pub fn with_default_from(mut self, default: DynamicDefault) -> Self {
+        self.default_from = Some(default);
+        self
+    }
+
     /// Creates a validated FieldDefinition entity using the builder pattern
     pub fn build(self) -> Result<FieldDefinition, validator::ValidationErrors> {
         FieldDefinition::try_from(self)
@@ -208,28 +960,328 @@ mod tests {
 
     #[test]
     fn test_field_builder() {
-        let field = FieldBuilder::new("test_id", "Test Label", FieldType::Text)
-            .required(true)
-            .with_description("A test field")
-            .build()
-            .expect("Field builder should produce a valid FieldDefinition here.");
+        let field = FieldBuilder::new(
+            "test_id",
+            "Test Label",
+            FieldType::Text {
+                min_length: None,
+                max_length: None,
+                pattern: None,
+            },
+        )
+        .required(true)
+        .with_description("A test field")
+        .build()
+        .expect("Field builder should produce a valid FieldDefinition here.");
 
         assert_eq!(field.id, "test_id");
-        assert_eq!(field.required, true);
+        assert!(field.required);
         assert_eq!(field.description, Some("A test field".to_string()));
-        assert!(matches!(field.field_type, FieldType::Text));
+        assert!(matches!(field.field_type, FieldType::Text { .. }));
     }
 
     #[test]
     fn test_serialization_text() {
-        // Test simple unit variant (Text)
-        let field_type = FieldType::Text;
+        // Test struct variant (Text), which carries its constraints in `config`
+        let field_type = FieldType::Text {
+            min_length: None,
+            max_length: Some(80),
+            pattern: None,
+        };
         let json = serde_json::to_value(&field_type).unwrap();
 
-        // Adjacently tagged unit variants usually serialize to just the tag object
-        // or tag with null config depending on specific serde behavior.
+        // Adjacently tagged struct variants serialize their fields under `config`.
         // Let's verify exact output.
-        assert_eq!(json, json!({ "kind": "text" }));
+        assert_eq!(
+            json,
+            json!({
+                "kind": "text",
+                "config": { "min_length": null, "max_length": 80, "pattern": null }
+            })
+        );
+        let round_trip: FieldType = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip, field_type);
+    }
+
+    #[test]
+    fn test_serialization_boolean() {
+        // Test simple unit variant (Boolean)
+        let field_type = FieldType::Boolean;
+        let json = serde_json::to_value(&field_type).unwrap();
+
+        // Adjacently tagged unit variants serialize to just the tag object
+        assert_eq!(json, json!({ "kind": "boolean" }));
+        let round_trip: FieldType = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip, field_type);
+    }
+
+    #[test]
+    fn test_deserialization_legacy_text_area() {
+        // Text kinds stored before they had constraints omit `config` entirely
+        let legacy: FieldType = serde_json::from_value(json!({ "kind": "text_area" })).unwrap();
+        assert_eq!(
+            legacy,
+            FieldType::TextArea {
+                min_length: None,
+                max_length: None,
+                pattern: None
+            }
+        );
+        let round_trip: FieldType =
+            serde_json::from_value(serde_json::to_value(&legacy).unwrap()).unwrap();
+        assert_eq!(round_trip, legacy);
+    }
+
+    #[test]
+    fn test_numeric_types() {
+        let json_input = json!({
+            "id": "amount",
+            "label": "Amount",
+            "field_type": {
+                "kind": "decimal",
+                "config": { "precision": 10, "scale": 2, "min": "0.00", "max": 5000 }
+            }
+        });
+        let field: FieldDefinition = serde_json::from_value(json_input).unwrap();
+        match field.field_type() {
+            FieldType::Decimal { min, max, .. } => {
+                assert_eq!(*min, Some(Decimal::ZERO));
+                assert_eq!(*max, Some(Decimal::from(5000)));
+            }
+            _ => panic!("Wrong field type deserialized"),
+        }
+
+        let decimal = |precision, scale| FieldType::Decimal {
+            precision,
+            scale,
+            min: None,
+            max: None,
+        };
+        for (precision, scale) in [(0, 0), (29, 2), (4, 5)] {
+            let err = FieldBuilder::new("amount", "Amount", decimal(precision, scale))
+                .build()
+                .unwrap_err();
+            assert!(err.to_string().contains("invalid_decimal_precision"));
+        }
+
+        let err = FieldBuilder::new(
+            "qty",
+            "Quantity",
+            FieldType::Integer {
+                min: Some(10),
+                max: Some(1),
+            },
+        )
+        .build()
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid_range"));
+
+        assert_eq!(parse_decimal("-12.50"), Some(Decimal::new(-1250, 2)));
+        assert_eq!(parse_decimal("1.25e3"), Some(Decimal::from(1250)));
+        assert_eq!(parse_decimal("12,50"), None);
+    }
+
+    #[test]
+    fn test_defaults_checked() {
+        let severity = || FieldType::Integer {
+            min: Some(1),
+            max: Some(5),
+        };
+
+        assert!(
+            FieldBuilder::new("severity", "Severity", severity())
+                .with_default(json!(3))
+                .build()
+                .is_ok()
+        );
+        for invalid in [json!(9), json!("3"), json!(2.5)] {
+            let err = FieldBuilder::new("severity", "Severity", severity())
+                .with_default(invalid)
+                .build()
+                .unwrap_err();
+            assert!(err.to_string().contains("invalid_default"));
+        }
+
+        let err = FieldBuilder::new("severity", "Severity", severity())
+            .with_default_from(DynamicDefault::Now)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid_default_from"));
+
+        let err = FieldBuilder::new("severity", "Severity", severity())
+            .with_default(json!(3))
+            .with_default_from(DynamicDefault::CopyField {
+                field: "priority".into(),
+            })
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("conflicting_defaults"));
+    }
+
+    #[test]
+    fn test_text_constraints() {
+        let text = |min_length, max_length, pattern: Option<&str>| FieldType::Text {
+            min_length,
+            max_length,
+            pattern: pattern.map(str::to_string),
+        };
+
+        let field = FieldBuilder::new("case", "Case Number", text(None, None, Some(r"QA-\d{5}")))
+            .build()
+            .unwrap();
+        let pattern = field.pattern().expect("pattern should be compiled");
+        assert!(pattern.is_match("QA-12345"));
+        // The pattern must match the whole value
+        assert!(!pattern.is_match("QA-123456"));
+        assert!(!pattern.is_match("Ref QA-12345"));
+
+        // Invalid patterns are rejected when the field is built
+        let err = FieldBuilder::new("case", "Case Number", text(None, None, Some("QA-(")))
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid_pattern"));
+
+        let err = FieldBuilder::new("case", "Case Number", text(Some(5), Some(2), None))
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid_length_range"));
+
+        // The compiled pattern is restored when a definition is deserialized
+        let json = serde_json::to_value(
+            FieldBuilder::new("case", "Case Number", text(Some(1), None, Some(r"\d+")))
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        let field: FieldDefinition = serde_json::from_value(json).unwrap();
+        assert!(field.pattern().unwrap().is_match("42"));
+    }
+
+    #[test]
+    fn test_groups() {
+        let json_input = json!({
+            "id": "parts",
+            "label": "Affected Parts",
+            "field_type": {
+                "kind": "group",
+                "config": {
+                    "fields": [
+                        { "id": "part_number", "label": "Part Number", "field_type": { "kind": "text" } },
+                        { "id": "qty", "label": "Quantity", "field_type": { "kind": "integer", "config": { "min": 1 } } }
+                    ],
+                    "min_items": 1,
+                    "repeatable": true
+                }
+            }
+        });
+
+        let field: FieldDefinition = serde_json::from_value(json_input).unwrap();
+        let group = field.group().expect("Group fields have an item form");
+        assert_eq!(group.fields().len(), 2);
+        assert_eq!(
+            serde_json::from_value::<FieldDefinition>(serde_json::to_value(&field).unwrap())
+                .unwrap(),
+            field
+        );
+
+        // Field IDs must be unique within the group
+        let qty = group.fields()[1].clone();
+        let group_of = |fields: Vec<FieldDefinition>, min_items, repeatable| {
+            FieldBuilder::new(
+                "parts",
+                "Parts",
+                FieldType::Group {
+                    fields,
+                    min_items,
+                    max_items: None,
+                    repeatable,
+                },
+            )
+            .build()
+        };
+        let err = group_of(vec![qty.clone(), qty.clone()], None, true).unwrap_err();
+        assert!(err.to_string().contains("duplicate_field_id"));
+
+        let err = group_of(vec![], None, true).unwrap_err();
+        assert!(err.to_string().contains("empty_group"));
+        let err = group_of(vec![qty], Some(1), false).unwrap_err();
+        assert!(err.to_string().contains("item_bounds_on_single_group"));
+    }
+
+    #[test]
+    fn test_user_fields() {
+        let user = |allow_multiple| FieldType::User { allow_multiple };
+
+        let reporter = FieldBuilder::new("reporter", "Reporter", user(false))
+            .with_default_from(DynamicDefault::CurrentActor)
+            .build()
+            .unwrap();
+        assert_eq!(reporter.field_type().kind(), "user");
+
+        // A list of users cannot default to a single actor
+        let err = FieldBuilder::new("reviewers", "Reviewers", user(true))
+            .with_default_from(DynamicDefault::CurrentActor)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid_default_from"));
+
+        let reviewers = FieldBuilder::new("reviewers", "Reviewers", user(true))
+            .with_default(json!(["qa_lead"]))
+            .build()
+            .unwrap();
+        assert_eq!(reviewers.default_value(), Some(&json!(["qa_lead"])));
+    }
+
+    #[test]
+    fn test_attachments() {
+        let json_input = json!({
+            "id": "photos",
+            "label": "Photos",
+            "field_type": {
+                "kind": "attachment",
+                "config": {
+                    "max_size": 10485760,
+                    "allowed_mime_types": ["image/*", "application/pdf"],
+                    "allow_multiple": true
+                }
+            }
+        });
+        let field: FieldDefinition = serde_json::from_value(json_input).unwrap();
+        assert_eq!(field.field_type().kind(), "attachment");
+
+        let attachment = |max_size, allowed: &[&str]| {
+            FieldBuilder::new(
+                "photos",
+                "Photos",
+                FieldType::Attachment {
+                    max_size,
+                    allowed_mime_types: allowed.iter().map(|m| m.to_string()).collect(),
+                    allow_multiple: false,
+                },
+            )
+            .build()
+        };
+        let err = attachment(Some(0), &[]).unwrap_err();
+        assert!(err.to_string().contains("invalid_max_size"));
+        for invalid in ["image", "*/*", "image/", "image/jpeg; q=1"] {
+            let err = attachment(None, &[invalid]).unwrap_err();
+            assert!(err.to_string().contains("invalid_mime_type"));
+        }
+
+        // Attachments cannot be defaulted
+        let err = FieldBuilder::new(
+            "photos",
+            "Photos",
+            FieldType::Attachment {
+                max_size: None,
+                allowed_mime_types: vec![],
+                allow_multiple: false,
+            },
+        )
+        .with_default(json!("att-1"))
+        .build()
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid_default"));
     }
 
     #[test]
@@ -278,7 +1330,7 @@ mod tests {
                 allow_multiple,
             } => {
                 assert_eq!(options, vec!["Open", "Closed"]);
-                assert_eq!(allow_multiple, true);
+                assert!(allow_multiple);
             }
             _ => panic!("Wrong field type deserialized"),
         }
@@ -299,7 +1351,7 @@ mod tests {
         let field: FieldDefinition =
             serde_json::from_value(json_input).expect("Field definition should be valid");
 
-        assert_eq!(field.required, false); // Default is false
+        assert!(!field.required); // Default is false
 
         match field.field_type {
             FieldType::Number { min, max } => {
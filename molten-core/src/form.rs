@@ -2,14 +2,18 @@
 //! which serve as blueprints for documents within the Molten system.
 //!
 //! It includes `FormDefinition` to describe the overall structure of a form,
-//! containing a collection of `FieldDefinition`s and associated validation rules.
-//! The `FormBuilder` is provided for programmatic construction and validation
+//! containing a collection of `FieldDefinition`s and the cross-field `FormRule`s
+//! that relate them. The `FormBuilder` is provided for programmatic construction and validation
 //! of form definitions.
-use crate::field::FieldDefinition;
+use crate::actor::Actor;
+use crate::field::{DynamicDefault, FieldDefinition};
+use crate::rule::{FormRule, RuleKind};
+use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use validator::{Validate, ValidationError};
 
 // Only alphanumeric, hyphens, and underscores
@@ -39,8 +43,28 @@ pub struct FormDefinition {
     /// # Validation
     /// 1. Each field must be valid (nested validation).
     /// 2. Field IDs must be unique within the form (custom validation).
-    #[validate(nested, custom(function = "validate_unique_field_ids"))]
+    /// 3. Defaults copied from another field must name a field of the same type, which
+    ///    does not itself copy its default (custom validation).
+    /// 4. `required_when` and `visible_when` conditions may only reference fields of this
+    ///    form, must type check against it, and may not depend on themselves (checked when
+    ///    the form is built).
+    #[validate(
+        nested,
+        custom(function = "validate_unique_field_ids"),
+        custom(function = "validate_default_sources")
+    )]
     fields: Vec<FieldDefinition>,
+
+    /// Cross-field rules that every document of this form must satisfy.
+    ///
+    /// # Validation
+    /// 1. Each rule must be valid (nested validation).
+    /// 2. Rule IDs must be unique within the form (custom validation).
+    /// 3. Rules may only reference fields of this form, and their conditions must type
+    ///    check against it (checked when the form is built).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[validate(nested, custom(function = "validate_unique_rule_ids"))]
+    rules: Vec<FormRule>,
 }
 
 /// Custom validator to ensure no two fields share the same ID.
@@ -57,6 +81,202 @@ fn validate_unique_field_ids(fields: &[FieldDefinition]) -> Result<(), Validatio
     Ok(())
 }
 
+/// Custom validator to ensure no two rules share the same ID.
+fn validate_unique_rule_ids(rules: &[FormRule]) -> Result<(), ValidationError> {
+    let mut seen = HashSet::new();
+    for rule in rules {
+        if !seen.insert(rule.id.as_str()) {
+            let mut err = ValidationError::new("duplicate_rule_id");
+            err.add_param("duplicate_id".into(), &rule.id);
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// Checks the field references of every rule against the fields of its form.
+///
+/// Ensures that rules only name existing fields, that `at_least_one_of` rules name at
+/// least one field, and that rule conditions compare values of compatible types.
+///
+/// # Returns
+/// A `Result` which is `Ok` if all rules are valid for the form, or `Err` with
+/// `validator::ValidationErrors` describing every invalid rule.
+fn validate_rule_references(form: &FormDefinition) -> Result<(), validator::ValidationErrors> {
+    let field_ids: HashSet<&str> = form.fields.iter().map(|f| f.id()).collect();
+
+    let mut errors = validator::ValidationErrors::new();
+
+    for rule in form.rules.iter() {
+        for field_id in rule.fields() {
+            if !field_ids.contains(field_id) {
+                let mut err = ValidationError::new("unknown_rule_field");
+                err.add_param("rule".into(), &rule.id);
+                err.add_param("field_id".into(), &field_id);
+                errors.add("rules", err);
+            }
+        }
+
+        let condition = match &rule.kind {
+            RuleKind::Condition { condition } | RuleKind::RequiredWhen { condition, .. } => {
+                condition
+            }
+            RuleKind::AtLeastOneOf { fields } => {
+                if fields.is_empty() {
+                    let mut err = ValidationError::new("empty_rule_fields");
+                    err.add_param("rule".into(), &rule.id);
+                    errors.add("rules", err);
+                }
+                continue;
+            }
+        };
+        // Unknown fields are already reported above
+        if let Err(e) = condition.check(form)
+            && condition.fields().iter().all(|f| field_ids.contains(f))
+        {
+            let mut err = ValidationError::new("invalid_rule_condition");
+            err.add_param("rule".into(), &rule.id);
+            err.add_param("condition".into(), &condition.source());
+            err.add_param("error".into(), &e.to_string());
+            errors.add("rules", err);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks the `required_when` and `visible_when` conditions of every field.
+///
+/// Ensures that conditions only reference existing fields and compare values of
+/// compatible types, and that no field's conditions depend on the field itself, directly
+/// or through the conditions of other fields.
+///
+/// # Returns
+/// A `Result` which is `Ok` if all conditions are valid for the form, or `Err` with
+/// `validator::ValidationErrors` describing every invalid condition.
+fn validate_field_conditions(form: &FormDefinition) -> Result<(), validator::ValidationErrors> {
+    let field_ids: HashSet<&str> = form.fields.iter().map(|f| f.id()).collect();
+
+    let mut errors = validator::ValidationErrors::new();
+    let mut dependencies: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for field in form.fields.iter() {
+        let conditions = [
+            ("required_when", field.required_when()),
+            ("visible_when", field.visible_when()),
+        ];
+        for (name, condition) in conditions {
+            let Some(condition) = condition else {
+                continue;
+            };
+
+            let unknown: Vec<&str> = condition
+                .fields()
+                .into_iter()
+                .filter(|f| !field_ids.contains(f))
+                .collect();
+            let mut err = if !unknown.is_empty() {
+                let mut err = ValidationError::new("unknown_condition_field");
+                err.add_param("field_ids".into(), &unknown);
+                err
+            } else if let Err(e) = condition.check(form) {
+                let mut err = ValidationError::new("invalid_field_condition");
+                err.add_param("error".into(), &e.to_string());
+                err
+            } else {
+                dependencies
+                    .entry(field.id())
+                    .or_default()
+                    .extend(condition.fields());
+                continue;
+            };
+            err.add_param("field".into(), &field.id());
+            err.add_param("condition".into(), &condition.source());
+            errors.add(name, err);
+        }
+    }
+
+    let mut done = HashSet::new();
+    for field in form.fields.iter() {
+        if let Some(cycle) = find_cycle(field.id(), &dependencies, &mut Vec::new(), &mut done) {
+            let mut err = ValidationError::new("condition_cycle");
+            err.add_param("fields".into(), &cycle);
+            errors.add("fields", err);
+            break;
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Walks the condition dependencies from `id`, returning the first cycle found as the
+/// path of field IDs from a field back to itself.
+fn find_cycle<'a>(
+    id: &'a str,
+    dependencies: &HashMap<&'a str, Vec<&'a str>>,
+    path: &mut Vec<&'a str>,
+    done: &mut HashSet<&'a str>,
+) -> Option<Vec<&'a str>> {
+    if let Some(start) = path.iter().position(|p| *p == id) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(id);
+        return Some(cycle);
+    }
+    if done.contains(id) {
+        return None;
+    }
+
+    path.push(id);
+    for dependency in dependencies.get(id).into_iter().flatten() {
+        if let Some(cycle) = find_cycle(dependency, dependencies, path, done) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    done.insert(id);
+    None
+}
+
+/// Custom validator to ensure defaults are only copied from suitable fields.
+fn validate_default_sources(fields: &[FieldDefinition]) -> Result<(), ValidationError> {
+    for field in fields {
+        let Some(DynamicDefault::CopyField { field: source_id }) = field.default_from() else {
+            continue;
+        };
+
+        let mut err = match fields.iter().find(|f| f.id() == source_id) {
+            None => ValidationError::new("unknown_default_source"),
+            Some(source) if source.id() == field.id() => {
+                ValidationError::new("self_referencing_default")
+            }
+            Some(source) if source.field_type().kind() != field.field_type().kind() => {
+                ValidationError::new("default_source_type_mismatch")
+            }
+            Some(source)
+                if matches!(
+                    source.default_from(),
+                    Some(DynamicDefault::CopyField { .. })
+                ) =>
+            {
+                ValidationError::new("chained_default_copy")
+            }
+            Some(_) => continue,
+        };
+        err.add_param("field".into(), &field.id());
+        err.add_param("source".into(), source_id);
+        return Err(err);
+    }
+    Ok(())
+}
+
 impl FormDefinition {
     /// ID getter
     pub fn id(&self) -> &str {
@@ -74,6 +294,119 @@ impl FormDefinition {
     pub fn fields(&self) -> &[FieldDefinition] {
         &self.fields
     }
+    /// Rules getter
+    pub fn rules(&self) -> &[FormRule] {
+        &self.rules
+    }
+
+    /// Fills in the defaults of every field that is missing or null in `data`.
+    ///
+    /// Static and computed defaults are applied first, then defaults copied from other
+    /// fields, so that a field can copy another field's default. Fields without a value
+    /// or default are left untouched, for `validate_document` to report if required.
+    /// Defaults of fields hidden by their `visible_when` condition are not applied.
+    ///
+    /// # Arguments
+    /// * `data` - The document data to complete.
+    /// * `actor` - The actor creating the document, for `current_actor` defaults.
+    /// * `now` - The moment the document is created, for `now` and `today` defaults.
+    pub fn apply_defaults(
+        &self,
+        data: &mut HashMap<String, Value>,
+        actor: &Actor,
+        now: DateTime<Utc>,
+    ) {
+        let is_missing =
+            |data: &HashMap<String, Value>, id: &str| data.get(id).is_none_or(Value::is_null);
+
+        let mut defaulted = HashSet::new();
+        for field in &self.fields {
+            if !is_missing(data, field.id()) {
+                continue;
+            }
+            let value = match (field.default_value(), field.default_from()) {
+                (Some(value), _) => Some(value.clone()),
+                (None, Some(dynamic)) => dynamic.resolve(field.field_type(), actor, now),
+                (None, None) => None,
+            };
+            if let Some(value) = value {
+                data.insert(field.id().to_string(), value);
+                defaulted.insert(field.id());
+            }
+        }
+
+        for field in &self.fields {
+            if let Some(DynamicDefault::CopyField { field: source }) = field.default_from()
+                && is_missing(data, field.id())
+                && let Some(value) = data.get(source).filter(|v| !v.is_null()).cloned()
+            {
+                data.insert(field.id().to_string(), value);
+                defaulted.insert(field.id());
+            }
+        }
+
+        // Visibility is decided once every default is in place
+        let hidden: Vec<&str> = self
+            .fields
+            .iter()
+            .filter(|f| defaulted.contains(f.id()) && !f.is_visible(data, self))
+            .map(|f| f.id())
+            .collect();
+        for id in hidden {
+            data.remove(id);
+        }
+
+        // Each item of a group is completed like a document of its own
+        for field in &self.fields {
+            if let Some(group) = field.group()
+                && let Some(value) = data.get_mut(field.id())
+            {
+                let items = match value {
+                    Value::Array(items) => items.iter_mut().collect(),
+                    item => vec![item],
+                };
+                for item in items {
+                    if let Value::Object(map) = item {
+                        let mut item_data: HashMap<String, Value> =
+                            std::mem::take(map).into_iter().collect();
+                        group.apply_defaults(&mut item_data, actor, now);
+                        *map = item_data.into_iter().collect();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Builds the form describing one item of a `Group` field.
+    ///
+    /// The group's fields get the same checks as the fields of a form, except that the
+    /// form itself has no ID or name of its own to check.
+    pub(crate) fn for_group(
+        field_id: &str,
+        label: &str,
+        fields: Vec<FieldDefinition>,
+    ) -> Result<Self, validator::ValidationErrors> {
+        let group = FormDefinition {
+            id: field_id.to_string(),
+            name: label.to_string(),
+            version: 1,
+            fields,
+            rules: Vec::new(),
+        };
+
+        let mut errors = validator::ValidationErrors::new();
+        for check in [validate_unique_field_ids, validate_default_sources] {
+            if let Err(err) = check(&group.fields) {
+                errors.add("fields", err);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        validate_field_conditions(&group)?;
+        Ok(group)
+    }
 }
 
 /// Builder for constructing validated [`FormDefinition`] instances.
@@ -89,6 +422,9 @@ pub struct FormBuilder {
     #[serde(default)]
     /// The list of field definitions that make up this form.
     pub fields: Vec<FieldDefinition>,
+    #[serde(default)]
+    /// The cross-field rules that documents of this form must satisfy.
+    pub rules: Vec<FormRule>,
 }
 
 /// Provides the default version number for a form, which is `1`.
@@ -98,13 +434,14 @@ fn default_version() -> u32 {
 
 impl FormBuilder {
     /// Creates a new `FormBuilder` instance with the given ID and name,
-    /// defaulting the version to 1 and fields to an empty list.
+    /// defaulting the version to 1 and fields and rules to empty lists.
     pub fn new(id: &str, name: &str) -> Self {
         Self {
             id: id.to_string(),
             name: name.to_string(),
             version: 1,
             fields: Vec::new(),
+            rules: Vec::new(),
         }
     }
 
@@ -126,6 +463,12 @@ impl FormBuilder {
         self
     }
 
+    /// Adds a cross-field `FormRule` to the form.
+    pub fn add_rule(mut self, rule: FormRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
     /// Builds a validated `FormDefinition` from the `FormBuilder` instance.
     ///
     /// # Returns
@@ -145,9 +488,12 @@ impl TryFrom<FormBuilder> for FormDefinition {
             name: builder.name,
             version: builder.version,
             fields: builder.fields,
+            rules: builder.rules,
         };
 
         form.validate()?;
+        validate_field_conditions(&form)?;
+        validate_rule_references(&form)?;
         Ok(form)
     }
 }
@@ -159,9 +505,17 @@ mod tests {
     use serde_json::json;
 
     fn create_field(id: &str) -> FieldDefinition {
-        FieldBuilder::new(id, "Label", FieldType::Text)
-            .build()
-            .unwrap()
+        FieldBuilder::new(
+            id,
+            "Label",
+            FieldType::Text {
+                min_length: None,
+                max_length: None,
+                pattern: None,
+            },
+        )
+        .build()
+        .unwrap()
     }
 
     #[test]
@@ -203,6 +557,225 @@ mod tests {
         assert!(err.to_string().contains("duplicate_field_id"));
     }
 
+    fn text() -> FieldType {
+        FieldType::Text {
+            min_length: None,
+            max_length: None,
+            pattern: None,
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        let text = text();
+        let form = FormBuilder::new("incident", "Incident")
+            .add_field(
+                FieldBuilder::new("reported_by", "Reported By", text.clone())
+                    .with_default_from(DynamicDefault::CurrentActor)
+                    .build()
+                    .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new("owner", "Owner", text.clone())
+                    .with_default_from(DynamicDefault::CopyField {
+                        field: "reported_by".into(),
+                    })
+                    .build()
+                    .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "occurred_on",
+                    "Occurred On",
+                    FieldType::Date {
+                        min: None,
+                        max: None,
+                    },
+                )
+                .with_default_from(DynamicDefault::Today)
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new("status", "Status", text.clone())
+                    .with_default(json!("New"))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let mut data = HashMap::from([("status".to_string(), json!("Triaged"))]);
+        let now: DateTime<Utc> = "2026-10-16T09:00:00Z".parse().unwrap();
+        form.apply_defaults(&mut data, &Actor::new("alice", &[]), now);
+
+        assert_eq!(data["reported_by"], json!("alice"));
+        assert_eq!(data["owner"], json!("alice"));
+        assert_eq!(data["occurred_on"], json!("2026-10-16"));
+        // Provided values are kept
+        assert_eq!(data["status"], json!("Triaged"));
+
+        // Defaults apply to every item of a group
+        let form = FormBuilder::new("inspection", "Inspection")
+            .add_field(
+                FieldBuilder::new(
+                    "parts",
+                    "Parts",
+                    FieldType::Group {
+                        fields: vec![
+                            FieldBuilder::new("disposition", "Disposition", text.clone())
+                                .with_default(json!("Hold"))
+                                .build()
+                                .unwrap(),
+                        ],
+                        min_items: None,
+                        max_items: None,
+                        repeatable: true,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let mut data =
+            HashMap::from([("parts".to_string(), json!([{}, { "disposition": "Scrap" }]))]);
+        form.apply_defaults(&mut data, &Actor::new("alice", &[]), now);
+        assert_eq!(
+            data["parts"],
+            json!([{ "disposition": "Hold" }, { "disposition": "Scrap" }])
+        );
+
+        // Copied defaults must name another field of the same type
+        let err = FormBuilder::new("incident", "Incident")
+            .add_field(
+                FieldBuilder::new("owner", "Owner", text)
+                    .with_default_from(DynamicDefault::CopyField {
+                        field: "assignee".into(),
+                    })
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown_default_source"));
+    }
+
+    #[test]
+    fn test_field_conditions() {
+        use crate::expression::Expression;
+
+        let conditional = |id: &str, visible_when: &str| {
+            FieldBuilder::new(id, "Label", FieldType::Boolean)
+                .visible_when(Expression::parse(visible_when).unwrap())
+                .build()
+                .unwrap()
+        };
+        let injury_occurred = FieldBuilder::new("injury_occurred", "Injury", FieldType::Boolean)
+            .build()
+            .unwrap();
+        let injury_description = FieldBuilder::new("injury_description", "Description", text())
+            .required_when(Expression::parse("injury_occurred == true").unwrap())
+            .visible_when(Expression::parse("injury_occurred == true").unwrap())
+            .with_default(json!("None reported"))
+            .build()
+            .unwrap();
+
+        let form = FormBuilder::new("incident", "Incident")
+            .add_field(injury_occurred)
+            .add_field(injury_description)
+            .build()
+            .unwrap();
+
+        // Defaults are not applied to hidden fields
+        let mut data = HashMap::from([("injury_occurred".to_string(), json!(false))]);
+        let now: DateTime<Utc> = "2026-10-16T09:00:00Z".parse().unwrap();
+        form.apply_defaults(&mut data, &Actor::new("alice", &[]), now);
+        assert!(!data.contains_key("injury_description"));
+        let description = &form.fields()[1];
+        assert!(!description.is_required_for(&data, &form));
+
+        data.insert("injury_occurred".into(), json!(true));
+        assert!(description.is_required_for(&data, &form));
+
+        // Conditions may only reference fields of the form
+        let err = FormBuilder::new("incident", "Incident")
+            .add_field(conditional("witnessed", "injury_occurred == true"))
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown_condition_field"));
+
+        // Conditions may not depend on themselves
+        let err = FormBuilder::new("incident", "Incident")
+            .add_field(conditional("a", "b == true"))
+            .add_field(conditional("b", "c == true"))
+            .add_field(conditional("c", "a == true"))
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("condition_cycle"));
+    }
+
+    #[test]
+    fn test_form_rules() {
+        use crate::expression::Expression;
+
+        let datetime = |id: &str| {
+            FieldBuilder::new(id, "Label", FieldType::DateTime)
+                .build()
+                .unwrap()
+        };
+        let closed_after_opened = |condition: &str| {
+            FormRule::new(
+                "closed_after_opened",
+                "The incident must be closed after it was opened",
+                RuleKind::Condition {
+                    condition: Expression::parse(condition).unwrap(),
+                },
+            )
+        };
+        let builder = FormBuilder::new("incident", "Incident")
+            .add_field(datetime("opened_at"))
+            .add_field(datetime("closed_at"))
+            .add_field(create_field("email"));
+
+        let form = builder
+            .clone()
+            .add_rule(closed_after_opened("closed_at > opened_at"))
+            .build()
+            .unwrap();
+        assert_eq!(form.rules().len(), 1);
+
+        // Rules may only reference fields of the form
+        let err = builder
+            .clone()
+            .add_rule(FormRule::new(
+                "contact",
+                "Provide a way to contact you",
+                RuleKind::AtLeastOneOf {
+                    fields: vec!["email".into(), "phone".into()],
+                },
+            ))
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown_rule_field"));
+
+        // Conditions must type check
+        let err = builder
+            .clone()
+            .add_rule(closed_after_opened("closed_at > email"))
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid_rule_condition"));
+
+        // Rule IDs must be unique
+        let err = builder
+            .add_rule(closed_after_opened("closed_at > opened_at"))
+            .add_rule(closed_after_opened("closed_at >= opened_at"))
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("duplicate_rule_id"));
+    }
+
     #[test]
     fn test_serde_integration() {
         let json_input = json!({
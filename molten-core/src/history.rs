@@ -0,0 +1,37 @@
+//! This module defines the `TransitionRecord` struct, an entry in a document's
+//! audit trail.
+//!
+//! Every time a document changes phase, a `TransitionRecord` captures where it
+//! came from, where it went, which transition was taken, who took it and when.
+//! Records are append-only: they are never modified once written.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A single phase change in the history of a document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransitionRecord {
+    /// Unique identifier for this record (usually a UUID).
+    pub id: String,
+
+    /// The document that changed phase.
+    pub document_id: String,
+
+    /// The phase the document left. `None` when the document was created and
+    /// entered its workflow's start phase.
+    pub from_phase: Option<String>,
+
+    /// The phase the document entered.
+    pub to_phase: String,
+
+    /// The name of the transition that was taken. `None` when the document was created.
+    pub transition: Option<String>,
+
+    /// The ID of the actor who moved the document.
+    pub actor: String,
+
+    /// An optional free-text comment supplied with the transition.
+    pub comment: Option<String>,
+
+    /// When the document changed phase.
+    pub created_at: DateTime<Utc>,
+}
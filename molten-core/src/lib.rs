@@ -9,12 +9,27 @@
 
 #![warn(missing_docs)]
 
+pub mod actor;
+pub mod attachment;
+pub mod compat;
 pub mod document;
+pub mod expression;
 pub mod field;
 pub mod form;
+pub mod history;
+pub mod query;
+pub mod rule;
+pub mod temporal;
 pub mod workflow;
 
-pub use document::Document;
+pub use actor::Actor;
+pub use attachment::{Attachment, BlobStore};
+pub use compat::{CompatibilityReport, compare_forms};
+pub use document::{Document, DocumentReference};
+pub use expression::Expression;
 pub use field::{FieldBuilder, FieldDefinition, FieldType};
 pub use form::{FormBuilder, FormDefinition};
+pub use history::TransitionRecord;
+pub use query::{DataFilter, DocumentPage, DocumentQuery};
+pub use rule::FormRule;
 pub use workflow::{Phase, Transition, WorkflowBuilder, WorkflowDefinition};
@@ -0,0 +1,737 @@
+//! This module defines `DocumentQuery`, the storage-agnostic description of a
+//! document listing request.
+//!
+//! A query combines filters on document metadata with a sort order and a page
+//! size. Results are paginated with keyset cursors: a `Cursor` records the sort
+//! key and ID of the last document returned, and the next page starts strictly
+//! after it. Unlike offsets, cursors keep pages stable while new documents are
+//! being inserted.
+//!
+//! Queries may also filter on the dynamic document data with `DataFilter`s,
+//! written as `field:op:value` (e.g. `severity:gte:4`). Filters are typed by
+//! the `FormDefinition`: each operator is only available on the field types it
+//! makes sense for, and the value is parsed as the field's type.
+use crate::document::Document;
+use crate::field::{FieldType, parse_decimal};
+use crate::form::FormDefinition;
+use crate::temporal::{IsoDuration, parse_date, parse_time};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The number of documents returned per page when no limit is given.
+pub const DEFAULT_PAGE_SIZE: u64 = 50;
+
+/// The largest number of documents that may be requested per page.
+pub const MAX_PAGE_SIZE: u64 = 200;
+
+/// Errors raised when a query is malformed.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum QueryError {
+    /// The sort order could not be parsed.
+    #[error("Invalid sort '{0}', expected one of created_at, -created_at, updated_at, -updated_at")]
+    InvalidSort(String),
+
+    /// The cursor could not be decoded.
+    #[error("Invalid cursor")]
+    InvalidCursor,
+
+    /// The cursor was issued for a different sort order than the one requested.
+    #[error("Cursor was issued for sorting by '{cursor}', but the query sorts by '{query}'")]
+    CursorSortMismatch {
+        /// The sort field the cursor was issued for.
+        cursor: String,
+        /// The sort field of the query.
+        query: String,
+    },
+
+    /// The page size is out of range.
+    #[error("Invalid limit {0}, expected a value between 1 and {MAX_PAGE_SIZE}")]
+    InvalidLimit(u64),
+
+    /// A data filter is not of the form `field:op:value`, or uses an unknown operator.
+    #[error("Invalid filter '{0}', expected 'field:op:value'")]
+    InvalidFilter(String),
+
+    /// Data filters were given without identifying the form that types them.
+    #[error("Filtering on document data requires a form_id")]
+    FilterRequiresForm,
+
+    /// A data filter refers to a field that does not exist in the form.
+    #[error("Cannot filter on unknown field '{0}'")]
+    UnknownField(String),
+
+    /// A data filter uses an operator that does not apply to the field's type.
+    #[error("Operator '{op}' cannot be applied to {field_type} field '{field}'")]
+    UnsupportedOperator {
+        /// The ID of the filtered field.
+        field: String,
+        /// The operator used.
+        op: String,
+        /// The type of the filtered field.
+        field_type: String,
+    },
+
+    /// A data filter value cannot be a value of the field's type.
+    #[error("Invalid value '{value}' for field '{field}', expected {expected}")]
+    InvalidFilterValue {
+        /// The ID of the filtered field.
+        field: String,
+        /// The value given.
+        value: String,
+        /// A description of the values the field accepts.
+        expected: String,
+    },
+}
+
+/// The document timestamp a listing is ordered by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortField {
+    /// Order by creation time.
+    #[default]
+    CreatedAt,
+    /// Order by last modification time.
+    UpdatedAt,
+}
+
+impl SortField {
+    /// Returns the value of this sort field for a document.
+    pub fn value(&self, doc: &Document) -> DateTime<Utc> {
+        match self {
+            SortField::CreatedAt => doc.created_at,
+            SortField::UpdatedAt => doc.updated_at,
+        }
+    }
+}
+
+impl fmt::Display for SortField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortField::CreatedAt => write!(f, "created_at"),
+            SortField::UpdatedAt => write!(f, "updated_at"),
+        }
+    }
+}
+
+/// The direction of a sort order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    /// Oldest first.
+    Asc,
+    /// Newest first.
+    #[default]
+    Desc,
+}
+
+/// The order documents are listed in. Ties are broken by document ID.
+///
+/// Parsed from `created_at` / `updated_at` for ascending order, prefixed with `-`
+/// for descending order. Defaults to newest first (`-created_at`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DocumentSort {
+    /// The timestamp to order by.
+    pub field: SortField,
+    /// Whether to list oldest or newest first.
+    pub direction: SortDirection,
+}
+
+impl FromStr for DocumentSort {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (direction, name) = match s.strip_prefix('-') {
+            Some(name) => (SortDirection::Desc, name),
+            None => (SortDirection::Asc, s),
+        };
+        let field = match name {
+            "created_at" => SortField::CreatedAt,
+            "updated_at" => SortField::UpdatedAt,
+            _ => return Err(QueryError::InvalidSort(s.to_string())),
+        };
+        Ok(Self { field, direction })
+    }
+}
+
+/// A position in a sorted document listing.
+///
+/// Cursors are opaque to clients: they are handed out encoded as URL-safe strings
+/// and passed back unchanged to fetch the next page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor {
+    /// The sort field the cursor was issued for.
+    pub field: SortField,
+    /// The sort key of the last document on the previous page.
+    pub value: DateTime<Utc>,
+    /// The ID of the last document on the previous page.
+    pub id: String,
+}
+
+impl Cursor {
+    /// Creates a cursor positioned after the given document.
+    pub fn after(doc: &Document, field: SortField) -> Self {
+        Self {
+            field,
+            value: field.value(doc),
+            id: doc.id.clone(),
+        }
+    }
+
+    /// Encodes the cursor as an opaque, URL-safe string.
+    pub fn encode(&self) -> String {
+        let raw = format!(
+            "{}|{}|{}",
+            self.field,
+            self.value.timestamp_micros(),
+            self.id
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    /// Decodes a cursor previously produced by [`Cursor::encode`].
+    pub fn decode(encoded: &str) -> Result<Self, QueryError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| QueryError::InvalidCursor)?;
+        let raw = String::from_utf8(bytes).map_err(|_| QueryError::InvalidCursor)?;
+
+        // The ID goes last, so it may itself contain the separator
+        let mut parts = raw.splitn(3, '|');
+        let (Some(field), Some(value), Some(id)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(QueryError::InvalidCursor);
+        };
+
+        let field = match field {
+            "created_at" => SortField::CreatedAt,
+            "updated_at" => SortField::UpdatedAt,
+            _ => return Err(QueryError::InvalidCursor),
+        };
+        let value = value
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or(QueryError::InvalidCursor)?;
+
+        Ok(Self {
+            field,
+            value,
+            id: id.to_string(),
+        })
+    }
+}
+
+/// A comparison operator of a `DataFilter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    /// Equal to (`eq`).
+    Eq,
+    /// Not equal to, or absent (`ne`).
+    Ne,
+    /// Less than (`lt`). Numbers and date-times only.
+    Lt,
+    /// Less than or equal to (`lte`). Numbers and date-times only.
+    Lte,
+    /// Greater than (`gt`). Numbers and date-times only.
+    Gt,
+    /// Greater than or equal to (`gte`). Numbers and date-times only.
+    Gte,
+    /// Has the option among its selections (`contains`). Multi-selects only.
+    Contains,
+    /// Starts with the value, case-sensitively (`prefix`). Text only.
+    Prefix,
+    /// Matches a case-insensitive `LIKE` pattern, where `%` matches any run of
+    /// characters and `_` a single character (`ilike`). Text only.
+    ILike,
+}
+
+impl FromStr for FilterOp {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "eq" => Ok(FilterOp::Eq),
+            "ne" => Ok(FilterOp::Ne),
+            "lt" => Ok(FilterOp::Lt),
+            "lte" => Ok(FilterOp::Lte),
+            "gt" => Ok(FilterOp::Gt),
+            "gte" => Ok(FilterOp::Gte),
+            "contains" => Ok(FilterOp::Contains),
+            "prefix" => Ok(FilterOp::Prefix),
+            "ilike" => Ok(FilterOp::ILike),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for FilterOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FilterOp::Eq => "eq",
+            FilterOp::Ne => "ne",
+            FilterOp::Lt => "lt",
+            FilterOp::Lte => "lte",
+            FilterOp::Gt => "gt",
+            FilterOp::Gte => "gte",
+            FilterOp::Contains => "contains",
+            FilterOp::Prefix => "prefix",
+            FilterOp::ILike => "ilike",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The typed operand of a `DataFilter`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    /// A value compared as JSON: text, numbers, booleans and select options.
+    ///
+    /// `Number` and `Integer` filters hold a JSON number. Their range comparisons are
+    /// numeric, and skip stored values that are not JSON numbers (such as text kept
+    /// from before the field was retyped).
+    Json(Value),
+    /// A point in time, compared chronologically.
+    DateTime(DateTime<Utc>),
+    /// A decimal number, compared exactly whether stored as a JSON number or string.
+    Decimal(Decimal),
+    /// A calendar date, compared chronologically.
+    Date(NaiveDate),
+    /// A time of day, compared chronologically.
+    Time(NaiveTime),
+    /// A length of time, compared by length.
+    Duration(IsoDuration),
+}
+
+/// A filter on one field of the document data, typed by the form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataFilter {
+    /// The ID of the filtered field.
+    pub field: String,
+    /// The comparison to apply.
+    pub op: FilterOp,
+    /// The value to compare the field with.
+    pub value: FilterValue,
+}
+
+impl DataFilter {
+    /// Parses a `field:op:value` filter and checks it against a form.
+    ///
+    /// The value is everything after the second `:`, so it may itself contain colons
+    /// (as date-times do).
+    ///
+    /// # Arguments
+    /// * `source` - The filter text, e.g. `severity:gte:4`.
+    /// * `form` - The `FormDefinition` of the documents being filtered.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(DataFilter)` if the field exists, the operator applies to
+    /// its type and the value parses as that type, or `Err(QueryError)` otherwise.
+    pub fn parse(source: &str, form: &FormDefinition) -> Result<Self, QueryError> {
+        let mut parts = source.splitn(3, ':');
+        let (Some(field), Some(op), Some(raw)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(QueryError::InvalidFilter(source.to_string()));
+        };
+        let op: FilterOp = op
+            .parse()
+            .map_err(|_| QueryError::InvalidFilter(source.to_string()))?;
+
+        let field_type = form
+            .fields()
+            .iter()
+            .find(|f| f.id() == field)
+            .map(|f| f.field_type())
+            .ok_or_else(|| QueryError::UnknownField(field.to_string()))?;
+
+        let unsupported = || QueryError::UnsupportedOperator {
+            field: field.to_string(),
+            op: op.to_string(),
+            field_type: field_type.kind().to_string(),
+        };
+        let invalid = |expected: &str| QueryError::InvalidFilterValue {
+            field: field.to_string(),
+            value: raw.to_string(),
+            expected: expected.to_string(),
+        };
+
+        let value = match field_type {
+            FieldType::Text { .. } | FieldType::TextArea { .. } => match op {
+                FilterOp::Eq | FilterOp::Ne | FilterOp::Prefix | FilterOp::ILike => {
+                    FilterValue::Json(Value::String(raw.to_string()))
+                }
+                _ => return Err(unsupported()),
+            },
+            FieldType::Number { .. } => match op {
+                FilterOp::Contains | FilterOp::Prefix | FilterOp::ILike => {
+                    return Err(unsupported());
+                }
+                _ => {
+                    let number: f64 = raw.parse().map_err(|_| invalid("a number"))?;
+                    let number = serde_json::Number::from_f64(number)
+                        .ok_or_else(|| invalid("a finite number"))?;
+                    FilterValue::Json(Value::Number(number))
+                }
+            },
+            FieldType::Integer { .. } => match op {
+                FilterOp::Contains | FilterOp::Prefix | FilterOp::ILike => {
+                    return Err(unsupported());
+                }
+                _ => {
+                    let number: i64 = raw.parse().map_err(|_| invalid("an integer"))?;
+                    FilterValue::Json(Value::from(number))
+                }
+            },
+            FieldType::Decimal { .. } => match op {
+                FilterOp::Contains | FilterOp::Prefix | FilterOp::ILike => {
+                    return Err(unsupported());
+                }
+                _ => FilterValue::Decimal(parse_decimal(raw).ok_or_else(|| invalid("a decimal"))?),
+            },
+            FieldType::Boolean => match op {
+                FilterOp::Eq | FilterOp::Ne => {
+                    let flag: bool = raw.parse().map_err(|_| invalid("true or false"))?;
+                    FilterValue::Json(Value::Bool(flag))
+                }
+                _ => return Err(unsupported()),
+            },
+            FieldType::DateTime => match op {
+                FilterOp::Contains | FilterOp::Prefix | FilterOp::ILike => {
+                    return Err(unsupported());
+                }
+                _ => {
+                    let instant = DateTime::parse_from_rfc3339(raw)
+                        .map_err(|_| invalid("an RFC 3339 date-time"))?;
+                    FilterValue::DateTime(instant.with_timezone(&Utc))
+                }
+            },
+            FieldType::Date { .. } => match op {
+                FilterOp::Contains | FilterOp::Prefix | FilterOp::ILike => {
+                    return Err(unsupported());
+                }
+                _ => FilterValue::Date(parse_date(raw).ok_or_else(|| invalid("a date"))?),
+            },
+            FieldType::Time { .. } => match op {
+                FilterOp::Contains | FilterOp::Prefix | FilterOp::ILike => {
+                    return Err(unsupported());
+                }
+                _ => FilterValue::Time(parse_time(raw).ok_or_else(|| invalid("a time"))?),
+            },
+            FieldType::Duration { .. } => match op {
+                FilterOp::Contains | FilterOp::Prefix | FilterOp::ILike => {
+                    return Err(unsupported());
+                }
+                _ => {
+                    FilterValue::Duration(raw.parse().map_err(|_| invalid("an ISO 8601 duration"))?)
+                }
+            },
+            FieldType::Select {
+                options,
+                allow_multiple,
+            } => {
+                let applies = if *allow_multiple {
+                    op == FilterOp::Contains
+                } else {
+                    matches!(op, FilterOp::Eq | FilterOp::Ne)
+                };
+                if !applies {
+                    return Err(unsupported());
+                }
+                if !options.iter().any(|o| o == raw) {
+                    return Err(invalid(&format!("one of {:?}", options)));
+                }
+                FilterValue::Json(Value::String(raw.to_string()))
+            }
+            FieldType::User { allow_multiple }
+            | FieldType::Reference { allow_multiple, .. }
+            | FieldType::Attachment { allow_multiple, .. } => {
+                let applies = if *allow_multiple {
+                    op == FilterOp::Contains
+                } else {
+                    matches!(op, FilterOp::Eq | FilterOp::Ne)
+                };
+                if !applies {
+                    return Err(unsupported());
+                }
+                FilterValue::Json(Value::String(raw.to_string()))
+            }
+            FieldType::Group { .. } => return Err(unsupported()),
+        };
+
+        Ok(Self {
+            field: field.to_string(),
+            op,
+            value,
+        })
+    }
+}
+
+/// Describes which documents to list, in what order, and which page to return.
+///
+/// Timestamp ranges are half-open: `*_from` is inclusive and `*_to` is exclusive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentQuery {
+    /// Only list documents of this form.
+    pub form_id: Option<String>,
+    /// Only list documents created with this version of the form.
+    pub form_version: Option<u32>,
+    /// Only list documents governed by this workflow.
+    pub workflow_id: Option<String>,
+    /// Only list documents currently in this phase.
+    pub current_phase: Option<String>,
+    /// Only list documents created at or after this time.
+    pub created_from: Option<DateTime<Utc>>,
+    /// Only list documents created before this time.
+    pub created_to: Option<DateTime<Utc>>,
+    /// Only list documents last modified at or after this time.
+    pub updated_from: Option<DateTime<Utc>>,
+    /// Only list documents last modified before this time.
+    pub updated_to: Option<DateTime<Utc>>,
+    /// Only list documents whose data matches all of these filters.
+    pub data: Vec<DataFilter>,
+    /// The order to list documents in.
+    pub sort: DocumentSort,
+    /// The maximum number of documents to return.
+    pub limit: u64,
+    /// Start listing after this position, as returned with the previous page.
+    pub after: Option<Cursor>,
+}
+
+impl Default for DocumentQuery {
+    fn default() -> Self {
+        Self {
+            form_id: None,
+            form_version: None,
+            workflow_id: None,
+            current_phase: None,
+            created_from: None,
+            created_to: None,
+            updated_from: None,
+            updated_to: None,
+            data: Vec::new(),
+            sort: DocumentSort::default(),
+            limit: DEFAULT_PAGE_SIZE,
+            after: None,
+        }
+    }
+}
+
+impl DocumentQuery {
+    /// Checks that the query can be executed.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok` if the limit is in range, data filters are scoped to a
+    /// form, and the cursor (if any) was issued for the query's sort field, or
+    /// `Err(QueryError)` otherwise.
+    pub fn check(&self) -> Result<(), QueryError> {
+        if self.limit == 0 || self.limit > MAX_PAGE_SIZE {
+            return Err(QueryError::InvalidLimit(self.limit));
+        }
+
+        if !self.data.is_empty() && self.form_id.is_none() {
+            return Err(QueryError::FilterRequiresForm);
+        }
+
+        if let Some(cursor) = &self.after
+            && cursor.field != self.sort.field
+        {
+            return Err(QueryError::CursorSortMismatch {
+                cursor: cursor.field.to_string(),
+                query: self.sort.field.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// One page of a document listing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentPage {
+    /// The documents on this page, in query order.
+    pub items: Vec<Document>,
+    /// The cursor for the next page, or `None` if this is the last page.
+    pub next_cursor: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_parsing() {
+        let sort: DocumentSort = "-updated_at".parse().unwrap();
+        assert_eq!(sort.field, SortField::UpdatedAt);
+        assert_eq!(sort.direction, SortDirection::Desc);
+
+        let sort: DocumentSort = "created_at".parse().unwrap();
+        assert_eq!(sort.field, SortField::CreatedAt);
+        assert_eq!(sort.direction, SortDirection::Asc);
+
+        assert!("title".parse::<DocumentSort>().is_err());
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let doc = Document::new("doc|1", "form_1", "flow_1");
+        let cursor = Cursor::after(&doc, SortField::UpdatedAt);
+
+        let decoded = Cursor::decode(&cursor.encode()).unwrap();
+        assert_eq!(decoded.field, SortField::UpdatedAt);
+        assert_eq!(decoded.id, "doc|1");
+        assert_eq!(
+            decoded.value.timestamp_micros(),
+            doc.updated_at.timestamp_micros()
+        );
+
+        assert_eq!(
+            Cursor::decode("not a cursor"),
+            Err(QueryError::InvalidCursor)
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let doc = Document::new("doc_1", "form_1", "flow_1");
+        let query = DocumentQuery {
+            after: Some(Cursor::after(&doc, SortField::UpdatedAt)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            query.check(),
+            Err(QueryError::CursorSortMismatch { .. })
+        ));
+
+        let query = DocumentQuery {
+            limit: MAX_PAGE_SIZE + 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            query.check(),
+            Err(QueryError::InvalidLimit(MAX_PAGE_SIZE + 1))
+        );
+
+        assert!(DocumentQuery::default().check().is_ok());
+    }
+
+    #[test]
+    fn test_data_filters_typed_by_form() {
+        use crate::field::FieldBuilder;
+        use crate::form::FormBuilder;
+
+        let field = |id: &str, field_type: FieldType| {
+            FieldBuilder::new(id, id, field_type).build().unwrap()
+        };
+        let form = FormBuilder::new("incident", "Incident")
+            .add_field(field(
+                "title",
+                FieldType::Text {
+                    min_length: None,
+                    max_length: None,
+                    pattern: None,
+                },
+            ))
+            .add_field(field(
+                "severity",
+                FieldType::Number {
+                    min: None,
+                    max: None,
+                },
+            ))
+            .add_field(field("occurred_at", FieldType::DateTime))
+            .add_field(field(
+                "shift",
+                FieldType::Duration {
+                    min: None,
+                    max: None,
+                },
+            ))
+            .add_field(field(
+                "quantity",
+                FieldType::Integer {
+                    min: None,
+                    max: None,
+                },
+            ))
+            .add_field(field(
+                "amount",
+                FieldType::Decimal {
+                    precision: 10,
+                    scale: 2,
+                    min: None,
+                    max: None,
+                },
+            ))
+            .add_field(field(
+                "tags",
+                FieldType::Select {
+                    options: vec!["safety".into(), "quality".into()],
+                    allow_multiple: true,
+                },
+            ))
+            .build()
+            .unwrap();
+
+        let filter = DataFilter::parse("severity:gte:4", &form).unwrap();
+        assert_eq!(filter.op, FilterOp::Gte);
+        assert_eq!(filter.value, FilterValue::Json(serde_json::json!(4.0)));
+
+        let filter = DataFilter::parse("occurred_at:lt:2026-01-01T00:00:00+02:00", &form).unwrap();
+        assert_eq!(
+            filter.value,
+            FilterValue::DateTime("2025-12-31T22:00:00Z".parse().unwrap())
+        );
+
+        let filter = DataFilter::parse("shift:lte:PT8H", &form).unwrap();
+        assert_eq!(
+            filter.value,
+            FilterValue::Duration(IsoDuration(chrono::TimeDelta::hours(8)))
+        );
+        assert!(matches!(
+            DataFilter::parse("shift:gt:8h", &form),
+            Err(QueryError::InvalidFilterValue { .. })
+        ));
+
+        let filter = DataFilter::parse("quantity:lt:10", &form).unwrap();
+        assert_eq!(filter.value, FilterValue::Json(serde_json::json!(10)));
+        let filter = DataFilter::parse("amount:gte:19.99", &form).unwrap();
+        assert_eq!(filter.value, FilterValue::Decimal(Decimal::new(1999, 2)));
+        assert!(matches!(
+            DataFilter::parse("quantity:eq:2.5", &form),
+            Err(QueryError::InvalidFilterValue { .. })
+        ));
+
+        assert!(DataFilter::parse("tags:contains:safety", &form).is_ok());
+        assert!(DataFilter::parse("title:ilike:%pump%", &form).is_ok());
+
+        assert_eq!(
+            DataFilter::parse("owner:eq:alice", &form),
+            Err(QueryError::UnknownField("owner".into()))
+        );
+        assert!(matches!(
+            DataFilter::parse("title:gt:a", &form),
+            Err(QueryError::UnsupportedOperator { .. })
+        ));
+        assert!(matches!(
+            DataFilter::parse("tags:eq:safety", &form),
+            Err(QueryError::UnsupportedOperator { .. })
+        ));
+        assert!(matches!(
+            DataFilter::parse("severity:eq:high", &form),
+            Err(QueryError::InvalidFilterValue { .. })
+        ));
+        assert!(matches!(
+            DataFilter::parse("tags:contains:cost", &form),
+            Err(QueryError::InvalidFilterValue { .. })
+        ));
+        assert!(matches!(
+            DataFilter::parse("severity", &form),
+            Err(QueryError::InvalidFilter(_))
+        ));
+    }
+}
@@ -0,0 +1,104 @@
+//! This module defines cross-field validation rules, which constrain how the values of
+//! several fields of a form relate to each other.
+//!
+//! Each field of a document is validated on its own against its `FieldDefinition`.
+//! A `FormRule` adds a constraint over the document as a whole, such as "`closed_at`
+//! must be after `opened_at`" or "at least one of `email` and `phone` is required".
+//! Rules are declared on a `FormDefinition` and evaluated by `molten-document`.
+use crate::expression::Expression;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// A named constraint over several fields of a form.
+///
+/// # Examples
+/// ```
+/// use molten_core::expression::Expression;
+/// use molten_core::rule::{FormRule, RuleKind};
+///
+/// let rule = FormRule::new(
+///     "closed_after_opened",
+///     "The incident must be closed after it was opened",
+///     RuleKind::Condition {
+///         condition: Expression::parse("closed_at > opened_at").unwrap(),
+///     },
+/// );
+/// assert_eq!(rule.fields(), vec!["closed_at", "opened_at"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+pub struct FormRule {
+    /// Unique identifier of the rule within its form (e.g., "closed_after_opened").
+    #[validate(length(min = 1, max = 64))]
+    pub id: String,
+
+    /// The message reported when a document violates the rule.
+    #[validate(length(min = 1, max = 500))]
+    pub message: String,
+
+    /// What the rule checks.
+    #[serde(flatten)]
+    pub kind: RuleKind,
+}
+
+/// The kinds of cross-field rules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleKind {
+    /// The condition must hold (e.g., `closed_at > opened_at`).
+    ///
+    /// The rule only applies once every field it references holds a value, so that
+    /// optional fields can be left empty. Combine it with `required_when` or field
+    /// requiredness to also demand the values.
+    Condition {
+        /// The condition over the document data. See [`crate::expression`].
+        condition: Expression,
+    },
+
+    /// A field must hold a value whenever the condition holds
+    /// (e.g., `other_reason` when `reason == "Other"`).
+    RequiredWhen {
+        /// The ID of the field that becomes required.
+        field: String,
+        /// The condition under which the field is required.
+        condition: Expression,
+    },
+
+    /// At least one of the fields must hold a value (e.g., `email` or `phone`).
+    AtLeastOneOf {
+        /// The IDs of the fields, of which at least one is required.
+        fields: Vec<String>,
+    },
+}
+
+impl FormRule {
+    /// Creates a new `FormRule` instance.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the rule within its form.
+    /// * `message` - The message reported when a document violates the rule.
+    /// * `kind` - What the rule checks.
+    pub fn new(id: &str, message: &str, kind: RuleKind) -> Self {
+        Self {
+            id: id.to_string(),
+            message: message.to_string(),
+            kind,
+        }
+    }
+
+    /// Returns the IDs of every field the rule involves, deduplicated.
+    ///
+    /// For `RequiredWhen`, the required field comes first, followed by the fields of
+    /// the condition in sorted order.
+    pub fn fields(&self) -> Vec<&str> {
+        let mut fields: Vec<&str> = match &self.kind {
+            RuleKind::Condition { condition } => condition.fields(),
+            RuleKind::RequiredWhen { field, condition } => std::iter::once(field.as_str())
+                .chain(condition.fields())
+                .collect(),
+            RuleKind::AtLeastOneOf { fields } => fields.iter().map(String::as_str).collect(),
+        };
+        let mut seen = std::collections::HashSet::new();
+        fields.retain(|f| seen.insert(*f));
+        fields
+    }
+}
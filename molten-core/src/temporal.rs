@@ -0,0 +1,415 @@
+//! This module defines the values and bounds of the date-only, time-only and duration
+//! field types.
+//!
+//! Values are written in ISO 8601: dates as `2026-10-16`, times as `08:30` or
+//! `08:30:00`, and durations as `PT8H30M` or `P2D`. Bounds are either fixed values or,
+//! for dates and times, relative to the moment of validation (e.g. `today`, so that a
+//! date cannot be in the future).
+use chrono::{NaiveDate, NaiveTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Raised when a date, time, duration or bound cannot be parsed.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Invalid {kind} '{value}'")]
+pub struct TemporalParseError {
+    /// What was being parsed, e.g. "date bound".
+    pub kind: &'static str,
+    /// The text that failed to parse.
+    pub value: String,
+}
+
+impl TemporalParseError {
+    fn new(kind: &'static str, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
+
+/// Parses an ISO 8601 calendar date, e.g. `2026-10-16`.
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+}
+
+/// Parses an ISO 8601 time of day, with or without seconds, e.g. `08:30` or `08:30:15.5`.
+pub fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text, "%H:%M:%S%.f")
+        .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+        .ok()
+}
+
+/// A bound on a `Date` field.
+///
+/// Written as a fixed date (`2020-01-01`), or relative to the current UTC date:
+/// `today`, `today-30d` or `today+7d`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum DateBound {
+    /// A fixed calendar date.
+    Date(NaiveDate),
+    /// The current date, shifted by a number of days.
+    Today {
+        /// The number of days to add to the current date (negative for the past).
+        offset_days: i64,
+    },
+}
+
+impl DateBound {
+    /// Resolves the bound against the current date.
+    pub fn resolve(&self, today: NaiveDate) -> NaiveDate {
+        match self {
+            DateBound::Date(date) => *date,
+            DateBound::Today { offset_days } => today
+                .checked_add_signed(TimeDelta::days(*offset_days))
+                .unwrap_or(today),
+        }
+    }
+}
+
+/// Fixed bounds order by date, and relative bounds by offset. A fixed and a relative
+/// bound are not comparable, as their order changes over time.
+impl PartialOrd for DateBound {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (DateBound::Date(a), DateBound::Date(b)) => Some(a.cmp(b)),
+            (DateBound::Today { offset_days: a }, DateBound::Today { offset_days: b }) => {
+                Some(a.cmp(b))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for DateBound {
+    type Err = TemporalParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || TemporalParseError::new("date bound", s);
+
+        let Some(offset) = s.strip_prefix("today") else {
+            return parse_date(s).map(DateBound::Date).ok_or_else(invalid);
+        };
+        if offset.is_empty() {
+            return Ok(DateBound::Today { offset_days: 0 });
+        }
+
+        let days = offset.strip_suffix('d').ok_or_else(invalid)?;
+        let offset_days = match days.as_bytes().first() {
+            Some(b'+') | Some(b'-') => days.parse().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        };
+        Ok(DateBound::Today { offset_days })
+    }
+}
+
+impl fmt::Display for DateBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateBound::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            DateBound::Today { offset_days: 0 } => f.write_str("today"),
+            DateBound::Today { offset_days } => write!(f, "today{:+}d", offset_days),
+        }
+    }
+}
+
+/// A bound on a `Time` field.
+///
+/// Written as a fixed time of day (`08:00`), or `now` for the current UTC time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TimeBound {
+    /// A fixed time of day.
+    Time(NaiveTime),
+    /// The current time of day.
+    Now,
+}
+
+impl TimeBound {
+    /// Resolves the bound against the current time of day.
+    pub fn resolve(&self, now: NaiveTime) -> NaiveTime {
+        match self {
+            TimeBound::Time(time) => *time,
+            TimeBound::Now => now,
+        }
+    }
+}
+
+/// Fixed bounds order by time of day. `now` is only comparable with itself.
+impl PartialOrd for TimeBound {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (TimeBound::Time(a), TimeBound::Time(b)) => Some(a.cmp(b)),
+            (TimeBound::Now, TimeBound::Now) => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for TimeBound {
+    type Err = TemporalParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "now" {
+            return Ok(TimeBound::Now);
+        }
+        parse_time(s)
+            .map(TimeBound::Time)
+            .ok_or_else(|| TemporalParseError::new("time bound", s))
+    }
+}
+
+impl fmt::Display for TimeBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeBound::Time(time) => write!(f, "{}", time.format("%H:%M:%S%.f")),
+            TimeBound::Now => f.write_str("now"),
+        }
+    }
+}
+
+/// An exact ISO 8601 duration, e.g. `PT8H30M`, `P2D` or `P1W`.
+///
+/// Only weeks, days, hours, minutes and (possibly fractional) seconds are supported.
+/// Years and months are rejected, as they have no fixed length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IsoDuration(pub TimeDelta);
+
+impl FromStr for IsoDuration {
+    type Err = TemporalParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_duration(s)
+            .map(IsoDuration)
+            .ok_or_else(|| TemporalParseError::new("duration", s))
+    }
+}
+
+impl fmt::Display for IsoDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let delta = self.0;
+        if delta < TimeDelta::zero() {
+            f.write_str("-")?;
+        }
+        let secs = delta.num_seconds().unsigned_abs();
+        let nanos = delta.subsec_nanos().unsigned_abs();
+        let (days, hours, minutes, seconds) = (
+            secs / 86_400,
+            secs % 86_400 / 3_600,
+            secs % 3_600 / 60,
+            secs % 60,
+        );
+
+        f.write_str("P")?;
+        if days > 0 {
+            write!(f, "{}D", days)?;
+        }
+        if hours == 0 && minutes == 0 && seconds == 0 && nanos == 0 {
+            return if days == 0 {
+                f.write_str("T0S")
+            } else {
+                Ok(())
+            };
+        }
+        f.write_str("T")?;
+        if hours > 0 {
+            write!(f, "{}H", hours)?;
+        }
+        if minutes > 0 {
+            write!(f, "{}M", minutes)?;
+        }
+        if nanos > 0 {
+            let fraction = format!("{:09}", nanos);
+            write!(f, "{}.{}S", seconds, fraction.trim_end_matches('0'))?;
+        } else if seconds > 0 {
+            write!(f, "{}S", seconds)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the supported subset of ISO 8601 durations.
+fn parse_duration(text: &str) -> Option<TimeDelta> {
+    let (negative, rest) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let rest = rest.strip_prefix('P')?;
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) if !time.is_empty() => (date, Some(time)),
+        Some(_) => return None,
+        None if !rest.is_empty() => (rest, None),
+        None => return None,
+    };
+
+    let mut total = TimeDelta::zero();
+    for (number, unit) in components(date, &['W', 'D'])? {
+        let n: i64 = number.parse().ok()?;
+        total = total.checked_add(&match unit {
+            'W' => TimeDelta::try_weeks(n)?,
+            _ => TimeDelta::try_days(n)?,
+        })?;
+    }
+    for (number, unit) in components(time.unwrap_or_default(), &['H', 'M', 'S'])? {
+        let part = match unit {
+            'H' => TimeDelta::try_hours(number.parse().ok()?)?,
+            'M' => TimeDelta::try_minutes(number.parse().ok()?)?,
+            _ => parse_seconds(number)?,
+        };
+        total = total.checked_add(&part)?;
+    }
+
+    Some(if negative { -total } else { total })
+}
+
+/// Splits `12H30M` into `[("12", 'H'), ("30", 'M')]`, requiring the units to appear at
+/// most once and in the given order.
+fn components<'a>(part: &'a str, units: &[char]) -> Option<Vec<(&'a str, char)>> {
+    let mut found = Vec::new();
+    let mut next_unit = 0;
+    let mut start = 0;
+
+    for (i, c) in part.char_indices() {
+        if c.is_ascii_digit() || c == '.' {
+            continue;
+        }
+        let position = units[next_unit..].iter().position(|u| *u == c)?;
+        next_unit += position + 1;
+
+        let number = &part[start..i];
+        // Only seconds may be fractional
+        if number.is_empty() || (number.contains('.') && c != 'S') {
+            return None;
+        }
+        found.push((number, c));
+        start = i + 1;
+    }
+
+    if start != part.len() {
+        return None;
+    }
+    Some(found)
+}
+
+/// Parses a seconds component with up to nanosecond precision, e.g. `1.25`.
+fn parse_seconds(number: &str) -> Option<TimeDelta> {
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let nanos: i64 = format!("{:0<9}", fraction).parse().ok()?;
+    TimeDelta::try_seconds(whole.parse().ok()?)?.checked_add(&TimeDelta::nanoseconds(nanos))
+}
+
+impl TryFrom<String> for DateBound {
+    type Error = TemporalParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<DateBound> for String {
+    fn from(value: DateBound) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for TimeBound {
+    type Error = TemporalParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<TimeBound> for String {
+    fn from(value: TimeBound) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for IsoDuration {
+    type Error = TemporalParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<IsoDuration> for String {
+    fn from(value: IsoDuration) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_bounds() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+
+        let bound: DateBound = "today".parse().unwrap();
+        assert_eq!(bound.resolve(today), today);
+        let bound: DateBound = "today-30d".parse().unwrap();
+        assert_eq!(bound, DateBound::Today { offset_days: -30 });
+        assert_eq!(bound.to_string(), "today-30d");
+        assert_eq!(
+            bound.resolve(today),
+            NaiveDate::from_ymd_opt(2026, 9, 16).unwrap()
+        );
+        let bound: DateBound = "2020-02-29".parse().unwrap();
+        assert_eq!(bound.to_string(), "2020-02-29");
+
+        for invalid in ["tomorrow", "today+7", "today7d", "2020-02-30"] {
+            assert!(invalid.parse::<DateBound>().is_err(), "{}", invalid);
+        }
+
+        let bound = |s: &str| s.parse::<DateBound>().unwrap();
+        assert!(bound("today-30d") < bound("today"));
+        assert!(bound("2020-01-01") < bound("2020-02-29"));
+        assert_eq!(bound("today").partial_cmp(&bound("2020-01-01")), None);
+    }
+
+    #[test]
+    fn test_time_bounds() {
+        assert_eq!("now".parse::<TimeBound>().unwrap(), TimeBound::Now);
+        let bound: TimeBound = "08:30".parse().unwrap();
+        assert_eq!(bound.to_string(), "08:30:00");
+        assert!("8.30".parse::<TimeBound>().is_err());
+
+        assert!(bound < "17:00".parse().unwrap());
+        assert_eq!(bound.partial_cmp(&TimeBound::Now), None);
+    }
+
+    #[test]
+    fn test_durations() {
+        let parse = |s: &str| s.parse::<IsoDuration>().map(|d| d.0);
+
+        assert_eq!(parse("PT8H30M"), Ok(TimeDelta::minutes(510)));
+        assert_eq!(parse("P1W"), Ok(TimeDelta::days(7)));
+        assert_eq!(
+            parse("P1DT0.5S"),
+            Ok(TimeDelta::days(1) + TimeDelta::milliseconds(500))
+        );
+        assert_eq!(parse("-PT15M"), Ok(TimeDelta::minutes(-15)));
+
+        for invalid in ["P", "PT", "P1Y", "P1M", "PT1.5H", "PT30M1H", "8H", "P1D2"] {
+            assert!(parse(invalid).is_err(), "{}", invalid);
+        }
+
+        for canonical in ["PT0S", "P2D", "P1DT2H", "PT8H30M", "PT1.25S"] {
+            assert_eq!(
+                parse(canonical).map(|d| IsoDuration(d).to_string()),
+                Ok(canonical.into())
+            );
+        }
+    }
+}
@@ -5,7 +5,12 @@
 //! It includes `Phase` and `Transition` to model the states and movements
 //! within a workflow, `WorkflowDefinition` to represent a complete state machine,
 //! and `WorkflowBuilder` for programmatic construction and validation of workflows.
+use crate::document::Document;
+use crate::expression::Expression;
+use crate::field::FieldType;
+use crate::form::FormDefinition;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 use validator::{Validate, ValidationError};
@@ -40,6 +45,12 @@ pub struct Phase {
     /// The behavior type of this phase.
     #[serde(rename = "type")]
     pub phase_type: PhaseType,
+
+    /// The ID of a `User` field naming whoever a document in this phase belongs to
+    /// (e.g., "reviewer" for a "review" phase). Documents show up in the work queue of
+    /// the users the field holds while they are in this phase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
 }
 
 impl Phase {
@@ -54,8 +65,15 @@ impl Phase {
             id: id.to_string(),
             label: label.to_string(),
             phase_type,
+            assignee: None,
         }
     }
+
+    /// Assigns documents in this phase to the users held by the given `User` field.
+    pub fn with_assignee(mut self, field_id: &str) -> Self {
+        self.assignee = Some(field_id.to_string());
+        self
+    }
 }
 
 /// A directed edge between two Phases.
@@ -73,13 +91,24 @@ pub struct Transition {
     /// The ID of the target phase.
     #[validate(length(min = 1, max = 64))]
     pub to: String,
-    // Future expansion: We will add "guards" or "permissions" here later.
-    // e.g., pub required_role: Option<String>
 
-    // TODO: Add required_fields for a transition to be valid.
-    // This is different from the global is_required in Field definition
-    // #[serde(default)]
-    // pub required_fields: Vec<String>,
+    /// Roles allowed to fire this transition. An empty list means anyone may fire it.
+    #[serde(default)]
+    pub allowed_roles: Vec<String>,
+
+    /// Field IDs that must hold a value before this transition may fire.
+    ///
+    /// This is different from the global `required` flag on a Field definition: a field
+    /// can be optional while a document is drafted but mandatory before it is closed.
+    #[serde(default)]
+    pub required_fields: Vec<String>,
+
+    /// An optional condition over the document data that must hold for this transition
+    /// to fire (e.g., `severity >= 4 && category == "Safety"`).
+    ///
+    /// See [`crate::expression`] for the syntax.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Expression>,
 }
 
 impl Transition {
@@ -94,8 +123,29 @@ impl Transition {
             name: name.to_string(),
             from: from.to_string(),
             to: to.to_string(),
+            allowed_roles: Vec::new(),
+            required_fields: Vec::new(),
+            condition: None,
         }
     }
+
+    /// Restricts this transition to actors holding at least one of the given roles.
+    pub fn with_allowed_roles(mut self, roles: &[&str]) -> Self {
+        self.allowed_roles = roles.iter().map(|r| r.to_string()).collect();
+        self
+    }
+
+    /// Sets the fields that must hold a value before this transition may fire.
+    pub fn with_required_fields(mut self, fields: &[&str]) -> Self {
+        self.required_fields = fields.iter().map(|f| f.to_string()).collect();
+        self
+    }
+
+    /// Sets the condition that the document data must satisfy for this transition to fire.
+    pub fn with_condition(mut self, condition: Expression) -> Self {
+        self.condition = Some(condition);
+        self
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -121,6 +171,14 @@ pub struct WorkflowDefinition {
     /// All allowed transitions (movements) between phases.
     #[validate(nested)]
     transitions: Vec<Transition>,
+
+    /// The ID of the form whose documents this workflow governs, if it is bound to one.
+    ///
+    /// Transition guards and phase assignees were checked against this form when the
+    /// workflow was built. Unbound workflows, and those stored before forms were bound,
+    /// have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    form_id: Option<String>,
 }
 
 /// Trait for querying workflow capability.
@@ -128,6 +186,12 @@ pub trait WorkflowGraph {
     /// Returns true if a transition exists from `current_phase` to `target_phase`.
     fn can_transition(&self, current_phase: &str, target_phase: &str) -> bool;
 
+    /// Returns every transition that may be fired from `current_phase`.
+    fn available_transitions(&self, current_phase: &str) -> Vec<&Transition>;
+
+    /// Returns the transition named `name` leaving `current_phase`, if any.
+    fn find_transition(&self, current_phase: &str, name: &str) -> Option<&Transition>;
+
     /// Returns the Phase definition for a given ID.
     fn get_phase(&self, phase_id: &str) -> Option<&Phase>;
 
@@ -142,6 +206,19 @@ impl WorkflowGraph for WorkflowDefinition {
             .any(|t| t.from == current_phase && t.to == target_phase)
     }
 
+    fn available_transitions(&self, current_phase: &str) -> Vec<&Transition> {
+        self.transitions
+            .iter()
+            .filter(|t| t.from == current_phase)
+            .collect()
+    }
+
+    fn find_transition(&self, current_phase: &str, name: &str) -> Option<&Transition> {
+        self.transitions
+            .iter()
+            .find(|t| t.from == current_phase && t.name == name)
+    }
+
     fn get_phase(&self, phase_id: &str) -> Option<&Phase> {
         self.phases.iter().find(|p| p.id == phase_id)
     }
@@ -170,43 +247,283 @@ impl WorkflowDefinition {
     pub fn transitions(&self) -> &[Transition] {
         &self.transitions
     }
+    /// Returns the ID of the form the workflow is bound to, if any.
+    pub fn form_id(&self) -> Option<&str> {
+        self.form_id.as_deref()
+    }
+
+    /// Checks the transition guards and phase assignees of the workflow against a form.
+    ///
+    /// This is the check `WorkflowBuilder::for_form` enables at build time, and that
+    /// bound workflows repeat against later versions of their form.
+    ///
+    /// # Arguments
+    /// * `form` - The `FormDefinition` of the documents this workflow governs.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok` if every guard and assignee is valid for the form, or
+    /// `Err` with `validator::ValidationErrors` describing the problems found.
+    pub fn check_form(&self, form: &FormDefinition) -> Result<(), validator::ValidationErrors> {
+        validate_transition_guards(self, form)?;
+        validate_phase_assignees(self, form)
+    }
+
+    /// Returns the IDs of the users a document currently belongs to: those held by the
+    /// assignee field of its current phase, each listed once.
+    ///
+    /// Returns an empty list if the phase has no assignee, or the field holds no users.
+    pub fn assignees(&self, doc: &Document) -> Vec<String> {
+        let Some(field_id) = self
+            .get_phase(&doc.current_phase)
+            .and_then(|p| p.assignee.as_deref())
+        else {
+            return Vec::new();
+        };
+        let mut assignees: Vec<String> = match doc.get_value(field_id) {
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            Some(Value::String(id)) => vec![id.clone()],
+            _ => Vec::new(),
+        };
+        let mut seen = HashSet::new();
+        assignees.retain(|a| seen.insert(a.clone()));
+        assignees
+    }
 }
 
 // -----------------------------------------------------------------------------
 // Validation Logic
 // -----------------------------------------------------------------------------
 
-// TODO: Implement additional validations for transitions, such as rules for phase types
-/// Ensures that all transitions in a `WorkflowDefinition` refer to valid, existing phases.
+/// Ensures that a `WorkflowDefinition` describes a well-formed state machine.
 ///
-/// This validation prevents transitions from or to non-existent phases, ensuring the
-/// integrity and consistency of the workflow graph.
+/// Every problem found is reported as its own structured `ValidationError`, keyed by
+/// `phases` or `transitions`:
+/// * `duplicate_phase_id` - two phases share an ID.
+/// * `missing_start_phase` / `multiple_start_phases` - there must be exactly one Start phase.
+/// * `invalid_transition_source` / `invalid_transition_target` - a transition refers to a
+///   phase that does not exist.
+/// * `transition_from_end_phase` - an End phase is terminal and cannot have outgoing transitions.
+/// * `duplicate_transition_name` - two transitions leaving the same phase share a name, so
+///   firing it by name would be ambiguous.
+/// * `unreachable_phase` - a phase cannot be reached from the Start phase.
+/// * `end_phase_unreachable` - no End phase can be reached from a phase, so documents
+///   entering it could never be completed.
 ///
 /// # Arguments
 /// * `definition` - A reference to the `WorkflowDefinition` to validate.
 ///
 /// # Returns
-/// A `Result` which is `Ok` if all transitions are valid, or `Err` with
-/// `validator::ValidationErrors` if any invalid transitions are found.
+/// A `Result` which is `Ok` if the graph is well-formed, or `Err` with
+/// `validator::ValidationErrors` describing every problem found.
 fn validate_workflow_integrity(
     definition: &WorkflowDefinition,
 ) -> Result<(), validator::ValidationErrors> {
-    let phase_ids: HashSet<&String> = definition.phases.iter().map(|p| &p.id).collect();
-
     let mut errors = validator::ValidationErrors::new();
 
+    // 1. Phase IDs must be unique
+    let mut phase_ids: HashSet<&str> = HashSet::new();
+    for phase in definition.phases.iter() {
+        if !phase_ids.insert(&phase.id) {
+            let mut err = ValidationError::new("duplicate_phase_id");
+            err.add_param("phase_id".into(), &phase.id);
+            errors.add("phases", err);
+        }
+    }
+
+    // 2. Exactly one Start phase
+    let start_phases: Vec<&str> = definition
+        .phases
+        .iter()
+        .filter(|p| p.phase_type == PhaseType::Start)
+        .map(|p| p.id.as_str())
+        .collect();
+    match start_phases.len() {
+        0 => errors.add("phases", ValidationError::new("missing_start_phase")),
+        1 => {}
+        _ => {
+            let mut err = ValidationError::new("multiple_start_phases");
+            err.add_param("phase_ids".into(), &start_phases);
+            errors.add("phases", err);
+        }
+    }
+
+    // 3. Transitions must connect existing phases, never leave an End phase,
+    //    and be unambiguous by name within their source phase
+    let end_phases: HashSet<&str> = definition
+        .phases
+        .iter()
+        .filter(|p| p.phase_type == PhaseType::End)
+        .map(|p| p.id.as_str())
+        .collect();
+    let mut names: HashSet<(&str, &str)> = HashSet::new();
+
     for transition in definition.transitions.iter() {
-        if !phase_ids.contains(&transition.from) {
+        if !phase_ids.contains(transition.from.as_str()) {
             let mut err = ValidationError::new("invalid_transition_source");
             err.add_param("phase_id".into(), &transition.from);
             errors.add("transitions", err);
         }
 
-        if !phase_ids.contains(&transition.to) {
+        if !phase_ids.contains(transition.to.as_str()) {
             let mut err = ValidationError::new("invalid_transition_target");
             err.add_param("phase_id".into(), &transition.to);
             errors.add("transitions", err);
         }
+
+        if end_phases.contains(transition.from.as_str()) {
+            let mut err = ValidationError::new("transition_from_end_phase");
+            err.add_param("transition".into(), &transition.name);
+            err.add_param("phase_id".into(), &transition.from);
+            errors.add("transitions", err);
+        }
+
+        if !names.insert((&transition.from, &transition.name)) {
+            let mut err = ValidationError::new("duplicate_transition_name");
+            err.add_param("transition".into(), &transition.name);
+            err.add_param("phase_id".into(), &transition.from);
+            errors.add("transitions", err);
+        }
+    }
+
+    // 4. Every phase must be reachable from the Start phase.
+    //    Skipped when the Start phase is ambiguous, as that is already reported.
+    if let [start] = start_phases.as_slice() {
+        let reachable = reachable_from(&[start], definition, |t| (&t.from, &t.to));
+        for phase in definition.phases.iter() {
+            if !reachable.contains(phase.id.as_str()) {
+                let mut err = ValidationError::new("unreachable_phase");
+                err.add_param("phase_id".into(), &phase.id);
+                errors.add("phases", err);
+            }
+        }
+    }
+
+    // 5. An End phase must be reachable from every phase (walk the edges backwards)
+    let end_ids: Vec<&str> = end_phases.into_iter().collect();
+    let completable = reachable_from(&end_ids, definition, |t| (&t.to, &t.from));
+    for phase in definition.phases.iter() {
+        if !completable.contains(phase.id.as_str()) {
+            let mut err = ValidationError::new("end_phase_unreachable");
+            err.add_param("phase_id".into(), &phase.id);
+            errors.add("phases", err);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Collects the IDs of every phase reachable from `roots` by following transitions.
+///
+/// `edge` maps a transition to the `(from, to)` pair to follow, which allows the graph
+/// to be walked backwards.
+fn reachable_from<'a>(
+    roots: &[&'a str],
+    definition: &'a WorkflowDefinition,
+    edge: impl Fn(&'a Transition) -> (&'a String, &'a String),
+) -> HashSet<&'a str> {
+    let mut seen: HashSet<&str> = roots.iter().copied().collect();
+    let mut queue: Vec<&str> = roots.to_vec();
+
+    while let Some(current) = queue.pop() {
+        for transition in definition.transitions.iter() {
+            let (from, to) = edge(transition);
+            if from == current && seen.insert(to) {
+                queue.push(to);
+            }
+        }
+    }
+
+    seen
+}
+
+/// Checks the field references of every transition guard against a form.
+///
+/// Ensures that each transition's `required_fields` exist in the form, and that its
+/// `condition` only references existing fields and compares values of compatible types.
+///
+/// # Arguments
+/// * `definition` - A reference to the `WorkflowDefinition` to validate.
+/// * `form` - The `FormDefinition` of the documents this workflow governs.
+///
+/// # Returns
+/// A `Result` which is `Ok` if all guards are valid for the form, or `Err` with
+/// `validator::ValidationErrors` describing every invalid guard.
+fn validate_transition_guards(
+    definition: &WorkflowDefinition,
+    form: &FormDefinition,
+) -> Result<(), validator::ValidationErrors> {
+    let field_ids: HashSet<&str> = form.fields().iter().map(|f| f.id()).collect();
+
+    let mut errors = validator::ValidationErrors::new();
+
+    for transition in definition.transitions.iter() {
+        for field_id in transition.required_fields.iter() {
+            if !field_ids.contains(field_id.as_str()) {
+                let mut err = ValidationError::new("unknown_required_field");
+                err.add_param("transition".into(), &transition.name);
+                err.add_param("field_id".into(), field_id);
+                errors.add("transitions", err);
+            }
+        }
+
+        if let Some(condition) = &transition.condition
+            && let Err(e) = condition.check(form)
+        {
+            let mut err = ValidationError::new("invalid_transition_condition");
+            err.add_param("transition".into(), &transition.name);
+            err.add_param("condition".into(), &condition.source());
+            err.add_param("error".into(), &e.to_string());
+            errors.add("transitions", err);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks the assignee of every phase against a form.
+///
+/// Ensures that each phase's `assignee` names a `User` field of the form.
+///
+/// # Arguments
+/// * `definition` - A reference to the `WorkflowDefinition` to validate.
+/// * `form` - The `FormDefinition` of the documents this workflow governs.
+///
+/// # Returns
+/// A `Result` which is `Ok` if all assignees are valid for the form, or `Err` with
+/// `validator::ValidationErrors` describing every invalid assignee.
+fn validate_phase_assignees(
+    definition: &WorkflowDefinition,
+    form: &FormDefinition,
+) -> Result<(), validator::ValidationErrors> {
+    let mut errors = validator::ValidationErrors::new();
+
+    for phase in definition.phases.iter() {
+        let Some(field_id) = &phase.assignee else {
+            continue;
+        };
+        let code = match form.fields().iter().find(|f| f.id() == field_id) {
+            None => "unknown_assignee_field",
+            Some(field) if !matches!(field.field_type(), FieldType::User { .. }) => {
+                "assignee_field_not_user"
+            }
+            Some(_) => continue,
+        };
+        let mut err = ValidationError::new(code);
+        err.add_param("phase_id".into(), &phase.id);
+        err.add_param("field_id".into(), field_id);
+        errors.add("phases", err);
     }
 
     if errors.is_empty() {
@@ -233,6 +550,13 @@ pub struct WorkflowBuilder {
     #[serde(default)]
     /// The transitions between phases in this workflow.
     pub transitions: Vec<Transition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The ID of the form whose documents this workflow governs.
+    pub form_id: Option<String>,
+    #[serde(skip)]
+    /// The form whose documents this workflow governs, used to check transition guards
+    /// and phase assignees.
+    pub form: Option<FormDefinition>,
 }
 
 impl WorkflowBuilder {
@@ -244,6 +568,8 @@ impl WorkflowBuilder {
             name: name.to_string(),
             phases: Vec::new(),
             transitions: Vec::new(),
+            form_id: None,
+            form: None,
         }
     }
 
@@ -259,6 +585,18 @@ impl WorkflowBuilder {
         self
     }
 
+    /// Binds the workflow to the given form, and checks transition guards and phase
+    /// assignees against it when the workflow is built.
+    ///
+    /// Without a form, conditions are only checked for syntax. With one, `build` also
+    /// rejects guards that reference undefined fields or compare incompatible types, and
+    /// assignees that are not `User` fields.
+    pub fn for_form(mut self, form: &FormDefinition) -> Self {
+        self.form_id = Some(form.id().to_string());
+        self.form = Some(form.clone());
+        self
+    }
+
     /// Builds a validated `WorkflowDefinition` from the `WorkflowBuilder` instance.
     ///
     /// # Returns
@@ -267,6 +605,22 @@ impl WorkflowBuilder {
     pub fn build(self) -> Result<WorkflowDefinition, validator::ValidationErrors> {
         WorkflowDefinition::try_from(self)
     }
+
+    /// Builds a `WorkflowDefinition` without validating it.
+    ///
+    /// This is meant for definitions read back from storage, which were validated when
+    /// they were saved but may not pass checks added since (such as the graph checks of
+    /// [`build`](Self::build)). Refusing to load them would strand their documents.
+    /// New and updated definitions must go through `build`.
+    pub fn build_lenient(self) -> WorkflowDefinition {
+        WorkflowDefinition {
+            id: self.id,
+            name: self.name,
+            phases: self.phases,
+            transitions: self.transitions,
+            form_id: self.form_id,
+        }
+    }
 }
 
 impl TryFrom<WorkflowBuilder> for WorkflowDefinition {
@@ -278,6 +632,7 @@ impl TryFrom<WorkflowBuilder> for WorkflowDefinition {
             name: builder.name,
             phases: builder.phases,
             transitions: builder.transitions,
+            form_id: builder.form_id,
         };
 
         // 1. Standard Field Validation
@@ -286,6 +641,11 @@ impl TryFrom<WorkflowBuilder> for WorkflowDefinition {
         // 2. Graph Integrity Validation (Custom Logic)
         validate_workflow_integrity(&wf)?;
 
+        // 3. Guard and Assignee Validation against the governed Form (if known)
+        if let Some(form) = &builder.form {
+            wf.check_form(form)?;
+        }
+
         Ok(wf)
     }
 }
@@ -326,10 +686,176 @@ mod tests {
         assert!(err_msg.contains("invalid_transition_target"));
     }
 
+    #[test]
+    fn test_stored_workflows_load_leniently() {
+        // Saved before the graph checks existed: "archived" cannot be reached
+        let stored = serde_json::json!({
+            "id": "wf_legacy",
+            "name": "Legacy Workflow",
+            "phases": [
+                { "id": "open", "label": "Open", "type": "start" },
+                { "id": "archived", "label": "Archived", "type": "end" },
+                { "id": "closed", "label": "Closed", "type": "end" }
+            ],
+            "transitions": [{ "name": "close", "from": "open", "to": "closed" }]
+        });
+
+        let builder: WorkflowBuilder = serde_json::from_value(stored.clone()).unwrap();
+        let err = builder.clone().build().unwrap_err();
+        assert!(err.to_string().contains("unreachable_phase"));
+        assert!(serde_json::from_value::<WorkflowDefinition>(stored).is_err());
+
+        let wf = builder.build_lenient();
+        assert!(wf.can_transition("open", "closed"));
+        assert_eq!(wf.get_start_phase().unwrap().id, "open");
+    }
+
+    #[test]
+    fn test_available_transitions() {
+        let wf = WorkflowBuilder::new("wf_1", "Test")
+            .add_phase(Phase::new("review", "Review", PhaseType::Start))
+            .add_phase(Phase::new("done", "Done", PhaseType::End))
+            // Two transitions between the same pair of phases
+            .add_transition(Transition::new("approve", "review", "done"))
+            .add_transition(Transition::new("withdraw", "review", "done"))
+            .build()
+            .unwrap();
+
+        let names: Vec<&str> = wf
+            .available_transitions("review")
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["approve", "withdraw"]);
+        assert!(wf.available_transitions("done").is_empty());
+
+        assert_eq!(wf.find_transition("review", "withdraw").unwrap().to, "done");
+        assert!(wf.find_transition("done", "approve").is_none());
+    }
+
+    #[test]
+    fn test_transition_guards_checked_against_form() {
+        use crate::field::{FieldBuilder, FieldType};
+        use crate::form::FormBuilder;
+
+        let form = FormBuilder::new("incident", "Incident")
+            .add_field(
+                FieldBuilder::new(
+                    "severity",
+                    "Severity",
+                    FieldType::Number {
+                        min: None,
+                        max: None,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let builder = |condition: &str, required: &[&str]| {
+            WorkflowBuilder::new("wf_1", "Test")
+                .add_phase(Phase::new("open", "Open", PhaseType::Start))
+                .add_phase(Phase::new("closed", "Closed", PhaseType::End))
+                .add_transition(
+                    Transition::new("close", "open", "closed")
+                        .with_required_fields(required)
+                        .with_condition(Expression::parse(condition).unwrap()),
+                )
+                .for_form(&form)
+        };
+
+        let wf = builder("severity >= 4", &["severity"]).build().unwrap();
+        assert_eq!(wf.form_id(), Some("incident"));
+
+        // The binding survives a round trip through storage
+        let stored: WorkflowDefinition =
+            serde_json::from_value(serde_json::to_value(&wf).unwrap()).unwrap();
+        assert_eq!(stored.form_id(), Some("incident"));
+
+        // Unbound workflows can still be checked once the form is known
+        let unbound = WorkflowBuilder {
+            form: None,
+            form_id: None,
+            ..builder("category == 'Safety'", &[])
+        }
+        .build()
+        .unwrap();
+        assert_eq!(unbound.form_id(), None);
+        assert!(unbound.check_form(&form).is_err());
+
+        let err = builder("category == 'Safety'", &[]).build().unwrap_err();
+        assert!(err.to_string().contains("invalid_transition_condition"));
+
+        let err = builder("severity == 'high'", &[]).build().unwrap_err();
+        assert!(err.to_string().contains("invalid_transition_condition"));
+
+        let err = builder("severity >= 4", &["root_cause"])
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown_required_field"));
+    }
+
+    #[test]
+    fn test_phase_assignees() {
+        use crate::field::FieldBuilder;
+        use crate::form::FormBuilder;
+        use serde_json::json;
+
+        let form = FormBuilder::new("capa", "Corrective Action")
+            .add_field(
+                FieldBuilder::new(
+                    "reviewers",
+                    "Reviewers",
+                    FieldType::User {
+                        allow_multiple: true,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new("owner", "Owner", FieldType::Boolean)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let builder = |assignee: &str| {
+            WorkflowBuilder::new("wf_capa", "CAPA")
+                .add_phase(Phase::new("draft", "Draft", PhaseType::Start))
+                .add_phase(
+                    Phase::new("review", "Review", PhaseType::Normal).with_assignee(assignee),
+                )
+                .add_phase(Phase::new("closed", "Closed", PhaseType::End))
+                .add_transition(Transition::new("submit", "draft", "review"))
+                .add_transition(Transition::new("approve", "review", "closed"))
+                .for_form(&form)
+        };
+
+        let err = builder("approver").build().unwrap_err();
+        assert!(err.to_string().contains("unknown_assignee_field"));
+        let err = builder("owner").build().unwrap_err();
+        assert!(err.to_string().contains("assignee_field_not_user"));
+
+        let wf = builder("reviewers").build().unwrap();
+        let mut doc = Document::new("capa-1", "capa", "wf_capa");
+        doc.set_value("reviewers", json!(["alice", "bob", "alice"]));
+
+        // Nobody is assigned while the document is drafted
+        doc.current_phase = "draft".into();
+        assert!(wf.assignees(&doc).is_empty());
+
+        doc.current_phase = "review".into();
+        assert_eq!(wf.assignees(&doc), vec!["alice", "bob"]);
+    }
+
     #[test]
     fn test_get_start_phase() {
         let wf = WorkflowBuilder::new("wf_1", "Test")
             .add_phase(Phase::new("draft", "Draft", PhaseType::Start))
+            .add_phase(Phase::new("done", "Done", PhaseType::End))
+            .add_transition(Transition::new("finish", "draft", "done"))
             .build()
             .unwrap();
 
@@ -337,4 +863,94 @@ mod tests {
         assert_eq!(start.id, "draft");
         assert!(matches!(start.phase_type, PhaseType::Start));
     }
+
+    /// Returns the codes of every error reported for `field`.
+    fn codes(errors: &validator::ValidationErrors, field: &str) -> Vec<String> {
+        errors
+            .field_errors()
+            .get(field)
+            .map(|errs| errs.iter().map(|e| e.code.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_start_phase_count() {
+        let err = WorkflowBuilder::new("wf_1", "No Start")
+            .add_phase(Phase::new("done", "Done", PhaseType::End))
+            .build()
+            .unwrap_err();
+        assert!(codes(&err, "phases").contains(&"missing_start_phase".to_string()));
+
+        let err = WorkflowBuilder::new("wf_1", "Two Starts")
+            .add_phase(Phase::new("a", "A", PhaseType::Start))
+            .add_phase(Phase::new("b", "B", PhaseType::Start))
+            .add_phase(Phase::new("done", "Done", PhaseType::End))
+            .add_transition(Transition::new("finish", "a", "done"))
+            .add_transition(Transition::new("finish", "b", "done"))
+            .build()
+            .unwrap_err();
+        assert_eq!(codes(&err, "phases"), vec!["multiple_start_phases"]);
+    }
+
+    #[test]
+    fn test_end_phase_is_terminal() {
+        let err = WorkflowBuilder::new("wf_1", "Reopen")
+            .add_phase(Phase::new("open", "Open", PhaseType::Start))
+            .add_phase(Phase::new("closed", "Closed", PhaseType::End))
+            .add_transition(Transition::new("close", "open", "closed"))
+            .add_transition(Transition::new("reopen", "closed", "open"))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            codes(&err, "transitions"),
+            vec!["transition_from_end_phase"]
+        );
+        assert!(codes(&err, "phases").is_empty());
+    }
+
+    #[test]
+    fn test_reachability() {
+        let err = WorkflowBuilder::new("wf_1", "Islands")
+            .add_phase(Phase::new("draft", "Draft", PhaseType::Start))
+            .add_phase(Phase::new("limbo", "Limbo", PhaseType::Normal))
+            .add_phase(Phase::new("orphan", "Orphan", PhaseType::Normal))
+            .add_phase(Phase::new("done", "Done", PhaseType::End))
+            .add_transition(Transition::new("finish", "draft", "done"))
+            // Limbo can be entered but never left
+            .add_transition(Transition::new("park", "draft", "limbo"))
+            // Orphan can reach the end, but nothing leads to it
+            .add_transition(Transition::new("finish", "orphan", "done"))
+            .build()
+            .unwrap_err();
+
+        let phase_errors = &err.field_errors()["phases"];
+        let found: Vec<(String, String)> = phase_errors
+            .iter()
+            .map(|e| (e.code.to_string(), e.params["phase_id"].to_string()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("unreachable_phase".to_string(), "\"orphan\"".to_string()),
+                ("end_phase_unreachable".to_string(), "\"limbo\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_ids_and_names() {
+        let err = WorkflowBuilder::new("wf_1", "Duplicates")
+            .add_phase(Phase::new("draft", "Draft", PhaseType::Start))
+            .add_phase(Phase::new("draft", "Draft Again", PhaseType::Normal))
+            .add_phase(Phase::new("done", "Done", PhaseType::End))
+            .add_transition(Transition::new("finish", "draft", "done"))
+            .add_transition(Transition::new("finish", "draft", "done"))
+            .build()
+            .unwrap_err();
+        assert_eq!(codes(&err, "phases"), vec!["duplicate_phase_id"]);
+        assert_eq!(
+            codes(&err, "transitions"),
+            vec!["duplicate_transition_name"]
+        );
+    }
 }
@@ -0,0 +1,210 @@
+//! This module finds the files attached to a document through its `Attachment` fields,
+//! and checks them once their metadata has been looked up.
+//!
+//! As with references, only storage knows the attachments, so checking is split into
+//! two synchronous steps around the caller's lookup: [`collect_attachment_ids`] lists
+//! the attachments a document holds, and [`check_attachments`] compares them with the
+//! metadata that was found.
+use crate::error::DocumentValidationError;
+use crate::validator::{value_ids, visit_values};
+use molten_core::attachment::Attachment;
+use molten_core::document::Document;
+use molten_core::field::FieldType;
+use molten_core::form::FormDefinition;
+use std::collections::HashMap;
+
+/// Lists the IDs of every attachment held by a document, including from fields within
+/// groups.
+///
+/// # Arguments
+/// * `doc` - The document.
+/// * `form` - The `FormDefinition` the document adheres to.
+///
+/// # Returns
+/// The attachment IDs in field order, each listed once.
+pub fn collect_attachment_ids(doc: &Document, form: &FormDefinition) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    visit_values(&|id| doc.data.get(id), form, "", &mut |_, field, value| {
+        if let FieldType::Attachment { .. } = field.field_type() {
+            for id in value_ids(value) {
+                if !ids.iter().any(|i| i == id) {
+                    ids.push(id.to_string());
+                }
+            }
+        }
+    });
+    ids
+}
+
+/// Checks the attachments held by a document against the metadata that was found for them.
+///
+/// # Arguments
+/// * `doc` - The document.
+/// * `form` - The `FormDefinition` the document adheres to.
+/// * `found` - The metadata of every attachment that exists, keyed by attachment ID.
+///
+/// # Returns
+/// A `Result` which is `Ok(())` if every attachment exists and fits the `max_size` and
+/// `allowed_mime_types` of its field, or `Err(Vec<DocumentValidationError>)` with one
+/// error per offending attachment.
+pub fn check_attachments(
+    doc: &Document,
+    form: &FormDefinition,
+    found: &HashMap<String, Attachment>,
+) -> Result<(), Vec<DocumentValidationError>> {
+    let mut errors = Vec::new();
+    visit_values(
+        &|id| doc.data.get(id),
+        form,
+        "",
+        &mut |path, field, value| {
+            let FieldType::Attachment {
+                max_size,
+                allowed_mime_types,
+                ..
+            } = field.field_type()
+            else {
+                return;
+            };
+            for id in value_ids(value) {
+                let Some(attachment) = found.get(id) else {
+                    errors.push(DocumentValidationError::AttachmentNotFound {
+                        field_id: path.to_string(),
+                        attachment_id: id.to_string(),
+                    });
+                    continue;
+                };
+                if let Some(max_size) = max_size
+                    && attachment.size > *max_size
+                {
+                    errors.push(DocumentValidationError::AttachmentTooLarge {
+                        field_id: path.to_string(),
+                        attachment_id: id.to_string(),
+                        size: attachment.size,
+                        max_size: *max_size,
+                    });
+                }
+                if !attachment.has_mime_type(allowed_mime_types) {
+                    errors.push(DocumentValidationError::AttachmentTypeNotAllowed {
+                        field_id: path.to_string(),
+                        attachment_id: id.to_string(),
+                        mime_type: attachment.mime_type.clone(),
+                        allowed: allowed_mime_types.clone(),
+                    });
+                }
+            }
+        },
+    );
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use molten_core::field::FieldBuilder;
+    use molten_core::form::FormBuilder;
+    use serde_json::json;
+
+    fn attachment(id: &str, mime_type: &str, size: u64) -> (String, Attachment) {
+        let attachment = Attachment {
+            id: id.into(),
+            file_name: format!("{}.bin", id),
+            mime_type: mime_type.into(),
+            size,
+            sha256: String::new(),
+            uploaded_by: "alice".into(),
+            created_at: Utc::now(),
+        };
+        (id.to_string(), attachment)
+    }
+
+    #[test]
+    fn test_attachments() {
+        let form = FormBuilder::new("inspection", "Inspection")
+            .add_field(
+                FieldBuilder::new(
+                    "report",
+                    "Report",
+                    FieldType::Attachment {
+                        max_size: None,
+                        allowed_mime_types: vec!["application/pdf".into()],
+                        allow_multiple: false,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "findings",
+                    "Findings",
+                    FieldType::Group {
+                        fields: vec![
+                            FieldBuilder::new(
+                                "photos",
+                                "Photos",
+                                FieldType::Attachment {
+                                    max_size: Some(1000),
+                                    allowed_mime_types: vec!["image/*".into()],
+                                    allow_multiple: true,
+                                },
+                            )
+                            .build()
+                            .unwrap(),
+                        ],
+                        min_items: None,
+                        max_items: None,
+                        repeatable: true,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let mut doc = Document::new("insp-1", "inspection", "flow_inspection");
+        doc.set_value("report", json!("att-1"));
+        doc.set_value(
+            "findings",
+            json!([{ "photos": ["att-2", "att-3"] }, { "photos": ["att-2", "att-4"] }]),
+        );
+        assert_eq!(
+            collect_attachment_ids(&doc, &form),
+            vec!["att-1", "att-2", "att-3", "att-4"]
+        );
+
+        let found = HashMap::from([
+            attachment("att-1", "application/pdf", 50_000),
+            attachment("att-2", "image/jpeg", 800),
+            attachment("att-3", "application/pdf", 1200),
+        ]);
+        assert_eq!(
+            check_attachments(&doc, &form, &found).unwrap_err(),
+            vec![
+                DocumentValidationError::AttachmentTooLarge {
+                    field_id: "findings/0/photos".into(),
+                    attachment_id: "att-3".into(),
+                    size: 1200,
+                    max_size: 1000,
+                },
+                DocumentValidationError::AttachmentTypeNotAllowed {
+                    field_id: "findings/0/photos".into(),
+                    attachment_id: "att-3".into(),
+                    mime_type: "application/pdf".into(),
+                    allowed: vec!["image/*".into()],
+                },
+                DocumentValidationError::AttachmentNotFound {
+                    field_id: "findings/1/photos".into(),
+                    attachment_id: "att-4".into(),
+                },
+            ]
+        );
+    }
+}
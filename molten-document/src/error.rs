@@ -7,12 +7,19 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Represents errors encountered during the document validation process.
-#[derive(Error, Debug, PartialEq, Serialize, Deserialize)]
+///
+/// Fields nested in a `Group` are identified by their JSON-pointer-style path within the
+/// document data, such as `parts/2/qty` for the `qty` of the third item of `parts`.
+#[derive(Error, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DocumentValidationError {
     /// Indicates that a required field was missing from the document or its value was null.
     #[error("Field '{0}' is required but was missing or null")]
     MissingRequiredField(String),
 
+    /// Indicates that a field hidden by its `visible_when` condition holds a value.
+    #[error("Field '{0}' is hidden and must be absent or null")]
+    HiddenFieldSet(String),
+
     /// Indicates a type mismatch for a field's value.
     #[error("Field '{field_id}' expected type '{expected_type}', but got '{got_type}'")]
     InvalidType {
@@ -24,6 +31,39 @@ pub enum DocumentValidationError {
         got_type: String,
     },
 
+    /// Indicates that a text field's value has fewer characters than its minimum length.
+    #[error("Field '{field_id}' has {length} characters, fewer than the minimum of {min_length}")]
+    TextTooShort {
+        /// The ID of the text field.
+        field_id: String,
+        /// The number of characters in the value.
+        length: usize,
+        /// The minimum allowed number of characters.
+        min_length: usize,
+    },
+
+    /// Indicates that a text field's value has more characters than its maximum length.
+    #[error("Field '{field_id}' has {length} characters, more than the maximum of {max_length}")]
+    TextTooLong {
+        /// The ID of the text field.
+        field_id: String,
+        /// The number of characters in the value.
+        length: usize,
+        /// The maximum allowed number of characters.
+        max_length: usize,
+    },
+
+    /// Indicates that a text field's value does not match its pattern.
+    #[error("Field '{field_id}' value '{value}' does not match pattern '{pattern}'")]
+    PatternMismatch {
+        /// The ID of the text field.
+        field_id: String,
+        /// The value that did not match.
+        value: String,
+        /// The pattern the value must match.
+        pattern: String,
+    },
+
     /// Indicates that a numerical field's value is below its specified minimum.
     #[error("Field '{field_id}' value {value} is less than minimum {min}")]
     ValueTooLow {
@@ -46,6 +86,43 @@ pub enum DocumentValidationError {
         max: f64,
     },
 
+    /// Indicates that a decimal field's value is not a decimal number.
+    #[error("Field '{field_id}' expected a decimal number, but got '{value}'")]
+    InvalidDecimal {
+        /// The ID of the decimal field.
+        field_id: String,
+        /// The value received, as text.
+        value: String,
+    },
+
+    /// Indicates that a decimal field's value has more digits after the decimal point
+    /// than its scale allows.
+    #[error("Field '{field_id}' value {value} has more than {scale} decimal places")]
+    DecimalScaleExceeded {
+        /// The ID of the decimal field.
+        field_id: String,
+        /// The value received, as text.
+        value: String,
+        /// The maximum number of digits after the decimal point.
+        scale: u32,
+    },
+
+    /// Indicates that a decimal field's value has more digits before the decimal point
+    /// than its precision and scale allow.
+    #[error(
+        "Field '{field_id}' value {value} does not fit {precision} digits with {scale} decimal places"
+    )]
+    DecimalPrecisionExceeded {
+        /// The ID of the decimal field.
+        field_id: String,
+        /// The value received, as text.
+        value: String,
+        /// The maximum total number of digits.
+        precision: u32,
+        /// The maximum number of digits after the decimal point.
+        scale: u32,
+    },
+
     /// Indicates that a selection field's value is not among the allowed options.
     #[error("Field '{field_id}' value '{value}' is not a valid option. Allowed: {allowed:?}")]
     InvalidSelection {
@@ -66,6 +143,164 @@ pub enum DocumentValidationError {
         value: String,
     },
 
+    /// Indicates that a time field's value is not a valid ISO 8601 time of day.
+    #[error("Field '{field_id}' expected a time string (ISO 8601), but got '{value}'")]
+    InvalidTimeFormat {
+        /// The ID of the time field.
+        field_id: String,
+        /// The invalid time string received.
+        value: String,
+    },
+
+    /// Indicates that a duration field's value is not a valid ISO 8601 duration.
+    #[error("Field '{field_id}' expected a duration string (ISO 8601), but got '{value}'")]
+    InvalidDurationFormat {
+        /// The ID of the duration field.
+        field_id: String,
+        /// The invalid duration string received.
+        value: String,
+    },
+
+    /// Indicates that a date or time field's value is before its earliest allowed value.
+    #[error("Field '{field_id}' value '{value}' is earlier than minimum '{min}'")]
+    TooEarly {
+        /// The ID of the date or time field.
+        field_id: String,
+        /// The value that was too early.
+        value: String,
+        /// The earliest allowed value, resolved if the bound was relative.
+        min: String,
+    },
+
+    /// Indicates that a date or time field's value is after its latest allowed value.
+    #[error("Field '{field_id}' value '{value}' is later than maximum '{max}'")]
+    TooLate {
+        /// The ID of the date or time field.
+        field_id: String,
+        /// The value that was too late.
+        value: String,
+        /// The latest allowed value, resolved if the bound was relative.
+        max: String,
+    },
+
+    /// Indicates that a duration field's value is shorter than its minimum.
+    #[error("Field '{field_id}' duration '{value}' is shorter than minimum '{min}'")]
+    DurationTooShort {
+        /// The ID of the duration field.
+        field_id: String,
+        /// The duration that was too short.
+        value: String,
+        /// The shortest allowed duration.
+        min: String,
+    },
+
+    /// Indicates that a duration field's value is longer than its maximum.
+    #[error("Field '{field_id}' duration '{value}' is longer than maximum '{max}'")]
+    DurationTooLong {
+        /// The ID of the duration field.
+        field_id: String,
+        /// The duration that was too long.
+        value: String,
+        /// The longest allowed duration.
+        max: String,
+    },
+
+    /// Indicates that a repeatable group holds fewer items than its minimum.
+    #[error("Field '{field_id}' has {count} items, fewer than the minimum of {min_items}")]
+    TooFewItems {
+        /// The ID of the group field.
+        field_id: String,
+        /// The number of items in the value.
+        count: usize,
+        /// The minimum allowed number of items.
+        min_items: usize,
+    },
+
+    /// Indicates that a repeatable group holds more items than its maximum.
+    #[error("Field '{field_id}' has {count} items, more than the maximum of {max_items}")]
+    TooManyItems {
+        /// The ID of the group field.
+        field_id: String,
+        /// The number of items in the value.
+        count: usize,
+        /// The maximum allowed number of items.
+        max_items: usize,
+    },
+
+    /// Indicates that a reference field names a document that does not exist.
+    #[error("Field '{field_id}' references document '{document_id}', which does not exist")]
+    ReferenceNotFound {
+        /// The ID of the reference field.
+        field_id: String,
+        /// The ID of the missing document.
+        document_id: String,
+    },
+
+    /// Indicates that a reference field names a document of another form.
+    #[error(
+        "Field '{field_id}' references document '{document_id}' of form '{actual_form}', expected form '{expected_form}'"
+    )]
+    ReferenceFormMismatch {
+        /// The ID of the reference field.
+        field_id: String,
+        /// The ID of the referenced document.
+        document_id: String,
+        /// The form the field requires.
+        expected_form: String,
+        /// The form the referenced document belongs to.
+        actual_form: String,
+    },
+
+    /// Indicates that an attachment field names an attachment that does not exist.
+    #[error("Field '{field_id}' holds attachment '{attachment_id}', which does not exist")]
+    AttachmentNotFound {
+        /// The ID of the attachment field.
+        field_id: String,
+        /// The ID of the missing attachment.
+        attachment_id: String,
+    },
+
+    /// Indicates that an attached file is larger than its field allows.
+    #[error(
+        "Field '{field_id}' holds attachment '{attachment_id}' of {size} bytes, more than the maximum of {max_size}"
+    )]
+    AttachmentTooLarge {
+        /// The ID of the attachment field.
+        field_id: String,
+        /// The ID of the attachment.
+        attachment_id: String,
+        /// The size of the file, in bytes.
+        size: u64,
+        /// The largest size the field allows, in bytes.
+        max_size: u64,
+    },
+
+    /// Indicates that an attached file is of a type its field does not allow.
+    #[error(
+        "Field '{field_id}' holds attachment '{attachment_id}' of type '{mime_type}', expected one of {allowed:?}"
+    )]
+    AttachmentTypeNotAllowed {
+        /// The ID of the attachment field.
+        field_id: String,
+        /// The ID of the attachment.
+        attachment_id: String,
+        /// The MIME type of the file.
+        mime_type: String,
+        /// The MIME types the field allows.
+        allowed: Vec<String>,
+    },
+
+    /// Indicates that the document violates one of its form's cross-field rules.
+    #[error("Rule '{rule_id}' violated: {message}")]
+    RuleViolated {
+        /// The ID of the violated rule.
+        rule_id: String,
+        /// The message declared on the rule.
+        message: String,
+        /// The IDs of the fields the rule involves.
+        fields: Vec<String>,
+    },
+
     /// Indicates that the document's `form_id` does not match the `FormDefinition`'s ID.
     #[error("Document form_id '{doc_form}' does not match definition id '{def_id}'")]
     FormIdMismatch {
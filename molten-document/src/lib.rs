@@ -8,8 +8,14 @@
 
 #![warn(missing_docs)]
 
+pub mod attachment;
 pub mod error;
+pub mod patch;
+pub mod reference;
 pub mod validator;
 
+pub use attachment::{check_attachments, collect_attachment_ids};
 pub use error::DocumentValidationError;
-pub use validator::validate_document;
+pub use patch::{apply_merge_patch, merge_patch};
+pub use reference::{check_references, collect_references};
+pub use validator::{validate_document, validate_document_at, validate_field_value};
@@ -0,0 +1,126 @@
+//! This module implements JSON Merge Patch (RFC 7396) for document data.
+//!
+//! A merge patch describes changes using the shape of the document itself: keys
+//! present in the patch replace the target's values, nested objects are merged
+//! recursively, and `null` removes a key.
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Applies an RFC 7396 merge patch to a JSON value in place.
+///
+/// # Arguments
+/// * `target` - The value to modify.
+/// * `patch` - The merge patch to apply.
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        // A non-object patch replaces the target wholesale
+        *target = patch.clone();
+        return;
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// Applies an RFC 7396 merge patch to a document's data map in place.
+///
+/// Top-level keys follow the same rules as [`merge_patch`]: `null` removes the field,
+/// objects are merged into the existing value, and anything else replaces it.
+///
+/// # Arguments
+/// * `data` - The document data, keyed by field ID.
+/// * `patch` - The merge patch to apply, keyed by field ID.
+pub fn apply_merge_patch(data: &mut HashMap<String, Value>, patch: &Map<String, Value>) {
+    for (key, value) in patch {
+        if value.is_null() {
+            data.remove(key);
+        } else {
+            merge_patch(data.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rfc7396_examples() {
+        // Test cases from RFC 7396, Appendix A
+        let cases = [
+            (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+            (
+                json!({"a": "b"}),
+                json!({"b": "c"}),
+                json!({"a": "b", "b": "c"}),
+            ),
+            (json!({"a": "b"}), json!({"a": null}), json!({})),
+            (
+                json!({"a": "b", "b": "c"}),
+                json!({"a": null}),
+                json!({"b": "c"}),
+            ),
+            (json!({"a": ["b"]}), json!({"a": "c"}), json!({"a": "c"})),
+            (json!({"a": "c"}), json!({"a": ["b"]}), json!({"a": ["b"]})),
+            (
+                json!({"a": {"b": "c"}}),
+                json!({"a": {"b": "d", "c": null}}),
+                json!({"a": {"b": "d"}}),
+            ),
+            (
+                json!({"a": [{"b": "c"}]}),
+                json!({"a": [1]}),
+                json!({"a": [1]}),
+            ),
+            (json!(["a", "b"]), json!(["c", "d"]), json!(["c", "d"])),
+            (json!({"a": "b"}), json!(["c"]), json!(["c"])),
+            (json!({"a": "foo"}), json!(null), json!(null)),
+            (json!({"a": "foo"}), json!("bar"), json!("bar")),
+            (
+                json!({"e": null}),
+                json!({"a": 1}),
+                json!({"e": null, "a": 1}),
+            ),
+            (
+                json!([1, 2]),
+                json!({"a": "b", "c": null}),
+                json!({"a": "b"}),
+            ),
+            (
+                json!({}),
+                json!({"a": {"bb": {"ccc": null}}}),
+                json!({"a": {"bb": {}}}),
+            ),
+        ];
+
+        for (mut target, patch, expected) in cases {
+            merge_patch(&mut target, &patch);
+            assert_eq!(target, expected, "patch {}", patch);
+        }
+    }
+
+    #[test]
+    fn test_apply_to_document_data() {
+        let mut data: HashMap<String, Value> = HashMap::new();
+        data.insert("title".into(), json!("Teh title"));
+        data.insert("severity".into(), json!(3));
+
+        let patch = json!({"title": "The title", "severity": null, "tags": ["a"]});
+        apply_merge_patch(&mut data, patch.as_object().unwrap());
+
+        assert_eq!(data.get("title"), Some(&json!("The title")));
+        assert!(!data.contains_key("severity"));
+        assert_eq!(data.get("tags"), Some(&json!(["a"])));
+    }
+}
@@ -0,0 +1,170 @@
+//! This module finds the documents referenced by a document's `Reference` fields, and
+//! checks them once they have been looked up.
+//!
+//! Only storage can tell whether a referenced document exists, so reference checking
+//! is split into two synchronous steps around the caller's lookup:
+//! [`collect_references`] lists the references a document holds, and
+//! [`check_references`] compares them with the documents that were found.
+use crate::error::DocumentValidationError;
+use crate::validator::{value_ids, visit_values};
+use molten_core::document::{Document, DocumentReference};
+use molten_core::field::FieldType;
+use molten_core::form::FormDefinition;
+use std::collections::HashMap;
+
+/// Lists every document referenced by a document, including from fields within groups.
+///
+/// Values that are not document IDs are skipped, as `validate_document` reports them.
+///
+/// # Arguments
+/// * `doc` - The referencing document.
+/// * `form` - The `FormDefinition` the document adheres to.
+///
+/// # Returns
+/// The references in field order, each naming the form the referenced document must
+/// belong to as its `target_form_id`.
+pub fn collect_references(doc: &Document, form: &FormDefinition) -> Vec<DocumentReference> {
+    let mut references = Vec::new();
+    visit_values(
+        &|id| doc.data.get(id),
+        form,
+        "",
+        &mut |path, field, value| {
+            if let FieldType::Reference { form_id, .. } = field.field_type() {
+                references.extend(value_ids(value).into_iter().map(|target_id| {
+                    DocumentReference {
+                        document_id: doc.id.clone(),
+                        field_id: path.to_string(),
+                        target_id: target_id.to_string(),
+                        target_form_id: form_id.clone(),
+                    }
+                }));
+            }
+        },
+    );
+    references
+}
+
+/// Checks references against the documents that were found for them.
+///
+/// # Arguments
+/// * `references` - The references to check, as listed by [`collect_references`].
+/// * `found` - The form ID of every referenced document that exists, keyed by document ID.
+///
+/// # Returns
+/// A `Result` which is `Ok(())` if every referenced document exists and belongs to the
+/// expected form, or `Err(Vec<DocumentValidationError>)` with one error per broken reference.
+pub fn check_references(
+    references: &[DocumentReference],
+    found: &HashMap<String, String>,
+) -> Result<(), Vec<DocumentValidationError>> {
+    let errors: Vec<DocumentValidationError> = references
+        .iter()
+        .filter_map(|reference| match found.get(&reference.target_id) {
+            None => Some(DocumentValidationError::ReferenceNotFound {
+                field_id: reference.field_id.clone(),
+                document_id: reference.target_id.clone(),
+            }),
+            Some(form_id) if *form_id != reference.target_form_id => {
+                Some(DocumentValidationError::ReferenceFormMismatch {
+                    field_id: reference.field_id.clone(),
+                    document_id: reference.target_id.clone(),
+                    expected_form: reference.target_form_id.clone(),
+                    actual_form: form_id.clone(),
+                })
+            }
+            Some(_) => None,
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use molten_core::field::FieldBuilder;
+    use molten_core::form::FormBuilder;
+    use serde_json::json;
+
+    #[test]
+    fn test_references() {
+        let reference = |id: &str, form_id: &str, allow_multiple| {
+            FieldBuilder::new(
+                id,
+                id,
+                FieldType::Reference {
+                    form_id: form_id.into(),
+                    allow_multiple,
+                },
+            )
+            .build()
+            .unwrap()
+        };
+        let form = FormBuilder::new("capa", "Corrective Action")
+            .add_field(reference("incident", "incident", false))
+            .add_field(
+                FieldBuilder::new(
+                    "actions",
+                    "Actions",
+                    FieldType::Group {
+                        fields: vec![reference("procedures", "procedure", true)],
+                        min_items: None,
+                        max_items: None,
+                        repeatable: true,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let mut doc = Document::new("capa-1", "capa", "flow_capa");
+        doc.set_value("incident", json!("inc-1"));
+        doc.set_value(
+            "actions",
+            json!([{ "procedures": ["sop-1"] }, { "procedures": ["sop-2", "inc-2"] }]),
+        );
+
+        let references = collect_references(&doc, &form);
+        let targets: Vec<(&str, &str)> = references
+            .iter()
+            .map(|r| (r.field_id.as_str(), r.target_id.as_str()))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                ("incident", "inc-1"),
+                ("actions/0/procedures", "sop-1"),
+                ("actions/1/procedures", "sop-2"),
+                ("actions/1/procedures", "inc-2"),
+            ]
+        );
+
+        let found = HashMap::from([
+            ("inc-1".to_string(), "incident".to_string()),
+            ("sop-1".to_string(), "procedure".to_string()),
+            ("inc-2".to_string(), "incident".to_string()),
+        ]);
+        assert_eq!(
+            check_references(&references, &found).unwrap_err(),
+            vec![
+                DocumentValidationError::ReferenceNotFound {
+                    field_id: "actions/1/procedures".into(),
+                    document_id: "sop-2".into(),
+                },
+                DocumentValidationError::ReferenceFormMismatch {
+                    field_id: "actions/1/procedures".into(),
+                    document_id: "inc-2".into(),
+                    expected_form: "procedure".into(),
+                    actual_form: "incident".into(),
+                },
+            ]
+        );
+    }
+}
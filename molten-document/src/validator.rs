@@ -3,20 +3,34 @@
 //!
 //! It includes functions to perform comprehensive checks on document data,
 //! ensuring that all required fields are present, data types match, and
-//! specific constraints (like numerical ranges or selection options) are met.
+//! specific constraints (like numerical ranges or selection options) are met, and
+//! that the form's cross-field rules hold.
 use crate::error::DocumentValidationError;
+use chrono::{DateTime, Utc};
 use molten_core::document::Document;
-use molten_core::field::{FieldDefinition, FieldType};
+use molten_core::field::{FieldDefinition, FieldType, integer_digits, parse_decimal};
 use molten_core::form::FormDefinition;
+use molten_core::rule::{FormRule, RuleKind};
+use molten_core::temporal::{IsoDuration, parse_date, parse_time};
+use rust_decimal::prelude::ToPrimitive;
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 
 /// Validates a `Document` against its `FormDefinition`.
 ///
 /// This function performs a comprehensive check, ensuring:
 /// 1. The `Document`'s `form_id` matches the `FormDefinition`'s ID.
-/// 2. All `required` fields as defined in the `FormDefinition` are present and not null in the `Document`.
+/// 2. All `required` fields as defined in the `FormDefinition` (including fields whose `required_when`
+///    condition holds) are present and not null in the `Document`, and fields hidden by their
+///    `visible_when` condition are absent or null.
 /// 3. Data types for each field in the `Document` match the `FieldType` specified in the `FormDefinition`.
-/// 4. Specific constraints (e.g., `min`/`max` for numbers, `options` for selects, ISO 8601 for dates) are met.
+/// 4. Specific constraints (e.g., length and `pattern` for text, `min`/`max` for numbers, dates, times
+///    and durations, `options` for selects, ISO 8601 for dates) are met.
+/// 5. The form's cross-field rules hold. Rules involving a field that already failed one of
+///    the checks above are skipped, as their outcome would be meaningless.
+/// 6. `Group` fields hold an object (or, if repeatable, a list of objects within the item
+///    bounds), and each item passes the checks above against the group's fields. Errors
+///    within groups identify fields by their path, such as `parts/2/qty`.
 ///
 /// # Arguments
 /// * `doc` - A reference to the `Document` to be validated.
@@ -28,6 +42,28 @@ use serde_json::Value;
 pub fn validate_document(
     doc: &Document,
     form: &FormDefinition,
+) -> Result<(), Vec<DocumentValidationError>> {
+    validate_document_at(doc, form, Utc::now())
+}
+
+/// Validates a `Document` against its `FormDefinition` as of a given moment.
+///
+/// Identical to [`validate_document`], except that relative bounds (such as a `Date`
+/// field that may not be later than `today`) are resolved against `now` rather than the
+/// current time.
+///
+/// # Arguments
+/// * `doc` - A reference to the `Document` to be validated.
+/// * `form` - A reference to the `FormDefinition` to validate against.
+/// * `now` - The moment relative bounds are resolved against.
+///
+/// # Returns
+/// A `Result` which is `Ok(())` if the document is valid, or `Err(Vec<DocumentValidationError>)`
+/// containing a list of all validation errors found.
+pub fn validate_document_at(
+    doc: &Document,
+    form: &FormDefinition,
+    now: DateTime<Utc>,
 ) -> Result<(), Vec<DocumentValidationError>> {
     let mut errors = Vec::new();
 
@@ -40,24 +76,115 @@ pub fn validate_document(
         return Err(errors);
     }
 
-    // 2. Iterate over every field defined in the schema
+    // 2. Validate the fields and rules, recursing into groups
+    validate_data(&doc.data, form, "", now, &mut errors);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Validates data against the fields and rules of a form, which is either the form of a
+/// document or the form of one item of a `Group` field.
+///
+/// # Arguments
+/// * `data` - The document data, or the data of one group item.
+/// * `form` - The form the data must conform to.
+/// * `prefix` - The path of the data within the document, empty for the document itself.
+/// * `now` - The moment relative date and time bounds are resolved against.
+/// * `errors` - Collects every error found, identifying fields by their path.
+fn validate_data(
+    data: &HashMap<String, Value>,
+    form: &FormDefinition,
+    prefix: &str,
+    now: DateTime<Utc>,
+    errors: &mut Vec<DocumentValidationError>,
+) {
+    let mut invalid_fields = HashSet::new();
     for field_def in form.fields() {
-        let value = doc.get_value(field_def.id());
+        let value = data.get(field_def.id());
+        let path = pointer(prefix, field_def.id());
+
+        // Check Visibility
+        if !field_def.is_visible(data, form) {
+            if value.is_some_and(|v| !v.is_null()) {
+                errors.push(DocumentValidationError::HiddenFieldSet(path));
+                invalid_fields.insert(field_def.id());
+            }
+            continue; // Hidden fields are never required
+        }
 
-        // Check Required
-        if field_def.is_required() && (value.is_none() || value.unwrap().is_null()) {
-            errors.push(DocumentValidationError::MissingRequiredField(
-                field_def.id().to_string(),
-            ));
+        // Check Required, unconditionally or by `required_when`
+        if field_def.is_required_for(data, form) && value.is_none_or(Value::is_null) {
+            errors.push(DocumentValidationError::MissingRequiredField(path));
+            invalid_fields.insert(field_def.id());
             continue; // Cannot validate type if missing
         }
 
         // If value exists, validate its content
-        if let Some(val) = value
-            && !val.is_null()
-        {
-            if let Err(e) = validate_value(val, field_def) {
-                errors.push(e);
+        let Some(val) = value.filter(|v| !v.is_null()) else {
+            continue;
+        };
+        if let Err(e) = validate_value(val, field_def, &path, now) {
+            errors.push(e);
+            invalid_fields.insert(field_def.id());
+            continue;
+        }
+
+        // Validate each item of a group like a document of its own
+        if let Some(group) = field_def.group() {
+            let found = errors.len();
+            validate_group_items(val, group, &path, now, errors);
+            if errors.len() > found {
+                invalid_fields.insert(field_def.id());
+            }
+        }
+    }
+
+    // Evaluate the cross-field rules over the fields that passed
+    for rule in form.rules() {
+        let fields = rule.fields();
+        if fields.iter().any(|f| invalid_fields.contains(f)) {
+            continue;
+        }
+        if !rule_holds(rule, data, form) {
+            errors.push(DocumentValidationError::RuleViolated {
+                rule_id: rule.id.clone(),
+                message: rule.message.clone(),
+                fields: fields.into_iter().map(|f| pointer(prefix, f)).collect(),
+            });
+        }
+    }
+}
+
+/// Validates the value of a single top-level field against its `FieldDefinition`.
+///
+/// This applies the same type and constraint checks as [`validate_document`] does for
+/// the field, including the items of a `Group` field, but ignores requiredness,
+/// visibility and cross-field rules. Workflow guards use it to check that the fields a
+/// transition requires hold valid values.
+///
+/// # Arguments
+/// * `value` - The value of the field, which should not be `null`.
+/// * `field` - The `FieldDefinition` to validate against.
+///
+/// # Returns
+/// `Ok(())` if the value is valid, or `Err(Vec<DocumentValidationError>)` with every
+/// error found.
+pub fn validate_field_value(
+    value: &Value,
+    field: &FieldDefinition,
+) -> Result<(), Vec<DocumentValidationError>> {
+    let now = Utc::now();
+    let path = field.id().to_string();
+    let mut errors = Vec::new();
+    match validate_value(value, field, &path, now) {
+        Err(e) => errors.push(e),
+        Ok(()) => {
+            if let Some(group) = field.group() {
+                validate_group_items(value, group, &path, now, &mut errors);
             }
         }
     }
@@ -69,36 +196,114 @@ pub fn validate_document(
     }
 }
 
+/// Validates each item of a `Group` field's value like a document of its own.
+///
+/// # Arguments
+/// * `value` - The group's value, a single item or a list of items.
+/// * `group` - The form of one item of the group.
+/// * `path` - The path of the group within the document.
+/// * `now` - The moment relative date and time bounds are resolved against.
+/// * `errors` - Collects every error found, identifying fields by their path.
+fn validate_group_items(
+    value: &Value,
+    group: &FormDefinition,
+    path: &str,
+    now: DateTime<Utc>,
+    errors: &mut Vec<DocumentValidationError>,
+) {
+    let items: Vec<(String, &Value)> = match value {
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (pointer(path, &i.to_string()), item))
+            .collect(),
+        item => vec![(path.to_string(), item)],
+    };
+    for (item_path, item) in items {
+        if let Value::Object(map) = item {
+            let item_data: HashMap<String, Value> =
+                map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            validate_data(&item_data, group, &item_path, now, errors);
+        }
+    }
+}
+
 /// Validates a single `serde_json::Value` against a `FieldDefinition`.
 ///
 /// This private helper function checks the value's type and applies any constraints
-/// specified in the `FieldDefinition` (e.g., numerical ranges, valid selection options,
-/// or date format).
+/// specified in the `FieldDefinition` (e.g., text length and pattern, numerical ranges,
+/// valid selection options, or date format).
 ///
 /// # Arguments
 /// * `value` - A reference to the `serde_json::Value` to validate.
 /// * `field` - A reference to the `FieldDefinition` to validate against.
+/// * `path` - The path of the value within the document, used to identify it in errors.
+/// * `now` - The moment relative date and time bounds are resolved against.
 ///
 /// # Returns
 /// A `Result` which is `Ok(())` if the value is valid according to the field definition,
 /// or `Err(DocumentValidationError)` if any validation rule is violated.
-fn validate_value(value: &Value, field: &FieldDefinition) -> Result<(), DocumentValidationError> {
+fn validate_value(
+    value: &Value,
+    field: &FieldDefinition,
+    path: &str,
+    now: DateTime<Utc>,
+) -> Result<(), DocumentValidationError> {
     match field.field_type() {
-        FieldType::Text | FieldType::TextArea => {
-            if !value.is_string() {
-                return Err(DocumentValidationError::InvalidType {
-                    field_id: field.id().to_string(),
+        FieldType::Text {
+            min_length,
+            max_length,
+            ..
+        }
+        | FieldType::TextArea {
+            min_length,
+            max_length,
+            ..
+        } => {
+            let s = value
+                .as_str()
+                .ok_or_else(|| DocumentValidationError::InvalidType {
+                    field_id: path.to_string(),
                     expected_type: "String".to_string(),
                     got_type: get_json_type(value),
+                })?;
+
+            let length = s.chars().count();
+            if let Some(min) = min_length
+                && length < *min
+            {
+                return Err(DocumentValidationError::TextTooShort {
+                    field_id: path.to_string(),
+                    length,
+                    min_length: *min,
+                });
+            }
+            if let Some(max) = max_length
+                && length > *max
+            {
+                return Err(DocumentValidationError::TextTooLong {
+                    field_id: path.to_string(),
+                    length,
+                    max_length: *max,
+                });
+            }
+
+            // The pattern was compiled when the form was built
+            if let Some(pattern) = field.pattern()
+                && !pattern.is_match(s)
+            {
+                return Err(DocumentValidationError::PatternMismatch {
+                    field_id: path.to_string(),
+                    value: s.to_string(),
+                    pattern: field_pattern(field.field_type()),
                 });
             }
-            // Future: Add Regex validation here
         }
         FieldType::Number { min, max } => {
             let num = value
                 .as_f64()
                 .ok_or_else(|| DocumentValidationError::InvalidType {
-                    field_id: field.id().to_string(),
+                    field_id: path.to_string(),
                     expected_type: "Number".to_string(),
                     got_type: get_json_type(value),
                 })?;
@@ -107,7 +312,7 @@ fn validate_value(value: &Value, field: &FieldDefinition) -> Result<(), Document
                 && num < *min_val
             {
                 return Err(DocumentValidationError::ValueTooLow {
-                    field_id: field.id().to_string(),
+                    field_id: path.to_string(),
                     value: num,
                     min: *min_val,
                 });
@@ -116,16 +321,105 @@ fn validate_value(value: &Value, field: &FieldDefinition) -> Result<(), Document
                 && num > *max_val
             {
                 return Err(DocumentValidationError::ValueTooHigh {
-                    field_id: field.id().to_string(),
+                    field_id: path.to_string(),
                     value: num,
                     max: *max_val,
                 });
             }
         }
+        FieldType::Integer { min, max } => {
+            // Only JSON integers are accepted, so `2.5` (or `2.0`) is not an integer
+            let num = value
+                .as_i64()
+                .ok_or_else(|| DocumentValidationError::InvalidType {
+                    field_id: path.to_string(),
+                    expected_type: "Integer".to_string(),
+                    got_type: get_json_type(value),
+                })?;
+
+            if let Some(min_val) = min
+                && num < *min_val
+            {
+                return Err(DocumentValidationError::ValueTooLow {
+                    field_id: path.to_string(),
+                    value: num as f64,
+                    min: *min_val as f64,
+                });
+            }
+            if let Some(max_val) = max
+                && num > *max_val
+            {
+                return Err(DocumentValidationError::ValueTooHigh {
+                    field_id: path.to_string(),
+                    value: num as f64,
+                    max: *max_val as f64,
+                });
+            }
+        }
+        FieldType::Decimal {
+            precision,
+            scale,
+            min,
+            max,
+        } => {
+            let text = match value {
+                Value::String(s) => s.trim().to_string(),
+                Value::Number(n) => n.to_string(),
+                _ => {
+                    return Err(DocumentValidationError::InvalidType {
+                        field_id: path.to_string(),
+                        expected_type: "Decimal (Number or String)".to_string(),
+                        got_type: get_json_type(value),
+                    });
+                }
+            };
+            let num =
+                parse_decimal(&text).ok_or_else(|| DocumentValidationError::InvalidDecimal {
+                    field_id: path.to_string(),
+                    value: text.clone(),
+                })?;
+
+            // Trailing zeros do not count towards the scale: "12.50" fits a scale of 1
+            let num = num.normalize();
+            if num.scale() > *scale {
+                return Err(DocumentValidationError::DecimalScaleExceeded {
+                    field_id: path.to_string(),
+                    value: text,
+                    scale: *scale,
+                });
+            }
+            if integer_digits(num) > precision - scale {
+                return Err(DocumentValidationError::DecimalPrecisionExceeded {
+                    field_id: path.to_string(),
+                    value: text,
+                    precision: *precision,
+                    scale: *scale,
+                });
+            }
+
+            if let Some(min_val) = min
+                && num < *min_val
+            {
+                return Err(DocumentValidationError::ValueTooLow {
+                    field_id: path.to_string(),
+                    value: num.to_f64().unwrap_or(f64::NAN),
+                    min: min_val.to_f64().unwrap_or(f64::NAN),
+                });
+            }
+            if let Some(max_val) = max
+                && num > *max_val
+            {
+                return Err(DocumentValidationError::ValueTooHigh {
+                    field_id: path.to_string(),
+                    value: num.to_f64().unwrap_or(f64::NAN),
+                    max: max_val.to_f64().unwrap_or(f64::NAN),
+                });
+            }
+        }
         FieldType::Boolean => {
             if !value.is_boolean() {
                 return Err(DocumentValidationError::InvalidType {
-                    field_id: field.id().to_string(),
+                    field_id: path.to_string(),
                     expected_type: "Boolean".to_string(),
                     got_type: get_json_type(value),
                 });
@@ -140,7 +434,7 @@ fn validate_value(value: &Value, field: &FieldDefinition) -> Result<(), Document
                 let arr = value
                     .as_array()
                     .ok_or_else(|| DocumentValidationError::InvalidType {
-                        field_id: field.id().to_string(),
+                        field_id: path.to_string(),
                         expected_type: "Array".to_string(),
                         got_type: get_json_type(value),
                     })?;
@@ -149,13 +443,13 @@ fn validate_value(value: &Value, field: &FieldDefinition) -> Result<(), Document
                     let s = item
                         .as_str()
                         .ok_or_else(|| DocumentValidationError::InvalidType {
-                            field_id: field.id().to_string(),
+                            field_id: path.to_string(),
                             expected_type: "String".to_string(),
                             got_type: get_json_type(item),
                         })?;
                     if !options.contains(&s.to_string()) {
                         return Err(DocumentValidationError::InvalidSelection {
-                            field_id: field.id().to_string(),
+                            field_id: path.to_string(),
                             value: s.to_string(),
                             allowed: options.clone(),
                         });
@@ -166,14 +460,14 @@ fn validate_value(value: &Value, field: &FieldDefinition) -> Result<(), Document
                 let s = value
                     .as_str()
                     .ok_or_else(|| DocumentValidationError::InvalidType {
-                        field_id: field.id().to_string(),
+                        field_id: path.to_string(),
                         expected_type: "String".to_string(),
                         got_type: get_json_type(value),
                     })?;
 
                 if !options.contains(&s.to_string()) {
                     return Err(DocumentValidationError::InvalidSelection {
-                        field_id: field.id().to_string(),
+                        field_id: path.to_string(),
                         value: s.to_string(),
                         allowed: options.clone(),
                     });
@@ -184,16 +478,153 @@ fn validate_value(value: &Value, field: &FieldDefinition) -> Result<(), Document
             let s = value
                 .as_str()
                 .ok_or_else(|| DocumentValidationError::InvalidType {
-                    field_id: field.id().to_string(),
+                    field_id: path.to_string(),
                     expected_type: "String (ISO 8601)".to_string(),
                     got_type: get_json_type(value),
                 })?;
 
             // Validate it parses as an ISO string
-            if chrono::DateTime::parse_from_rfc3339(s).is_err() {
+            if DateTime::parse_from_rfc3339(s).is_err() {
                 return Err(DocumentValidationError::InvalidDateFormat {
-                    field_id: field.id().to_string(),
+                    field_id: path.to_string(),
+                    value: s.to_string(),
+                });
+            }
+        }
+        FieldType::Date { min, max } => {
+            let s = expect_str(value, path, "String (ISO 8601 date)")?;
+            let date = parse_date(s).ok_or_else(|| DocumentValidationError::InvalidDateFormat {
+                field_id: path.to_string(),
+                value: s.to_string(),
+            })?;
+
+            let today = now.date_naive();
+            if let Some(min) = min.map(|b| b.resolve(today))
+                && date < min
+            {
+                return Err(DocumentValidationError::TooEarly {
+                    field_id: path.to_string(),
+                    value: s.to_string(),
+                    min: min.to_string(),
+                });
+            }
+            if let Some(max) = max.map(|b| b.resolve(today))
+                && date > max
+            {
+                return Err(DocumentValidationError::TooLate {
+                    field_id: path.to_string(),
                     value: s.to_string(),
+                    max: max.to_string(),
+                });
+            }
+        }
+        FieldType::Time { min, max } => {
+            let s = expect_str(value, path, "String (ISO 8601 time)")?;
+            let time = parse_time(s).ok_or_else(|| DocumentValidationError::InvalidTimeFormat {
+                field_id: path.to_string(),
+                value: s.to_string(),
+            })?;
+
+            let time_now = now.time();
+            if let Some(min) = min.map(|b| b.resolve(time_now))
+                && time < min
+            {
+                return Err(DocumentValidationError::TooEarly {
+                    field_id: path.to_string(),
+                    value: s.to_string(),
+                    min: min.to_string(),
+                });
+            }
+            if let Some(max) = max.map(|b| b.resolve(time_now))
+                && time > max
+            {
+                return Err(DocumentValidationError::TooLate {
+                    field_id: path.to_string(),
+                    value: s.to_string(),
+                    max: max.to_string(),
+                });
+            }
+        }
+        FieldType::Duration { min, max } => {
+            let s = expect_str(value, path, "String (ISO 8601 duration)")?;
+            let duration: IsoDuration =
+                s.parse()
+                    .map_err(|_| DocumentValidationError::InvalidDurationFormat {
+                        field_id: path.to_string(),
+                        value: s.to_string(),
+                    })?;
+
+            if let Some(min) = min
+                && duration < *min
+            {
+                return Err(DocumentValidationError::DurationTooShort {
+                    field_id: path.to_string(),
+                    value: s.to_string(),
+                    min: min.to_string(),
+                });
+            }
+            if let Some(max) = max
+                && duration > *max
+            {
+                return Err(DocumentValidationError::DurationTooLong {
+                    field_id: path.to_string(),
+                    value: s.to_string(),
+                    max: max.to_string(),
+                });
+            }
+        }
+        FieldType::User { allow_multiple } => {
+            expect_ids(value, path, *allow_multiple, "String (user ID)")?;
+        }
+        FieldType::Reference { allow_multiple, .. } => {
+            // Whether the documents exist is checked by `check_references`
+            expect_ids(value, path, *allow_multiple, "String (document ID)")?;
+        }
+        FieldType::Attachment { allow_multiple, .. } => {
+            // Whether the attachments exist is checked by `check_attachments`
+            expect_ids(value, path, *allow_multiple, "String (attachment ID)")?;
+        }
+        FieldType::Group {
+            min_items,
+            max_items,
+            repeatable,
+            ..
+        } => {
+            // The items themselves are validated against the group's form by `validate_data`
+            let Some(items) = value.as_array().filter(|_| *repeatable) else {
+                if *repeatable || !value.is_object() {
+                    return Err(DocumentValidationError::InvalidType {
+                        field_id: path.to_string(),
+                        expected_type: if *repeatable { "Array" } else { "Object" }.to_string(),
+                        got_type: get_json_type(value),
+                    });
+                }
+                return Ok(());
+            };
+
+            if let Some(min_items) = min_items
+                && items.len() < *min_items
+            {
+                return Err(DocumentValidationError::TooFewItems {
+                    field_id: path.to_string(),
+                    count: items.len(),
+                    min_items: *min_items,
+                });
+            }
+            if let Some(max_items) = max_items
+                && items.len() > *max_items
+            {
+                return Err(DocumentValidationError::TooManyItems {
+                    field_id: path.to_string(),
+                    count: items.len(),
+                    max_items: *max_items,
+                });
+            }
+            if let Some((i, item)) = items.iter().enumerate().find(|(_, v)| !v.is_object()) {
+                return Err(DocumentValidationError::InvalidType {
+                    field_id: pointer(path, &i.to_string()),
+                    expected_type: "Object".to_string(),
+                    got_type: get_json_type(item),
                 });
             }
         }
@@ -201,6 +632,135 @@ fn validate_value(value: &Value, field: &FieldDefinition) -> Result<(), Document
     Ok(())
 }
 
+/// Checks whether a cross-field rule holds for the document data.
+///
+/// Condition rules only apply once all of their fields hold a value. Conditions cannot
+/// fail to evaluate here, as the fields they read have already been validated against
+/// their types.
+fn rule_holds(rule: &FormRule, data: &HashMap<String, Value>, form: &FormDefinition) -> bool {
+    let has_value = |id: &str| data.get(id).is_some_and(|v| !v.is_null());
+
+    match &rule.kind {
+        RuleKind::Condition { condition } => {
+            !condition.fields().into_iter().all(has_value)
+                || condition.evaluate(data, form).unwrap_or(true)
+        }
+        RuleKind::RequiredWhen { field, condition } => {
+            has_value(field) || !condition.evaluate(data, form).unwrap_or(false)
+        }
+        RuleKind::AtLeastOneOf { fields } => fields.iter().any(|f| has_value(f)),
+    }
+}
+
+/// Returns a value as a string, or an `InvalidType` error naming the expected type.
+fn expect_str<'v>(
+    value: &'v Value,
+    path: &str,
+    expected_type: &str,
+) -> Result<&'v str, DocumentValidationError> {
+    value
+        .as_str()
+        .ok_or_else(|| DocumentValidationError::InvalidType {
+            field_id: path.to_string(),
+            expected_type: expected_type.to_string(),
+            got_type: get_json_type(value),
+        })
+}
+
+/// Checks that a value is an ID, or a list of IDs if `allow_multiple` is set.
+fn expect_ids(
+    value: &Value,
+    path: &str,
+    allow_multiple: bool,
+    expected_type: &str,
+) -> Result<(), DocumentValidationError> {
+    if !allow_multiple {
+        return expect_str(value, path, expected_type).map(|_| ());
+    }
+    let arr = value
+        .as_array()
+        .ok_or_else(|| DocumentValidationError::InvalidType {
+            field_id: path.to_string(),
+            expected_type: "Array".to_string(),
+            got_type: get_json_type(value),
+        })?;
+    for item in arr {
+        expect_str(item, path, expected_type)?;
+    }
+    Ok(())
+}
+
+/// Calls `visit` with the path, definition and value of every field holding a value in
+/// data of `form`, found at `prefix` in the document, including fields within groups.
+///
+/// Group items that are not objects are skipped, as `validate_document` reports them.
+pub(crate) fn visit_values<'a>(
+    data: &dyn Fn(&str) -> Option<&'a Value>,
+    form: &FormDefinition,
+    prefix: &str,
+    visit: &mut dyn FnMut(&str, &FieldDefinition, &'a Value),
+) {
+    for field in form.fields() {
+        let Some(value) = data(field.id()) else {
+            continue;
+        };
+        let path = pointer(prefix, field.id());
+        visit(&path, field, value);
+
+        let Some(group) = field.group() else {
+            continue;
+        };
+        let items: Vec<(String, &Value)> = match value {
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| (pointer(&path, &i.to_string()), item))
+                .collect(),
+            item => vec![(path.clone(), item)],
+        };
+        for (item_path, item) in items {
+            if let Value::Object(map) = item {
+                visit_values(&|id| map.get(id), group, &item_path, visit);
+            }
+        }
+    }
+}
+
+/// Returns the IDs held by a `Reference` or `Attachment` value, skipping items that are
+/// not strings.
+pub(crate) fn value_ids(value: &Value) -> Vec<&str> {
+    match value {
+        Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+        value => value.as_str().into_iter().collect(),
+    }
+}
+
+/// Appends a segment to a JSON-pointer-style path (without the leading `/`), escaping
+/// `~` and `/` as in RFC 6901.
+pub(crate) fn pointer(prefix: &str, segment: &str) -> String {
+    let segment = segment.replace('~', "~0").replace('/', "~1");
+    if prefix.is_empty() {
+        segment
+    } else {
+        format!("{}/{}", prefix, segment)
+    }
+}
+
+/// Returns the pattern of a text field as it was written in the form definition.
+fn field_pattern(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Text {
+            pattern: Some(pattern),
+            ..
+        }
+        | FieldType::TextArea {
+            pattern: Some(pattern),
+            ..
+        } => pattern.clone(),
+        _ => String::new(),
+    }
+}
+
 /// Helper function to get a string representation of a `serde_json::Value`'s type.
 ///
 /// # Arguments
@@ -226,15 +786,24 @@ mod tests {
     use molten_core::document::Document;
     use molten_core::field::{FieldBuilder, FieldType};
     use molten_core::form::FormBuilder;
+    use rust_decimal::Decimal;
     use serde_json::json;
 
     fn create_test_form() -> FormDefinition {
         FormBuilder::new("ticket", "Ticket")
             .add_field(
-                FieldBuilder::new("title", "Title", FieldType::Text)
-                    .required(true)
-                    .build()
-                    .unwrap(),
+                FieldBuilder::new(
+                    "title",
+                    "Title",
+                    FieldType::Text {
+                        min_length: None,
+                        max_length: None,
+                        pattern: None,
+                    },
+                )
+                .required(true)
+                .build()
+                .unwrap(),
             )
             .add_field(
                 FieldBuilder::new(
@@ -317,6 +886,240 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_text_constraints() {
+        let form = FormBuilder::new("ticket", "Ticket")
+            .add_field(
+                FieldBuilder::new(
+                    "case_number",
+                    "Case Number",
+                    FieldType::Text {
+                        min_length: None,
+                        max_length: None,
+                        pattern: Some(r"QA-\d{5}".into()),
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "summary",
+                    "Summary",
+                    FieldType::TextArea {
+                        min_length: Some(3),
+                        max_length: Some(5),
+                        pattern: None,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let mut doc = Document::new("doc1", "ticket", "flow_ticket");
+
+        doc.set_value("case_number", json!("QA-00042"));
+        doc.set_value("summary", json!("Bolt"));
+        assert!(validate_document(&doc, &form).is_ok());
+
+        doc.set_value("case_number", json!("QA-42"));
+        doc.set_value("summary", json!("No"));
+        assert_eq!(
+            validate_document(&doc, &form).unwrap_err(),
+            vec![
+                DocumentValidationError::PatternMismatch {
+                    field_id: "case_number".into(),
+                    value: "QA-42".into(),
+                    pattern: r"QA-\d{5}".into(),
+                },
+                DocumentValidationError::TextTooShort {
+                    field_id: "summary".into(),
+                    length: 2,
+                    min_length: 3,
+                },
+            ]
+        );
+
+        // Length counts characters, not bytes
+        doc.set_value("case_number", json!("QA-00042"));
+        doc.set_value("summary", json!("Größe"));
+        assert!(validate_document(&doc, &form).is_ok());
+        doc.set_value("summary", json!("Größer"));
+        assert!(matches!(
+            validate_document(&doc, &form).unwrap_err()[0],
+            DocumentValidationError::TextTooLong { length: 6, .. }
+        ));
+    }
+
+    #[test]
+    fn test_integer_and_decimal() {
+        let form = FormBuilder::new("order", "Order")
+            .add_field(
+                FieldBuilder::new(
+                    "quantity",
+                    "Quantity",
+                    FieldType::Integer {
+                        min: Some(1),
+                        max: None,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "amount",
+                    "Amount",
+                    FieldType::Decimal {
+                        precision: 6,
+                        scale: 2,
+                        min: Some(Decimal::ZERO),
+                        max: None,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let mut doc = Document::new("doc1", "order", "flow_order");
+        let errors = |doc: &Document| validate_document(doc, &form).err().unwrap_or_default();
+
+        doc.set_value("quantity", json!(3));
+        doc.set_value("amount", json!("1234.50"));
+        assert!(errors(&doc).is_empty());
+        doc.set_value("amount", json!(9999.9));
+        assert!(errors(&doc).is_empty());
+
+        doc.set_value("quantity", json!(2.5));
+        doc.set_value("amount", json!("0.125"));
+        assert_eq!(
+            errors(&doc),
+            vec![
+                DocumentValidationError::InvalidType {
+                    field_id: "quantity".into(),
+                    expected_type: "Integer".into(),
+                    got_type: "Number".into(),
+                },
+                DocumentValidationError::DecimalScaleExceeded {
+                    field_id: "amount".into(),
+                    value: "0.125".into(),
+                    scale: 2,
+                },
+            ]
+        );
+
+        doc.set_value("quantity", json!(0));
+        doc.set_value("amount", json!(10000));
+        let errs = errors(&doc);
+        assert!(matches!(
+            errs[0],
+            DocumentValidationError::ValueTooLow { .. }
+        ));
+        assert!(matches!(
+            errs[1],
+            DocumentValidationError::DecimalPrecisionExceeded { precision: 6, .. }
+        ));
+
+        doc.set_value("quantity", json!(1));
+        doc.set_value("amount", json!("-0.01"));
+        assert!(matches!(
+            errors(&doc)[0],
+            DocumentValidationError::ValueTooLow { .. }
+        ));
+        doc.set_value("amount", json!("twelve"));
+        assert!(matches!(
+            errors(&doc)[0],
+            DocumentValidationError::InvalidDecimal { .. }
+        ));
+    }
+
+    #[test]
+    fn test_date_time_duration() {
+        let field = |id: &str, field_type: FieldType| {
+            FieldBuilder::new(id, id, field_type).build().unwrap()
+        };
+        let form = FormBuilder::new("incident", "Incident")
+            .add_field(field(
+                "occurred_on",
+                FieldType::Date {
+                    min: Some("2020-01-01".parse().unwrap()),
+                    max: Some("today".parse().unwrap()),
+                },
+            ))
+            .add_field(field(
+                "shift_start",
+                FieldType::Time {
+                    min: Some("06:00".parse().unwrap()),
+                    max: None,
+                },
+            ))
+            .add_field(field(
+                "downtime",
+                FieldType::Duration {
+                    min: None,
+                    max: Some("PT12H".parse().unwrap()),
+                },
+            ))
+            .build()
+            .unwrap();
+        let now: DateTime<Utc> = "2026-10-16T09:00:00Z".parse().unwrap();
+        let mut doc = Document::new("doc1", "incident", "flow_incident");
+        let errors = |doc: &Document| {
+            validate_document_at(doc, &form, now)
+                .err()
+                .unwrap_or_default()
+        };
+
+        doc.set_value("occurred_on", json!("2026-10-16"));
+        doc.set_value("shift_start", json!("06:30"));
+        doc.set_value("downtime", json!("PT1H30M"));
+        assert!(errors(&doc).is_empty());
+
+        // Not in the future, relative to `now`
+        doc.set_value("occurred_on", json!("2026-10-17"));
+        doc.set_value("shift_start", json!("05:59:59"));
+        doc.set_value("downtime", json!("P1D"));
+        assert_eq!(
+            errors(&doc),
+            vec![
+                DocumentValidationError::TooLate {
+                    field_id: "occurred_on".into(),
+                    value: "2026-10-17".into(),
+                    max: "2026-10-16".into(),
+                },
+                DocumentValidationError::TooEarly {
+                    field_id: "shift_start".into(),
+                    value: "05:59:59".into(),
+                    min: "06:00:00".into(),
+                },
+                DocumentValidationError::DurationTooLong {
+                    field_id: "downtime".into(),
+                    value: "P1D".into(),
+                    max: "PT12H".into(),
+                },
+            ]
+        );
+
+        doc.set_value("occurred_on", json!("2026-10-16T08:00:00Z"));
+        doc.set_value("shift_start", json!("6am"));
+        doc.set_value("downtime", json!("90 minutes"));
+        let errs = errors(&doc);
+        assert!(matches!(
+            errs[0],
+            DocumentValidationError::InvalidDateFormat { .. }
+        ));
+        assert!(matches!(
+            errs[1],
+            DocumentValidationError::InvalidTimeFormat { .. }
+        ));
+        assert!(matches!(
+            errs[2],
+            DocumentValidationError::InvalidDurationFormat { .. }
+        ));
+    }
+
     #[test]
     fn test_select_options() {
         let form = create_test_form();
@@ -331,4 +1134,235 @@ mod tests {
             DocumentValidationError::InvalidSelection { .. }
         ));
     }
+
+    #[test]
+    fn test_cross_field_rules() {
+        use molten_core::expression::Expression;
+
+        let text = || FieldType::Text {
+            min_length: None,
+            max_length: None,
+            pattern: None,
+        };
+        let field = |id: &str, field_type: FieldType| {
+            FieldBuilder::new(id, id, field_type).build().unwrap()
+        };
+        let condition = |source: &str| Expression::parse(source).unwrap();
+
+        let form = FormBuilder::new("incident", "Incident")
+            .add_field(field("opened_at", FieldType::DateTime))
+            .add_field(field("closed_at", FieldType::DateTime))
+            .add_field(field(
+                "reason",
+                FieldType::Select {
+                    options: vec!["Damage".into(), "Other".into()],
+                    allow_multiple: false,
+                },
+            ))
+            .add_field(field("other_reason", text()))
+            .add_field(field("email", text()))
+            .add_field(field("phone", text()))
+            .add_rule(FormRule::new(
+                "closed_after_opened",
+                "Must be closed after it was opened",
+                RuleKind::Condition {
+                    condition: condition("closed_at > opened_at"),
+                },
+            ))
+            .add_rule(FormRule::new(
+                "explain_other",
+                "Explain the other reason",
+                RuleKind::RequiredWhen {
+                    field: "other_reason".into(),
+                    condition: condition(r#"reason == "Other""#),
+                },
+            ))
+            .add_rule(FormRule::new(
+                "contact",
+                "Provide an email or phone number",
+                RuleKind::AtLeastOneOf {
+                    fields: vec!["email".into(), "phone".into()],
+                },
+            ))
+            .build()
+            .unwrap();
+
+        // Condition rules do not apply until their fields are filled in
+        let mut doc = Document::new("doc1", "incident", "flow_incident");
+        doc.set_value("opened_at", json!("2026-10-16T09:00:00Z"));
+        doc.set_value("reason", json!("Damage"));
+        doc.set_value("phone", json!("555-0100"));
+        assert!(validate_document(&doc, &form).is_ok());
+
+        doc.set_value("closed_at", json!("2026-10-16T08:00:00Z"));
+        doc.set_value("reason", json!("Other"));
+        doc.data.remove("phone");
+        let errs = validate_document(&doc, &form).unwrap_err();
+        let violated: Vec<&str> = errs
+            .iter()
+            .map(|e| match e {
+                DocumentValidationError::RuleViolated { rule_id, .. } => rule_id.as_str(),
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(
+            violated,
+            vec!["closed_after_opened", "explain_other", "contact"]
+        );
+        assert_eq!(
+            errs[1],
+            DocumentValidationError::RuleViolated {
+                rule_id: "explain_other".into(),
+                message: "Explain the other reason".into(),
+                fields: vec!["other_reason".into(), "reason".into()],
+            }
+        );
+
+        // Rules over invalid fields are skipped
+        doc.set_value("closed_at", json!("yesterday"));
+        doc.set_value("other_reason", json!("Flooding"));
+        doc.set_value("email", json!("ops@example.com"));
+        let errs = validate_document(&doc, &form).unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert!(matches!(
+            errs[0],
+            DocumentValidationError::InvalidDateFormat { .. }
+        ));
+    }
+
+    #[test]
+    fn test_conditional_fields() {
+        use molten_core::expression::Expression;
+
+        let injury = Expression::parse("injury_occurred == true").unwrap();
+        let form = FormBuilder::new("incident", "Incident")
+            .add_field(
+                FieldBuilder::new("injury_occurred", "Injury Occurred", FieldType::Boolean)
+                    .build()
+                    .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "injury_description",
+                    "Injury Description",
+                    FieldType::TextArea {
+                        min_length: None,
+                        max_length: None,
+                        pattern: None,
+                    },
+                )
+                .required_when(injury.clone())
+                .visible_when(injury)
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let mut doc = Document::new("doc1", "incident", "flow_incident");
+        doc.set_value("injury_occurred", json!(false));
+        assert!(validate_document(&doc, &form).is_ok());
+
+        // Hidden fields must be absent or null
+        doc.set_value("injury_description", json!("Cut finger"));
+        assert_eq!(
+            validate_document(&doc, &form).unwrap_err(),
+            vec![DocumentValidationError::HiddenFieldSet(
+                "injury_description".into()
+            )]
+        );
+        doc.set_value("injury_description", Value::Null);
+        assert!(validate_document(&doc, &form).is_ok());
+
+        // Conditionally required fields must be present
+        doc.set_value("injury_occurred", json!(true));
+        assert_eq!(
+            validate_document(&doc, &form).unwrap_err(),
+            vec![DocumentValidationError::MissingRequiredField(
+                "injury_description".into()
+            )]
+        );
+        doc.set_value("injury_description", json!("Cut finger"));
+        assert!(validate_document(&doc, &form).is_ok());
+    }
+
+    #[test]
+    fn test_groups() {
+        let field = |id: &str, field_type: FieldType| {
+            FieldBuilder::new(id, id, field_type)
+                .required(true)
+                .build()
+                .unwrap()
+        };
+        let parts = FieldType::Group {
+            fields: vec![
+                field(
+                    "part_number",
+                    FieldType::Text {
+                        min_length: None,
+                        max_length: None,
+                        pattern: None,
+                    },
+                ),
+                field(
+                    "qty",
+                    FieldType::Integer {
+                        min: Some(1),
+                        max: None,
+                    },
+                ),
+            ],
+            min_items: Some(1),
+            max_items: Some(3),
+            repeatable: true,
+        };
+        let form = FormBuilder::new("inspection", "Inspection")
+            .add_field(field("parts", parts))
+            .build()
+            .unwrap();
+
+        let mut doc = Document::new("doc1", "inspection", "flow_inspection");
+        doc.set_value(
+            "parts",
+            json!([
+                { "part_number": "P-1", "qty": 2 },
+                { "part_number": "P-2", "qty": 1 },
+                { "part_number": "P-3", "qty": 0 }
+            ]),
+        );
+        let errs = validate_document(&doc, &form).unwrap_err();
+        assert_eq!(
+            errs,
+            vec![DocumentValidationError::ValueTooLow {
+                field_id: "parts/2/qty".into(),
+                value: 0.0,
+                min: 1.0,
+            }]
+        );
+
+        doc.set_value("parts", json!([{ "qty": 1 }, "P-2"]));
+        let errs = validate_document(&doc, &form).unwrap_err();
+        assert_eq!(
+            errs,
+            vec![DocumentValidationError::InvalidType {
+                field_id: "parts/1".into(),
+                expected_type: "Object".into(),
+                got_type: "String".into(),
+            }]
+        );
+
+        doc.set_value("parts", json!([{ "qty": 1 }]));
+        assert_eq!(
+            validate_document(&doc, &form).unwrap_err(),
+            vec![DocumentValidationError::MissingRequiredField(
+                "parts/0/part_number".into()
+            )]
+        );
+
+        doc.set_value("parts", json!([]));
+        assert!(matches!(
+            validate_document(&doc, &form).unwrap_err()[0],
+            DocumentValidationError::TooFewItems { count: 0, .. }
+        ));
+    }
 }
@@ -1,12 +1,30 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_core_tables;
+mod m20261016_000001_create_document_transitions;
+mod m20261016_000002_add_document_version;
+mod m20261016_000003_add_document_listing_indexes;
+mod m20261016_000004_version_forms;
+mod m20261016_000005_create_document_references;
+mod m20261016_000006_create_attachments;
+mod m20261016_000007_create_document_assignees;
+mod m20261016_000008_create_try_cast_functions;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_core_tables::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_core_tables::Migration),
+            Box::new(m20261016_000001_create_document_transitions::Migration),
+            Box::new(m20261016_000002_add_document_version::Migration),
+            Box::new(m20261016_000003_add_document_listing_indexes::Migration),
+            Box::new(m20261016_000004_version_forms::Migration),
+            Box::new(m20261016_000005_create_document_references::Migration),
+            Box::new(m20261016_000006_create_attachments::Migration),
+            Box::new(m20261016_000007_create_document_assignees::Migration),
+            Box::new(m20261016_000008_create_try_cast_functions::Migration),
+        ]
     }
 }
@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 1. Create Document Transitions Table (append-only audit trail)
+        manager
+            .create_table(
+                Table::create()
+                    .table(DocumentTransitions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DocumentTransitions::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DocumentTransitions::DocumentId)
+                            .string()
+                            .not_null(),
+                    )
+                    // Null when the document was created and entered its start phase
+                    .col(ColumnDef::new(DocumentTransitions::FromPhase).string())
+                    .col(
+                        ColumnDef::new(DocumentTransitions::ToPhase)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DocumentTransitions::TransitionName).string())
+                    .col(
+                        ColumnDef::new(DocumentTransitions::Actor)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DocumentTransitions::Comment).text())
+                    .col(
+                        ColumnDef::new(DocumentTransitions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_document_transitions_document_id")
+                            .from(DocumentTransitions::Table, DocumentTransitions::DocumentId)
+                            .to(Documents::Table, Documents::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 2. Index for reading a document's history in order
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_document_transitions_document_id_created_at")
+                    .table(DocumentTransitions::Table)
+                    .col(DocumentTransitions::DocumentId)
+                    .col(DocumentTransitions::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DocumentTransitions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum DocumentTransitions {
+    Table,
+    Id,
+    DocumentId,
+    FromPhase,
+    ToPhase,
+    TransitionName,
+    Actor,
+    Comment,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Documents {
+    Table,
+    Id,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Version counter for optimistic concurrency control.
+        // Existing rows start at version 1, like newly created documents.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Documents::Table)
+                    .add_column(
+                        ColumnDef::new(Documents::Version)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Documents::Table)
+                    .drop_column(Documents::Version)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Documents {
+    Table,
+    Version,
+}
@@ -0,0 +1,60 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Keyset pagination orders by (timestamp, id), so index both pairs
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_documents_created_at_id")
+                    .table(Documents::Table)
+                    .col(Documents::CreatedAt)
+                    .col(Documents::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_documents_updated_at_id")
+                    .table(Documents::Table)
+                    .col(Documents::UpdatedAt)
+                    .col(Documents::Id)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_documents_updated_at_id")
+                    .table(Documents::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_documents_created_at_id")
+                    .table(Documents::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Documents {
+    Table,
+    Id,
+    CreatedAt,
+    UpdatedAt,
+}
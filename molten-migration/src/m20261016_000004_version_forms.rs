@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 1. Pin every document to the form version it is validated against
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Documents::Table)
+                    .add_column(
+                        ColumnDef::new(Documents::FormVersion)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing documents were validated against the only stored version of their form
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"UPDATE "documents" SET "form_version" = "forms"."version"
+               FROM "forms" WHERE "forms"."id" = "documents"."form_id""#,
+        )
+        .await?;
+
+        // 2. Key forms by (id, version) so that each published version is kept as its own row
+        db.execute_unprepared(r#"ALTER TABLE "forms" DROP CONSTRAINT "forms_pkey""#)
+            .await?;
+        db.execute_unprepared(r#"ALTER TABLE "forms" ADD PRIMARY KEY ("id", "version")"#)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Only the latest version of each form survives a rollback
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            r#"DELETE FROM "forms" AS "old" USING "forms" AS "new"
+               WHERE "old"."id" = "new"."id" AND "old"."version" < "new"."version""#,
+        )
+        .await?;
+        db.execute_unprepared(r#"ALTER TABLE "forms" DROP CONSTRAINT "forms_pkey""#)
+            .await?;
+        db.execute_unprepared(r#"ALTER TABLE "forms" ADD PRIMARY KEY ("id")"#)
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Documents::Table)
+                    .drop_column(Documents::FormVersion)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Documents {
+    Table,
+    FormVersion,
+}
@@ -0,0 +1,85 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 1. Create Document References Table (links held by `Reference` fields)
+        manager
+            .create_table(
+                Table::create()
+                    .table(DocumentReferences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DocumentReferences::DocumentId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DocumentReferences::FieldId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DocumentReferences::TargetId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DocumentReferences::TargetFormId)
+                            .string()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(DocumentReferences::DocumentId)
+                            .col(DocumentReferences::FieldId)
+                            .col(DocumentReferences::TargetId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_document_references_document_id")
+                            .from(DocumentReferences::Table, DocumentReferences::DocumentId)
+                            .to(Documents::Table, Documents::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 2. Index for finding the documents that reference a document
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_document_references_target_id")
+                    .table(DocumentReferences::Table)
+                    .col(DocumentReferences::TargetId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DocumentReferences::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum DocumentReferences {
+    Table,
+    DocumentId,
+    FieldId,
+    TargetId,
+    TargetFormId,
+}
+
+#[derive(Iden)]
+enum Documents {
+    Table,
+    Id,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 1. Create Attachments Table (metadata of uploaded files, whose contents are kept
+        //    in the blob store under their SHA-256 digest)
+        manager
+            .create_table(
+                Table::create()
+                    .table(Attachments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Attachments::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Attachments::FileName).string().not_null())
+                    .col(ColumnDef::new(Attachments::MimeType).string().not_null())
+                    .col(ColumnDef::new(Attachments::Size).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(Attachments::Sha256)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Attachments::UploadedBy).string().not_null())
+                    .col(
+                        ColumnDef::new(Attachments::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 2. Index for finding the uploads of identical contents
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_attachments_sha256")
+                    .table(Attachments::Table)
+                    .col(Attachments::Sha256)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Attachments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Attachments {
+    Table,
+    Id,
+    FileName,
+    MimeType,
+    Size,
+    Sha256,
+    UploadedBy,
+    CreatedAt,
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 1. Create Document Assignees Table (the users a document belongs to in its
+        //    current phase)
+        manager
+            .create_table(
+                Table::create()
+                    .table(DocumentAssignees::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DocumentAssignees::DocumentId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DocumentAssignees::Assignee)
+                            .string()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(DocumentAssignees::DocumentId)
+                            .col(DocumentAssignees::Assignee),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_document_assignees_document_id")
+                            .from(DocumentAssignees::Table, DocumentAssignees::DocumentId)
+                            .to(Documents::Table, Documents::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 2. Index for finding the documents assigned to a user (work queues)
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_document_assignees_assignee")
+                    .table(DocumentAssignees::Table)
+                    .col(DocumentAssignees::Assignee)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DocumentAssignees::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum DocumentAssignees {
+    Table,
+    DocumentId,
+    Assignee,
+}
+
+#[derive(Iden)]
+enum Documents {
+    Table,
+    Id,
+}
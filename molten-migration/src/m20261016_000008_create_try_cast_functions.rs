@@ -0,0 +1,84 @@
+use sea_orm_migration::prelude::*;
+
+/// The functions data filters cast document values with: the function, the SQL type it
+/// casts to, and the query computing the cast of `value`, or `NULL` if it does not parse.
+///
+/// Each query only casts values that a regular expression has already shown to parse,
+/// so that the functions need no exception handler. A PL/pgSQL handler would open a
+/// subtransaction for every row scanned, and these plain SQL functions can be inlined
+/// into the filtering query instead. Functions are created in order, as the date-time
+/// check uses `molten_try_date`.
+const CASTS: [(&str, &str, &str); 5] = [
+    (
+        "molten_try_numeric",
+        "numeric",
+        r"SELECT CASE WHEN value ~ '^\s*[+-]?(\d+\.?\d*|\.\d+)([eE][+-]?\d{1,4})?\s*$'
+    THEN CAST(value AS numeric) END",
+    ),
+    // The day is checked against the length of its month, as `2024-02-30` would fail
+    (
+        "molten_try_date",
+        "date",
+        r"SELECT CASE WHEN value ~ '^\d{4}-(0?[1-9]|1[0-2])-(0?[1-9]|[12]\d|3[01])$'
+    AND value !~ '^0000' THEN
+    CASE WHEN CAST(split_part(value, '-', 3) AS int) <= extract(day FROM make_date(
+        CAST(split_part(value, '-', 1) AS int), CAST(split_part(value, '-', 2) AS int), 1
+    ) + interval '1 month - 1 day') THEN CAST(value AS date) END
+END",
+    ),
+    (
+        "molten_try_timestamptz",
+        "timestamptz",
+        r"SELECT CASE WHEN value ~ '^\d{4}-\d{2}-\d{2}[Tt ]([01]\d|2[0-3]):[0-5]\d:[0-5]\d(\.\d+)?([Zz]|[+-](0\d|1[0-5]):[0-5]\d)$'
+    AND molten_try_date(substr(value, 1, 10)) IS NOT NULL
+    THEN CAST(value AS timestamptz) END",
+    ),
+    (
+        "molten_try_time",
+        "time",
+        r"SELECT CASE WHEN value ~ '^([01]?\d|2[0-3]):[0-5]?\d(:[0-5]?\d(\.\d+)?)?$'
+    THEN CAST(value AS time) END",
+    ),
+    // Postgres reads ISO 8601 durations, but not negative ones. Component lengths are
+    // bounded so that the interval cannot overflow.
+    (
+        "molten_try_interval",
+        "interval",
+        r"SELECT CASE WHEN value ~ '^-?P(\d{1,7}W)?(\d{1,8}D)?(T(\d{1,9}H)?(\d{1,9}M)?(\d{1,9}(\.\d{1,9})?S)?)?$'
+    AND value !~ '^-?PT?$' AND value !~ 'T$' THEN
+    CASE WHEN value LIKE '-%' THEN -CAST(substr(value, 2) AS interval)
+    ELSE CAST(value AS interval) END
+END",
+    ),
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Documents of older form versions (or imported ones) may hold values that do not
+        // parse as their field's current type. Typed data filters cast through these
+        // functions, which yield NULL for such values instead of failing the query.
+        let db = manager.get_connection();
+        for (function, sql_type, query) in CASTS {
+            db.execute_unprepared(&format!(
+                r#"CREATE OR REPLACE FUNCTION {function}(value text) RETURNS {sql_type} AS $$
+{query}
+$$ LANGUAGE sql STABLE"#
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for (function, _, _) in CASTS.iter().rev() {
+            db.execute_unprepared(&format!("DROP FUNCTION IF EXISTS {function}(text)"))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! This module provides a comprehensive set of error variants encapsulated by
 //! `ServiceError`, each detailing specific reasons why a service failed.
+use molten_core::CompatibilityReport;
+use molten_core::query::QueryError;
 use molten_document::DocumentValidationError;
 use molten_workflow::WorkflowError;
 use thiserror::Error;
@@ -21,6 +23,34 @@ pub enum ServiceError {
     #[error("Workflow definition not found: {0}")]
     WorkflowNotFound(String),
 
+    /// A requested document was not found.
+    #[error("Document not found: {0}")]
+    DocumentNotFound(String),
+
+    /// A requested attachment was not found.
+    #[error("Attachment not found: {0}")]
+    AttachmentNotFound(String),
+
+    /// A requested revalidation job was not found.
+    #[error("Revalidation job not found: {0}")]
+    JobNotFound(String),
+
+    /// The document was modified by someone else since it was read.
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// The document is no longer at the version the caller expected.
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    /// A new form version contains breaking changes and was not forced.
+    #[error("Breaking form change: {} of {} changes are breaking", .0.breaking_changes().count(), .0.changes.len())]
+    BreakingFormChange(CompatibilityReport),
+
+    /// A document listing query was malformed.
+    #[error("Invalid query: {0}")]
+    InvalidQuery(#[from] QueryError),
+
     /// Document validation failed, returning a list of specific errors.
     #[error("Document validation failed: {0:?}")]
     DocumentValidationErrors(Vec<DocumentValidationError>),
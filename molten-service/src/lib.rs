@@ -13,9 +13,13 @@ pub mod services;
 
 /// Re-exports of the service error types.
 pub use error::ServiceError;
+/// Re-exports of the Attachment service.
+pub use services::AttachmentService;
 /// Re-exports of the Document service.
 pub use services::DocumentService;
 /// Re-exports of the Form service.
 pub use services::FormService;
+/// Re-exports of the Revalidation service.
+pub use services::RevalidationService;
 /// Re-exports of the Workflow service.
 pub use services::WorkflowService;
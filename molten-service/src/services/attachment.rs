@@ -0,0 +1,112 @@
+//! This module provides the service struct for uploading and downloading attachments.
+
+use crate::error::ServiceError;
+use chrono::Utc;
+use molten_core::actor::Actor;
+use molten_core::attachment::{Attachment, BlobStore};
+use molten_storage_seaorm::repo::AttachmentRepository;
+use molten_storage_seaorm::sea_orm::DatabaseConnection;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Service for managing attachments, the files held by documents' `Attachment` fields.
+///
+/// The metadata of each upload is stored in the database, and the contents in a
+/// `BlobStore` under their SHA-256 digest, so identical files are stored once. Whether
+/// a file fits the field it is attached to is checked when the document is saved.
+pub struct AttachmentService {
+    db: DatabaseConnection,
+    blobs: Arc<dyn BlobStore>,
+}
+
+impl AttachmentService {
+    /// Creates a new `AttachmentService` instance.
+    ///
+    /// # Arguments
+    /// * `db` - A `sea_orm::DatabaseConnection` used for database operations.
+    /// * `blobs` - The `BlobStore` holding the file contents.
+    pub fn new(db: DatabaseConnection, blobs: Arc<dyn BlobStore>) -> Self {
+        Self { db, blobs }
+    }
+
+    /// Stores an uploaded file and records its metadata.
+    ///
+    /// # Arguments
+    /// * `file_name` - The name of the file, as uploaded.
+    /// * `mime_type` - The MIME type of the file.
+    /// * `contents` - The contents of the file.
+    /// * `actor` - The `Actor` uploading the file.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Attachment)` with the metadata of the new attachment, whose
+    /// ID documents can then hold, or `Err(ServiceError)` if storage fails.
+    pub async fn upload(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        contents: &[u8],
+        actor: &Actor,
+    ) -> Result<Attachment, ServiceError> {
+        let sha256 = format!("{:x}", Sha256::digest(contents));
+        let stored = self
+            .blobs
+            .exists(&sha256)
+            .await
+            .map_err(|e| ServiceError::Internal(e.into()))?;
+        if !stored {
+            self.blobs
+                .put(&sha256, contents)
+                .await
+                .map_err(|e| ServiceError::Internal(e.into()))?;
+        }
+
+        let attachment = Attachment {
+            id: Uuid::new_v4().to_string(),
+            file_name: file_name.to_string(),
+            mime_type: mime_type.to_string(),
+            size: contents.len() as u64,
+            sha256,
+            uploaded_by: actor.id.clone(),
+            created_at: Utc::now(),
+        };
+        AttachmentRepository::create(&self.db, &attachment)
+            .await
+            .map_err(ServiceError::Internal)?;
+
+        Ok(attachment)
+    }
+
+    /// Retrieves the metadata of an attachment by its ID.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the attachment.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Attachment)` if found, or `Err(ServiceError)` if the
+    /// attachment does not exist or a database error occurs.
+    pub async fn get_attachment(&self, id: &str) -> Result<Attachment, ServiceError> {
+        AttachmentRepository::find_by_id(&self.db, id)
+            .await
+            .map_err(ServiceError::Internal)?
+            .ok_or_else(|| ServiceError::AttachmentNotFound(id.to_string()))
+    }
+
+    /// Retrieves an attachment together with the contents of its file.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the attachment.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok((Attachment, Vec<u8>))` if found, or `Err(ServiceError)` if
+    /// the attachment does not exist or storage fails.
+    pub async fn download(&self, id: &str) -> Result<(Attachment, Vec<u8>), ServiceError> {
+        let attachment = self.get_attachment(id).await?;
+        let contents = self
+            .blobs
+            .get(&attachment.sha256)
+            .await
+            .map_err(|e| ServiceError::Internal(e.into()))?;
+        Ok((attachment, contents))
+    }
+}
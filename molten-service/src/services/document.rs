@@ -1,11 +1,22 @@
 //! This module provides the service struct for Document entity operations.
 use crate::error::ServiceError;
-use molten_core::document::Document;
-use molten_core::workflow::WorkflowGraph;
-use molten_document::validate_document;
-use molten_storage_seaorm::repo::{DocumentRepository, FormRepository, WorkflowRepository};
-use molten_storage_seaorm::sea_orm::DatabaseConnection;
-use serde_json::Value;
+use chrono::Utc;
+use molten_core::actor::Actor;
+use molten_core::document::{Document, DocumentReference};
+use molten_core::form::FormDefinition;
+use molten_core::history::TransitionRecord;
+use molten_core::query::{DocumentPage, DocumentQuery};
+use molten_core::workflow::{Transition, WorkflowDefinition, WorkflowGraph};
+use molten_document::{
+    apply_merge_patch, check_attachments, check_references, collect_attachment_ids,
+    collect_references, validate_document,
+};
+use molten_storage_seaorm::repo::{
+    AttachmentRepository, DocumentAssigneeRepository, DocumentReferenceRepository,
+    DocumentRepository, DocumentTransitionRepository, FormRepository, WorkflowRepository,
+};
+use molten_storage_seaorm::sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -30,9 +41,12 @@ impl DocumentService {
     /// and saves it to storage.
     ///
     /// # Arguments
-    /// * `form_id` - The ID of the form definition the document adheres to.
+    /// * `form_id` - The ID of the form definition the document adheres to. The document
+    ///   is pinned to the form's latest version.
     /// * `workflow_id` - The ID of the workflow that governs the document's lifecycle.
     /// * `data` - The actual data content of the document, as a `HashMap<String, Value>`.
+    /// * `actor` - The `Actor` creating the document, recorded as the first history entry
+    ///   and used for `current_actor` defaults.
     ///
     /// # Returns
     /// A `Result` which is `Ok(Document)` if the document was successfully created and
@@ -41,15 +55,19 @@ impl DocumentService {
     ///
     /// # Steps
     /// 1. Fetch Form Definition (to check schema).
-    /// 2. Fetch Workflow Definition (to find start phase).
+    /// 2. Fetch Workflow Definition (to find start phase), and check that it governs the form.
     /// 3. Create Document instance.
-    /// 4. Validate Data against Form.
-    /// 5. Save to Database.
+    /// 4. Fill in the defaults of missing fields.
+    /// 5. Validate Data against Form, then check that referenced documents and attached
+    ///    files exist.
+    /// 6. Save to Database, along with the entry into the start phase, the references and
+    ///    the assignees of the start phase.
     pub async fn create_document(
         &self,
         form_id: &str,
         workflow_id: &str,
         data: HashMap<String, Value>,
+        actor: &Actor,
     ) -> Result<Document, ServiceError> {
         // 1. Fetch Configuration
         // We need the Form to validate the data types.
@@ -64,6 +82,24 @@ impl DocumentService {
             .map_err(ServiceError::Internal)?
             .ok_or_else(|| ServiceError::WorkflowNotFound(workflow_id.to_string()))?;
 
+        // A bound workflow governs the documents of its form only. Its guards were checked
+        // against the form when it was saved, but the form may have gained versions since.
+        // Unbound workflows name no form, so their guards are not type-checked.
+        if let Some(bound_form) = workflow.form_id() {
+            if bound_form != form_id {
+                return Err(ServiceError::WorkflowRuleViolation(
+                    molten_workflow::WorkflowError::FormMismatch {
+                        workflow: workflow_id.to_string(),
+                        bound_form: bound_form.to_string(),
+                        form: form_id.to_string(),
+                    },
+                ));
+            }
+            workflow
+                .check_form(&form)
+                .map_err(ServiceError::WorkflowValidationErrors)?;
+        }
+
         // 2. Determine Start Phase
         // Every workflow must have exactly one "Start" phase.
         let start_phase = workflow.get_start_phase().ok_or_else(|| {
@@ -76,19 +112,40 @@ impl DocumentService {
         // We generate a UUID here (or you could let the DB do it, but application-side is usually easier).
         let doc_id = Uuid::new_v4().to_string();
         let mut doc = Document::new(&doc_id, form_id, workflow_id);
+        doc.form_version = form.version();
         doc.current_phase = start_phase.id.clone();
         doc.data = data;
 
-        // 4. Validate Data
+        // 4. Fill in Defaults
+        // Fields left out (or null) get their form-defined default, if any.
+        form.apply_defaults(&mut doc.data, actor, doc.created_at);
+
+        // 5. Validate Data
         // This runs the engine we built in Task 2.1
         if let Err(validation_errors) = validate_document(&doc, &form) {
             return Err(ServiceError::DocumentValidationErrors(validation_errors));
         }
+        // Referenced documents and attachments are checked on the transaction that saves
+        // the document, so that they cannot be removed in between
+        let txn = self.db.begin().await?;
+        let references = validate_references(&txn, &doc, &form).await?;
+        validate_attachments(&txn, &doc, &form).await?;
 
-        // 5. Persist
-        DocumentRepository::create(&self.db, &doc)
+        // 6. Persist
+        let record = new_record(&doc, None, None, actor, None);
+        DocumentRepository::create(&txn, &doc)
             .await
             .map_err(ServiceError::Internal)?;
+        DocumentTransitionRepository::create(&txn, &record)
+            .await
+            .map_err(ServiceError::Internal)?;
+        DocumentReferenceRepository::replace(&txn, &doc.id, &references)
+            .await
+            .map_err(ServiceError::Internal)?;
+        DocumentAssigneeRepository::replace(&txn, &doc.id, &workflow.assignees(&doc))
+            .await
+            .map_err(ServiceError::Internal)?;
+        txn.commit().await?;
 
         Ok(doc)
     }
@@ -105,9 +162,445 @@ impl DocumentService {
         DocumentRepository::find_by_id(&self.db, id)
             .await
             .map_err(ServiceError::Internal)?
-            .ok_or_else(|| ServiceError::Internal(anyhow::anyhow!("Document not found")))
-        // TODO: Implement specific NotFound error code
+            .ok_or_else(|| ServiceError::DocumentNotFound(id.to_string()))
+    }
+
+    /// Lists one page of documents matching the given filters and sort order.
+    ///
+    /// # Arguments
+    /// * `query` - The filters, sort order, page size and cursor to apply.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(DocumentPage)` holding the matching documents and the cursor
+    /// of the next page, or `Err(ServiceError)` if the query is malformed or a database
+    /// error occurs.
+    pub async fn list_documents(
+        &self,
+        query: &DocumentQuery,
+    ) -> Result<DocumentPage, ServiceError> {
+        query.check()?;
+
+        DocumentRepository::find_page(&self.db, query)
+            .await
+            .map_err(ServiceError::Internal)
+    }
+
+    /// Replaces the data of an existing document and revalidates it against its form.
+    ///
+    /// # Arguments
+    /// * `doc_id` - The unique ID of the document to update.
+    /// * `data` - The new data content of the document, replacing the old content entirely.
+    /// * `expected_versions` - If set, the versions the caller expects the document to be
+    ///   at (e.g. the entity tags of an `If-Match` header). The update is refused unless
+    ///   the document is at one of them.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Document)` containing the updated document, or
+    /// `Err(ServiceError)` if the document or its form cannot be found, the document was
+    /// modified concurrently, the new data fails validation, or a database error occurs.
+    pub async fn update_document(
+        &self,
+        doc_id: &str,
+        data: HashMap<String, Value>,
+        expected_versions: Option<&[i32]>,
+    ) -> Result<Document, ServiceError> {
+        let mut doc = self.get_document(doc_id).await?;
+        check_version(&doc, expected_versions)?;
+        doc.data = data;
+
+        self.save_data(doc)
+            .await
+            .map_err(|e| unmet_precondition(e, expected_versions))
+    }
+
+    /// Applies a JSON Merge Patch (RFC 7396) to the data of an existing document and
+    /// revalidates it against its form.
+    ///
+    /// Keys in the patch replace the corresponding fields, nested objects are merged,
+    /// and `null` removes a field.
+    ///
+    /// # Arguments
+    /// * `doc_id` - The unique ID of the document to update.
+    /// * `patch` - The merge patch to apply to the document's data.
+    /// * `expected_versions` - If set, the versions the caller expects the document to be
+    ///   at (e.g. the entity tags of an `If-Match` header). The update is refused unless
+    ///   the document is at one of them.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Document)` containing the updated document, or
+    /// `Err(ServiceError)` if the document or its form cannot be found, the document was
+    /// modified concurrently, the patched data fails validation, or a database error occurs.
+    pub async fn patch_document(
+        &self,
+        doc_id: &str,
+        patch: &Map<String, Value>,
+        expected_versions: Option<&[i32]>,
+    ) -> Result<Document, ServiceError> {
+        let mut doc = self.get_document(doc_id).await?;
+        check_version(&doc, expected_versions)?;
+        apply_merge_patch(&mut doc.data, patch);
+
+        self.save_data(doc)
+            .await
+            .map_err(|e| unmet_precondition(e, expected_versions))
+    }
+
+    /// Moves a document to a new phase according to its workflow definition.
+    ///
+    /// # Arguments
+    /// * `doc_id` - The unique ID of the document to transition.
+    /// * `target_phase` - The ID of the phase the document should move to.
+    /// * `actor` - The `Actor` requesting the transition.
+    /// * `comment` - An optional comment recorded in the document's history.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Document)` containing the document in its new phase, or
+    /// `Err(ServiceError)` if the document or workflow cannot be found, the workflow does
+    /// not allow the transition (or the actor to perform it), or a database error occurs.
+    ///
+    /// # Steps
+    /// 1. Fetch the Document.
+    /// 2. Fetch its Workflow Definition.
+    /// 3. Ask the workflow engine to apply the transition.
+    /// 4. Save to Database, along with a history entry.
+    pub async fn transition_document(
+        &self,
+        doc_id: &str,
+        target_phase: &str,
+        actor: &Actor,
+        comment: Option<&str>,
+    ) -> Result<Document, ServiceError> {
+        // 1. Fetch the Document
+        let mut doc = self.get_document(doc_id).await?;
+
+        // 2. Fetch the Workflow that governs it, and the Form that types its conditions
+        let workflow = self.get_workflow(&doc.workflow_id).await?;
+        let form = self.get_form(&doc).await?;
+
+        // 3. Run the Engine
+        // The engine only mutates `current_phase` if every rule passes.
+        let from_phase = doc.current_phase.clone();
+        let taken = molten_workflow::transition(&mut doc, &workflow, &form, target_phase, actor)?;
+
+        // 4. Persist
+        let record = new_record(&doc, Some(from_phase), taken, actor, comment);
+        self.save_transition(doc, record, &workflow).await
+    }
+
+    /// Fires a named transition (e.g. "approve") on a document.
+    ///
+    /// # Arguments
+    /// * `doc_id` - The unique ID of the document to transition.
+    /// * `transition_name` - The name of a transition leaving the document's current phase.
+    /// * `actor` - The `Actor` firing the transition.
+    /// * `comment` - An optional comment recorded in the document's history.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Document)` containing the document in its new phase, or
+    /// `Err(ServiceError)` if the document or workflow cannot be found, no such transition
+    /// leaves the current phase, its guards are not met, or a database error occurs.
+    pub async fn fire_transition(
+        &self,
+        doc_id: &str,
+        transition_name: &str,
+        actor: &Actor,
+        comment: Option<&str>,
+    ) -> Result<Document, ServiceError> {
+        let mut doc = self.get_document(doc_id).await?;
+        let workflow = self.get_workflow(&doc.workflow_id).await?;
+        let form = self.get_form(&doc).await?;
+
+        let from_phase = doc.current_phase.clone();
+        let taken = molten_workflow::fire(&mut doc, &workflow, &form, transition_name, actor)?;
+
+        let record = new_record(&doc, Some(from_phase), Some(taken), actor, comment);
+        self.save_transition(doc, record, &workflow).await
+    }
+
+    /// Retrieves the transition history of a document, oldest entry first.
+    ///
+    /// # Arguments
+    /// * `doc_id` - The unique ID of the document.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Vec<TransitionRecord>)`, or `Err(ServiceError)` if the
+    /// document cannot be found or a database error occurs.
+    pub async fn get_history(&self, doc_id: &str) -> Result<Vec<TransitionRecord>, ServiceError> {
+        // Distinguish an unknown document from one without history
+        self.get_document(doc_id).await?;
+
+        DocumentTransitionRepository::find_by_document(&self.db, doc_id)
+            .await
+            .map_err(ServiceError::Internal)
+    }
+
+    /// Lists the documents a document references through its `Reference` fields.
+    ///
+    /// # Arguments
+    /// * `doc_id` - The unique ID of the referencing document.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Vec<DocumentReference>)`, or `Err(ServiceError)` if the
+    /// document cannot be found or a database error occurs.
+    pub async fn get_references(
+        &self,
+        doc_id: &str,
+    ) -> Result<Vec<DocumentReference>, ServiceError> {
+        self.get_document(doc_id).await?;
+
+        DocumentReferenceRepository::find_by_document(&self.db, doc_id)
+            .await
+            .map_err(ServiceError::Internal)
+    }
+
+    /// Lists the references to a document held by other documents.
+    ///
+    /// # Arguments
+    /// * `doc_id` - The unique ID of the referenced document.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Vec<DocumentReference>)`, or `Err(ServiceError)` if the
+    /// document cannot be found or a database error occurs.
+    pub async fn get_referenced_by(
+        &self,
+        doc_id: &str,
+    ) -> Result<Vec<DocumentReference>, ServiceError> {
+        self.get_document(doc_id).await?;
+
+        DocumentReferenceRepository::find_by_target(&self.db, doc_id)
+            .await
+            .map_err(ServiceError::Internal)
+    }
+
+    /// Lists the work queue of an actor: the documents assigned to them by the assignee
+    /// field of their current phase, least recently updated first.
+    ///
+    /// # Arguments
+    /// * `actor` - The `Actor` whose queue to list.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Vec<Document>)`, or `Err(ServiceError)` if a database
+    /// error occurs.
+    pub async fn get_queue(&self, actor: &Actor) -> Result<Vec<Document>, ServiceError> {
+        DocumentRepository::find_by_assignee(&self.db, &actor.id)
+            .await
+            .map_err(ServiceError::Internal)
+    }
+
+    /// Lists the transitions the actor can fire from a document's current phase.
+    ///
+    /// Transitions restricted to roles the actor does not hold are omitted.
+    ///
+    /// # Arguments
+    /// * `doc_id` - The unique ID of the document.
+    /// * `actor` - The `Actor` the transitions are listed for.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Vec<Transition>)`, or `Err(ServiceError)` if the document or
+    /// its workflow cannot be found or a database error occurs.
+    pub async fn available_transitions(
+        &self,
+        doc_id: &str,
+        actor: &Actor,
+    ) -> Result<Vec<Transition>, ServiceError> {
+        let doc = self.get_document(doc_id).await?;
+        let workflow = self.get_workflow(&doc.workflow_id).await?;
+
+        Ok(workflow
+            .available_transitions(&doc.current_phase)
+            .into_iter()
+            .filter(|t| actor.has_any_role(&t.allowed_roles))
+            .cloned()
+            .collect())
+    }
+
+    /// Fetches the form definition a document adheres to, at the version the document
+    /// was created with.
+    async fn get_form(&self, doc: &Document) -> Result<FormDefinition, ServiceError> {
+        FormRepository::find_version(&self.db, &doc.form_id, doc.form_version)
+            .await
+            .map_err(ServiceError::Internal)?
+            .ok_or_else(|| {
+                ServiceError::FormNotFound(format!(
+                    "{} (version {})",
+                    doc.form_id, doc.form_version
+                ))
+            })
+    }
+
+    /// Fetches the workflow definition governing a document.
+    async fn get_workflow(&self, workflow_id: &str) -> Result<WorkflowDefinition, ServiceError> {
+        WorkflowRepository::find_by_id(&self.db, workflow_id)
+            .await
+            .map_err(ServiceError::Internal)?
+            .ok_or_else(|| ServiceError::WorkflowNotFound(workflow_id.to_string()))
+    }
+
+    /// Validates a document whose data was changed against its form, then persists it
+    /// together with its references and assignees, unless another writer saved the
+    /// document since it was read.
+    async fn save_data(&self, mut doc: Document) -> Result<Document, ServiceError> {
+        let form = self.get_form(&doc).await?;
+        let workflow = self.get_workflow(&doc.workflow_id).await?;
+
+        if let Err(validation_errors) = validate_document(&doc, &form) {
+            return Err(ServiceError::DocumentValidationErrors(validation_errors));
+        }
+        let txn = self.db.begin().await?;
+        let references = validate_references(&txn, &doc, &form).await?;
+        validate_attachments(&txn, &doc, &form).await?;
+
+        let expected_version = doc.version;
+        doc.version += 1;
+        doc.updated_at = Utc::now();
+
+        let updated = DocumentRepository::update(&txn, &doc, expected_version)
+            .await
+            .map_err(ServiceError::Internal)?;
+        if !updated {
+            return Err(concurrent_update(&doc.id));
+        }
+        DocumentReferenceRepository::replace(&txn, &doc.id, &references)
+            .await
+            .map_err(ServiceError::Internal)?;
+        // The assignee field may have changed
+        DocumentAssigneeRepository::replace(&txn, &doc.id, &workflow.assignees(&doc))
+            .await
+            .map_err(ServiceError::Internal)?;
+        txn.commit().await?;
+
+        Ok(doc)
+    }
+
+    /// Persists a document whose phase was changed by the workflow engine, together with
+    /// the history entry describing the change and the assignees of the new phase, in a
+    /// single database transaction.
+    ///
+    /// Like data updates, this fails with a conflict if the document was saved by another
+    /// writer since it was read.
+    async fn save_transition(
+        &self,
+        mut doc: Document,
+        record: TransitionRecord,
+        workflow: &WorkflowDefinition,
+    ) -> Result<Document, ServiceError> {
+        let expected_version = doc.version;
+        doc.version += 1;
+        doc.updated_at = record.created_at;
+
+        // Dropping the transaction without committing rolls it back
+        let txn = self.db.begin().await?;
+        let updated = DocumentRepository::update(&txn, &doc, expected_version)
+            .await
+            .map_err(ServiceError::Internal)?;
+        if !updated {
+            return Err(concurrent_update(&doc.id));
+        }
+        DocumentTransitionRepository::create(&txn, &record)
+            .await
+            .map_err(ServiceError::Internal)?;
+        DocumentAssigneeRepository::replace(&txn, &doc.id, &workflow.assignees(&doc))
+            .await
+            .map_err(ServiceError::Internal)?;
+        txn.commit().await?;
+
+        Ok(doc)
+    }
+}
+
+/// Checks that every document referenced by a document exists and belongs to the form
+/// its field requires.
+///
+/// This is the asynchronous counterpart of `validate_document`, which cannot reach
+/// storage.
+///
+/// # Returns
+/// The references held by the document, to be stored alongside it, or
+/// `ServiceError::DocumentValidationErrors` listing every broken reference.
+async fn validate_references<C: ConnectionTrait>(
+    db: &C,
+    doc: &Document,
+    form: &FormDefinition,
+) -> Result<Vec<DocumentReference>, ServiceError> {
+    let references = collect_references(doc, form);
+    if references.is_empty() {
+        return Ok(references);
     }
 
-    // Future: We will add `transition_document` here later
+    let mut ids: Vec<String> = references.iter().map(|r| r.target_id.clone()).collect();
+    ids.sort();
+    ids.dedup();
+    let found = DocumentRepository::find_form_ids(db, &ids)
+        .await
+        .map_err(ServiceError::Internal)?;
+
+    check_references(&references, &found).map_err(ServiceError::DocumentValidationErrors)?;
+    Ok(references)
+}
+
+/// Checks that the files attached to a document exist and fit their fields.
+async fn validate_attachments<C: ConnectionTrait>(
+    db: &C,
+    doc: &Document,
+    form: &FormDefinition,
+) -> Result<(), ServiceError> {
+    let ids = collect_attachment_ids(doc, form);
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let found = AttachmentRepository::find_by_ids(db, &ids)
+        .await
+        .map_err(ServiceError::Internal)?;
+    check_attachments(doc, form, &found).map_err(ServiceError::DocumentValidationErrors)
+}
+
+/// Builds the history entry for a document that just entered its current phase.
+fn new_record(
+    doc: &Document,
+    from_phase: Option<String>,
+    transition: Option<&Transition>,
+    actor: &Actor,
+    comment: Option<&str>,
+) -> TransitionRecord {
+    TransitionRecord {
+        id: Uuid::new_v4().to_string(),
+        document_id: doc.id.clone(),
+        from_phase,
+        to_phase: doc.current_phase.clone(),
+        transition: transition.map(|t| t.name.clone()),
+        actor: actor.id.clone(),
+        comment: comment.map(str::to_string),
+        created_at: Utc::now(),
+    }
+}
+
+/// The error returned when a document changed between being read and written.
+fn concurrent_update(doc_id: &str) -> ServiceError {
+    ServiceError::Conflict(format!(
+        "Document '{}' was modified concurrently, reload it and try again",
+        doc_id
+    ))
+}
+
+/// Ensures a document is still at one of the versions the caller expects, if any.
+fn check_version(doc: &Document, expected_versions: Option<&[i32]>) -> Result<(), ServiceError> {
+    match expected_versions {
+        Some(expected) if !expected.contains(&doc.version) => {
+            Err(ServiceError::PreconditionFailed(format!(
+                "Document '{}' is at version {}, not one of {:?}",
+                doc.id, doc.version, expected
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reports a concurrent update as a failed precondition when the caller expected a
+/// version, since the document no longer is at that version.
+fn unmet_precondition(error: ServiceError, expected_versions: Option<&[i32]>) -> ServiceError {
+    match (error, expected_versions) {
+        (ServiceError::Conflict(msg), Some(_)) => ServiceError::PreconditionFailed(msg),
+        (error, _) => error,
+    }
 }
@@ -1,13 +1,17 @@
 //! This module provides the service struct for Form entity operations.
 
 use crate::error::ServiceError;
-use molten_core::FormDefinition;
+use molten_core::{CompatibilityReport, FormDefinition, compare_forms};
 use molten_storage_seaorm::repo::FormRepository;
 use molten_storage_seaorm::sea_orm::DatabaseConnection;
 
 /// Service for managing form definitions.
 ///
 /// This service handles the creation, retrieval, and persistence of `FormDefinition` objects.
+/// Forms are versioned: once published, a version is never modified. Changes are made by
+/// publishing a new version, while existing documents stay on the version they were
+/// created with. A new version that could invalidate existing documents is only published
+/// when explicitly forced.
 pub struct FormService {
     db: DatabaseConnection,
 }
@@ -21,23 +25,86 @@ impl FormService {
         Self { db }
     }
 
-    /// Saves a given `FormDefinition` to the database.
+    /// Saves the first version of a new `FormDefinition` to the database.
     ///
     /// # Arguments
     /// * `form` - The `FormDefinition` to be saved.
     ///
     /// # Returns
     /// A `Result` which is `Ok(FormDefinition)` if the form was successfully saved,
-    /// or `Err(ServiceError)` if a database error occurs.
+    /// or `Err(ServiceError)` if a form with the same ID already exists or a database
+    /// error occurs.
     pub async fn save_form(&self, form: FormDefinition) -> Result<FormDefinition, ServiceError> {
-        FormRepository::save(&self.db, &form)
+        let exists = FormRepository::find_by_id(&self.db, form.id())
             .await
-            .map_err(ServiceError::Internal)?;
+            .map_err(ServiceError::Internal)?
+            .is_some();
+        if exists {
+            return Err(ServiceError::Conflict(format!(
+                "Form '{}' already exists, publish a new version instead",
+                form.id()
+            )));
+        }
 
-        Ok(form)
+        self.insert(form).await
     }
 
-    /// Retrieves a `FormDefinition` by its unique identifier.
+    /// Publishes a new version of an existing form.
+    ///
+    /// The new version must directly follow the latest published one. It is compared
+    /// against the latest version first, and refused if any change is breaking (see
+    /// [`compare_forms`]) unless `force` is set.
+    ///
+    /// # Arguments
+    /// * `form` - The new version of the `FormDefinition`.
+    /// * `force` - Publish the version even if it contains breaking changes.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(FormDefinition)` if the version was published, or
+    /// `Err(ServiceError)` if the form does not exist, the version does not follow the
+    /// latest one, it contains breaking changes and was not forced, or a database
+    /// error occurs.
+    pub async fn publish_version(
+        &self,
+        form: FormDefinition,
+        force: bool,
+    ) -> Result<FormDefinition, ServiceError> {
+        let latest = self.get_form(form.id()).await?;
+        if form.version() != latest.version() + 1 {
+            return Err(ServiceError::Conflict(format!(
+                "Form '{}' is at version {}, cannot publish version {}",
+                form.id(),
+                latest.version(),
+                form.version()
+            )));
+        }
+
+        let report = compare_forms(&latest, &form);
+        if report.breaking && !force {
+            return Err(ServiceError::BreakingFormChange(report));
+        }
+
+        self.insert(form).await
+    }
+
+    /// Compares a proposed version of a form against the latest published one, without
+    /// publishing it.
+    ///
+    /// # Arguments
+    /// * `form` - The proposed version of the `FormDefinition`.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(CompatibilityReport)` classifying every change, or
+    /// `Err(ServiceError)` if the form does not exist or a database error occurs.
+    pub async fn check_compatibility(
+        &self,
+        form: &FormDefinition,
+    ) -> Result<CompatibilityReport, ServiceError> {
+        let latest = self.get_form(form.id()).await?;
+        Ok(compare_forms(&latest, form))
+    }
+
+    /// Retrieves the latest version of a `FormDefinition` by its unique identifier.
     ///
     /// # Arguments
     /// * `id` - The unique ID of the form definition to retrieve.
@@ -49,7 +116,61 @@ impl FormService {
         FormRepository::find_by_id(&self.db, id)
             .await
             .map_err(ServiceError::Internal)?
-            .ok_or_else(|| ServiceError::Internal(anyhow::anyhow!("Form not found")))
-        // TODO: Implement specific NotFound error code
+            .ok_or_else(|| ServiceError::FormNotFound(id.to_string()))
+    }
+
+    /// Retrieves a specific version of a `FormDefinition`.
+    ///
+    /// # Arguments
+    /// * `id` - The unique ID of the form definition to retrieve.
+    /// * `version` - The version to retrieve.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(FormDefinition)` if the version is found, or
+    /// `Err(ServiceError)` if it is not found or a database error occurs.
+    pub async fn get_form_version(
+        &self,
+        id: &str,
+        version: u32,
+    ) -> Result<FormDefinition, ServiceError> {
+        FormRepository::find_version(&self.db, id, version)
+            .await
+            .map_err(ServiceError::Internal)?
+            .ok_or_else(|| ServiceError::FormNotFound(format!("{} (version {})", id, version)))
+    }
+
+    /// Lists every published version of a form, oldest first.
+    ///
+    /// # Arguments
+    /// * `id` - The unique ID of the form definition.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(Vec<FormDefinition>)`, or `Err(ServiceError)` if the form
+    /// is not found or a database error occurs.
+    pub async fn list_versions(&self, id: &str) -> Result<Vec<FormDefinition>, ServiceError> {
+        let versions = FormRepository::find_versions(&self.db, id)
+            .await
+            .map_err(ServiceError::Internal)?;
+
+        if versions.is_empty() {
+            return Err(ServiceError::FormNotFound(id.to_string()));
+        }
+        Ok(versions)
+    }
+
+    /// Stores a form version, treating an existing row as a concurrent publish.
+    async fn insert(&self, form: FormDefinition) -> Result<FormDefinition, ServiceError> {
+        let inserted = FormRepository::insert(&self.db, &form)
+            .await
+            .map_err(ServiceError::Internal)?;
+        if !inserted {
+            return Err(ServiceError::Conflict(format!(
+                "Version {} of form '{}' has already been published",
+                form.version(),
+                form.id()
+            )));
+        }
+
+        Ok(form)
     }
 }
@@ -1,11 +1,16 @@
 //! This module serves as a re-export module for various services within the `molten-service` crate.
 //!
-//! It provides a consolidated place to access services for Document, Form, and Workflow entities.
+//! It provides a consolidated place to access services for Document, Form, and Workflow entities,
+//! for attachments, and for revalidating stored documents.
 
+pub mod attachment;
 pub mod document;
 pub mod form;
+pub mod revalidation;
 pub mod workflow;
 
+pub use attachment::AttachmentService;
 pub use document::DocumentService;
 pub use form::FormService;
+pub use revalidation::RevalidationService;
 pub use workflow::WorkflowService;
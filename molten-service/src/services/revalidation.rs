@@ -0,0 +1,498 @@
+//! This module provides the service struct for revalidating stored documents against a form.
+
+use crate::error::ServiceError;
+use chrono::{DateTime, TimeDelta, Utc};
+use molten_core::form::FormDefinition;
+use molten_core::query::{
+    Cursor, DocumentQuery, DocumentSort, MAX_PAGE_SIZE, SortDirection, SortField,
+};
+use molten_document::{DocumentValidationError, validate_document};
+use molten_storage_seaorm::repo::{DocumentRepository, FormRepository};
+use molten_storage_seaorm::sea_orm::DatabaseConnection;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// The number of documents loaded and validated at a time, unless specified otherwise.
+pub const DEFAULT_BATCH_SIZE: u64 = 100;
+
+/// How long a completed or failed job can still be polled before it is evicted.
+pub const FINISHED_JOB_RETENTION: TimeDelta = TimeDelta::hours(1);
+
+/// The number of completed or failed jobs kept at most; the longest finished are evicted
+/// first.
+pub const MAX_FINISHED_JOBS: usize = 100;
+
+/// The number of invalid documents a report lists at most. Documents that fail validation
+/// beyond these are only counted.
+pub const MAX_REPORTED_INVALID: usize = 1000;
+
+/// The lifecycle of a revalidation job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// The job was created but has not started scanning yet.
+    Pending,
+    /// The job is scanning documents.
+    Running,
+    /// Every document was scanned.
+    Completed,
+    /// The scan stopped because of an error.
+    Failed,
+}
+
+/// A stored document that does not pass validation.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InvalidDocument {
+    /// The ID of the document.
+    pub document_id: String,
+    /// The form version the document was created with.
+    pub form_version: u32,
+    /// Every reason the document failed validation.
+    pub errors: Vec<DocumentValidationError>,
+}
+
+/// The outcome (so far) of revalidating the documents of a form.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RevalidationReport {
+    /// The ID of the form whose documents are revalidated.
+    pub form_id: String,
+    /// The form version the documents are validated against.
+    pub form_version: u32,
+    /// The number of documents of the form when the scan started.
+    pub total: u64,
+    /// The number of documents validated so far.
+    pub processed: u64,
+    /// The number of documents that failed validation so far.
+    pub invalid_count: u64,
+    /// The first [`MAX_REPORTED_INVALID`] documents that failed validation.
+    pub invalid: Vec<InvalidDocument>,
+}
+
+impl RevalidationReport {
+    /// Records a scanned batch: the number of documents validated and those that failed,
+    /// listing the failures while there is room for them.
+    fn record(&mut self, processed: u64, invalid: Vec<InvalidDocument>) {
+        self.processed += processed;
+        self.invalid_count += invalid.len() as u64;
+        let room = MAX_REPORTED_INVALID.saturating_sub(self.invalid.len());
+        self.invalid.extend(invalid.into_iter().take(room));
+    }
+}
+
+/// A revalidation running in the background, polled for progress.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RevalidationJob {
+    /// The unique identifier of the job.
+    pub id: String,
+    /// The state of the job.
+    pub status: JobStatus,
+    /// The number of documents loaded and validated at a time.
+    pub batch_size: u64,
+    /// The progress and findings of the job.
+    #[serde(flatten)]
+    pub report: RevalidationReport,
+    /// Why the job failed, if it did.
+    pub error: Option<String>,
+    /// When the job was created.
+    pub created_at: DateTime<Utc>,
+    /// When the job completed or failed.
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Service for revalidating stored documents after their form changes.
+///
+/// Documents are pinned to the form version they were created with, so publishing a new
+/// version never invalidates them on its own. This service reports which documents of a
+/// form would fail `validate_document` against a given version (the latest by default),
+/// scanning them in batches.
+///
+/// A revalidation can run to completion in a single call ([`revalidate`](Self::revalidate)),
+/// or as a job that is started, run in the background and polled for progress. Jobs are kept
+/// in memory, so they do not survive a restart. Finished jobs are evicted once they are older
+/// than [`FINISHED_JOB_RETENTION`], or when more than [`MAX_FINISHED_JOBS`] have finished.
+pub struct RevalidationService {
+    db: DatabaseConnection,
+    jobs: Mutex<HashMap<String, RevalidationJob>>,
+}
+
+impl RevalidationService {
+    /// Creates a new `RevalidationService` instance.
+    ///
+    /// # Arguments
+    /// * `db` - A `sea_orm::DatabaseConnection` used for database operations.
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            db,
+            jobs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Revalidates every document of a form and waits for the result.
+    ///
+    /// # Arguments
+    /// * `form_id` - The ID of the form whose documents are revalidated.
+    /// * `form_version` - The version to validate against, or `None` for the latest.
+    /// * `batch_size` - The number of documents loaded at a time, capped at `MAX_PAGE_SIZE`.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(RevalidationReport)` counting the invalid documents, or
+    /// `Err(ServiceError)` if the form or version does not exist or a database error occurs.
+    pub async fn revalidate(
+        &self,
+        form_id: &str,
+        form_version: Option<u32>,
+        batch_size: u64,
+    ) -> Result<RevalidationReport, ServiceError> {
+        let form = self.resolve_form(form_id, form_version).await?;
+        let mut report = self.new_report(&form).await?;
+
+        self.scan(&form, batch_size, |processed, invalid| {
+            report.record(processed, invalid)
+        })
+        .await?;
+
+        Ok(report)
+    }
+
+    /// Creates a pending revalidation job, to be executed with [`run_job`](Self::run_job).
+    ///
+    /// # Arguments
+    /// * `form_id` - The ID of the form whose documents are revalidated.
+    /// * `form_version` - The version to validate against, or `None` for the latest.
+    /// * `batch_size` - The number of documents loaded at a time, capped at `MAX_PAGE_SIZE`.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(RevalidationJob)` with the new job, or `Err(ServiceError)`
+    /// if the form or version does not exist or a database error occurs.
+    pub async fn start_job(
+        &self,
+        form_id: &str,
+        form_version: Option<u32>,
+        batch_size: u64,
+    ) -> Result<RevalidationJob, ServiceError> {
+        let form = self.resolve_form(form_id, form_version).await?;
+        let report = self.new_report(&form).await?;
+
+        let job = RevalidationJob {
+            id: Uuid::new_v4().to_string(),
+            status: JobStatus::Pending,
+            batch_size: clamp_batch_size(batch_size),
+            report,
+            error: None,
+            created_at: Utc::now(),
+            finished_at: None,
+        };
+
+        let mut jobs = self.lock_jobs();
+        evict_finished_jobs(&mut jobs, job.created_at);
+        jobs.insert(job.id.clone(), job.clone());
+        Ok(job)
+    }
+
+    /// Runs a pending revalidation job to completion, recording its progress after every
+    /// batch so that it can be polled with [`get_job`](Self::get_job).
+    ///
+    /// A failure while scanning is recorded on the job rather than returned.
+    ///
+    /// # Arguments
+    /// * `job_id` - The ID of a job created by [`start_job`](Self::start_job).
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(RevalidationJob)` with the finished job, or
+    /// `Err(ServiceError)` if the job does not exist or is not pending.
+    pub async fn run_job(&self, job_id: &str) -> Result<RevalidationJob, ServiceError> {
+        let job = self.update_job(job_id, |job| {
+            if job.status != JobStatus::Pending {
+                return Err(ServiceError::Conflict(format!(
+                    "Revalidation job '{}' has already been started",
+                    job.id
+                )));
+            }
+            job.status = JobStatus::Running;
+            Ok(())
+        })?;
+
+        let result = match self
+            .resolve_form(&job.report.form_id, Some(job.report.form_version))
+            .await
+        {
+            Ok(form) => {
+                self.scan(&form, job.batch_size, |processed, invalid| {
+                    // Only finished jobs are evicted, so the job is still registered
+                    let _ = self.update_job(job_id, |job| {
+                        job.report.record(processed, invalid);
+                        Ok(())
+                    });
+                })
+                .await
+            }
+            Err(e) => Err(e),
+        };
+
+        self.update_job(job_id, |job| {
+            match result {
+                Ok(()) => job.status = JobStatus::Completed,
+                Err(e) => {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(e.to_string());
+                }
+            }
+            job.finished_at = Some(Utc::now());
+            Ok(())
+        })
+    }
+
+    /// Retrieves the current state of a revalidation job.
+    ///
+    /// # Arguments
+    /// * `job_id` - The ID of the job.
+    ///
+    /// # Returns
+    /// A `Result` which is `Ok(RevalidationJob)` with a snapshot of the job, or
+    /// `Err(ServiceError::JobNotFound)` if no such job exists.
+    pub fn get_job(&self, job_id: &str) -> Result<RevalidationJob, ServiceError> {
+        self.lock_jobs()
+            .get(job_id)
+            .cloned()
+            .ok_or_else(|| ServiceError::JobNotFound(job_id.to_string()))
+    }
+
+    /// Loads the form version to validate against.
+    async fn resolve_form(
+        &self,
+        form_id: &str,
+        form_version: Option<u32>,
+    ) -> Result<FormDefinition, ServiceError> {
+        let form = match form_version {
+            Some(version) => FormRepository::find_version(&self.db, form_id, version).await,
+            None => FormRepository::find_by_id(&self.db, form_id).await,
+        }
+        .map_err(ServiceError::Internal)?;
+
+        form.ok_or_else(|| match form_version {
+            Some(version) => {
+                ServiceError::FormNotFound(format!("{} (version {})", form_id, version))
+            }
+            None => ServiceError::FormNotFound(form_id.to_string()),
+        })
+    }
+
+    /// Creates an empty report, counting the documents to scan.
+    async fn new_report(&self, form: &FormDefinition) -> Result<RevalidationReport, ServiceError> {
+        let total = DocumentRepository::count(&self.db, &documents_of(form))
+            .await
+            .map_err(ServiceError::Internal)?;
+
+        Ok(RevalidationReport {
+            form_id: form.id().to_string(),
+            form_version: form.version(),
+            total,
+            processed: 0,
+            invalid_count: 0,
+            invalid: Vec::new(),
+        })
+    }
+
+    /// Validates every document of a form, oldest first, one batch at a time.
+    ///
+    /// After each batch, `on_batch` receives the number of documents validated and the
+    /// ones that failed.
+    async fn scan<F>(
+        &self,
+        form: &FormDefinition,
+        batch_size: u64,
+        mut on_batch: F,
+    ) -> Result<(), ServiceError>
+    where
+        F: FnMut(u64, Vec<InvalidDocument>),
+    {
+        let mut query = DocumentQuery {
+            limit: clamp_batch_size(batch_size),
+            ..documents_of(form)
+        };
+
+        loop {
+            let page = DocumentRepository::find_page(&self.db, &query)
+                .await
+                .map_err(ServiceError::Internal)?;
+
+            let invalid = page
+                .items
+                .iter()
+                .filter_map(|doc| {
+                    validate_document(doc, form)
+                        .err()
+                        .map(|errors| InvalidDocument {
+                            document_id: doc.id.clone(),
+                            form_version: doc.form_version,
+                            errors,
+                        })
+                })
+                .collect();
+            on_batch(page.items.len() as u64, invalid);
+
+            match (page.next_cursor, page.items.last()) {
+                (Some(_), Some(last)) => {
+                    query.after = Some(Cursor::after(last, SortField::CreatedAt))
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Applies a change to a job and returns a snapshot of the result.
+    fn update_job<F>(&self, job_id: &str, change: F) -> Result<RevalidationJob, ServiceError>
+    where
+        F: FnOnce(&mut RevalidationJob) -> Result<(), ServiceError>,
+    {
+        let mut jobs = self.lock_jobs();
+        let job = jobs
+            .get_mut(job_id)
+            .ok_or_else(|| ServiceError::JobNotFound(job_id.to_string()))?;
+
+        change(job)?;
+        Ok(job.clone())
+    }
+
+    /// Locks the in-memory job registry.
+    fn lock_jobs(&self) -> std::sync::MutexGuard<'_, HashMap<String, RevalidationJob>> {
+        self.jobs
+            .lock()
+            .expect("revalidation job registry poisoned")
+    }
+}
+
+/// Evicts the finished jobs that are older than [`FINISHED_JOB_RETENTION`], then the
+/// longest finished ones until at most [`MAX_FINISHED_JOBS`] remain. Pending and running
+/// jobs are never evicted.
+fn evict_finished_jobs(jobs: &mut HashMap<String, RevalidationJob>, now: DateTime<Utc>) {
+    let cutoff = now - FINISHED_JOB_RETENTION;
+    jobs.retain(|_, job| job.finished_at.is_none_or(|at| at > cutoff));
+
+    let mut finished: Vec<(DateTime<Utc>, String)> = jobs
+        .values()
+        .filter_map(|job| job.finished_at.map(|at| (at, job.id.clone())))
+        .collect();
+    if finished.len() > MAX_FINISHED_JOBS {
+        finished.sort();
+        for (_, id) in &finished[..finished.len() - MAX_FINISHED_JOBS] {
+            jobs.remove(id);
+        }
+    }
+}
+
+/// Selects every document of a form, across all of its versions, oldest first.
+fn documents_of(form: &FormDefinition) -> DocumentQuery {
+    DocumentQuery {
+        form_id: Some(form.id().to_string()),
+        sort: DocumentSort {
+            field: SortField::CreatedAt,
+            direction: SortDirection::Asc,
+        },
+        ..Default::default()
+    }
+}
+
+/// Keeps a requested batch size within the page sizes the repository accepts.
+fn clamp_batch_size(batch_size: u64) -> u64 {
+    batch_size.clamp(1, MAX_PAGE_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: &str, finished_at: Option<DateTime<Utc>>) -> RevalidationJob {
+        RevalidationJob {
+            id: id.to_string(),
+            status: match finished_at {
+                Some(_) => JobStatus::Completed,
+                None => JobStatus::Running,
+            },
+            batch_size: DEFAULT_BATCH_SIZE,
+            report: RevalidationReport {
+                form_id: "ticket".to_string(),
+                form_version: 1,
+                total: 0,
+                processed: 0,
+                invalid_count: 0,
+                invalid: Vec::new(),
+            },
+            error: None,
+            created_at: finished_at.unwrap_or_else(Utc::now),
+            finished_at,
+        }
+    }
+
+    fn registry(jobs: Vec<RevalidationJob>) -> HashMap<String, RevalidationJob> {
+        jobs.into_iter().map(|job| (job.id.clone(), job)).collect()
+    }
+
+    #[test]
+    fn test_evicts_expired_finished_jobs() {
+        let now = Utc::now();
+        let mut jobs = registry(vec![
+            job(
+                "expired",
+                Some(now - FINISHED_JOB_RETENTION - TimeDelta::seconds(1)),
+            ),
+            job("recent", Some(now - TimeDelta::minutes(5))),
+            job("running", None),
+        ]);
+
+        evict_finished_jobs(&mut jobs, now);
+
+        let mut kept: Vec<&str> = jobs.keys().map(String::as_str).collect();
+        kept.sort();
+        assert_eq!(kept, vec!["recent", "running"]);
+    }
+
+    #[test]
+    fn test_caps_finished_jobs() {
+        let now = Utc::now();
+        let mut all: Vec<RevalidationJob> = (0..MAX_FINISHED_JOBS + 2)
+            .map(|i| {
+                job(
+                    &format!("job-{}", i),
+                    Some(now - TimeDelta::seconds(i as i64)),
+                )
+            })
+            .collect();
+        all.push(job("running", None));
+        let mut jobs = registry(all);
+
+        evict_finished_jobs(&mut jobs, now);
+
+        // The two longest finished jobs go, the running one stays
+        assert_eq!(jobs.len(), MAX_FINISHED_JOBS + 1);
+        assert!(!jobs.contains_key(&format!("job-{}", MAX_FINISHED_JOBS + 1)));
+        assert!(!jobs.contains_key(&format!("job-{}", MAX_FINISHED_JOBS)));
+        assert!(jobs.contains_key("running"));
+    }
+
+    #[test]
+    fn test_caps_listed_invalid_documents() {
+        let invalid = |count: usize| -> Vec<InvalidDocument> {
+            (0..count)
+                .map(|i| InvalidDocument {
+                    document_id: format!("doc-{}", i),
+                    form_version: 1,
+                    errors: Vec::new(),
+                })
+                .collect()
+        };
+        let mut report = job("job", None).report;
+
+        report.record(10, invalid(MAX_REPORTED_INVALID - 1));
+        report.record(10, invalid(3));
+        report.record(10, invalid(2));
+
+        assert_eq!(report.processed, 30);
+        assert_eq!(report.invalid_count, MAX_REPORTED_INVALID as u64 + 4);
+        assert_eq!(report.invalid.len(), MAX_REPORTED_INVALID);
+        // Only the first failure of the second batch still fits
+        assert_eq!(report.invalid.last().unwrap().document_id, "doc-0");
+    }
+}
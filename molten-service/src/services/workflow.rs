@@ -51,7 +51,6 @@ impl WorkflowService {
         WorkflowRepository::find_by_id(&self.db, id)
             .await
             .map_err(ServiceError::Internal)?
-            .ok_or_else(|| ServiceError::Internal(anyhow::anyhow!("Workflow not found")))
-        // TODO: Implement specific NotFound error code
+            .ok_or_else(|| ServiceError::WorkflowNotFound(id.to_string()))
     }
 }
@@ -0,0 +1,125 @@
+//! This module provides `LocalBlobStore`, a `BlobStore` keeping file contents on the
+//! local filesystem.
+
+use async_trait::async_trait;
+use molten_core::attachment::BlobStore;
+use std::io::{Error, ErrorKind, Result};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A `BlobStore` keeping each blob as a file below a root directory.
+///
+/// Blobs are spread over subdirectories named after the first two characters of their
+/// key, so that no single directory grows too large.
+pub struct LocalBlobStore {
+    root: PathBuf,
+    // Distinguishes the temporary files of concurrent writes
+    next_temp: AtomicU64,
+}
+
+impl LocalBlobStore {
+    /// Creates a new `LocalBlobStore` below `root`, which is created on the first write.
+    ///
+    /// # Arguments
+    /// * `root` - The directory holding the blobs.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            next_temp: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the path of the file holding the blob stored under `key`.
+    ///
+    /// Keys are SHA-256 digests, so anything but hex digits is rejected, which also keeps
+    /// keys from escaping the root directory.
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        if key.len() < 3 || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("invalid blob key '{}'", key),
+            ));
+        }
+        Ok(self.root.join(&key[..2]).join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, contents: &[u8]) -> Result<()> {
+        let path = self.path(key)?;
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+
+        // Write to a temporary file first, so that readers never see a partial blob
+        let n = self.next_temp.fetch_add(1, Ordering::Relaxed);
+        let temp = path.with_extension(format!("{}.{}.tmp", std::process::id(), n));
+        if let Err(e) = tokio::fs::write(&temp, contents).await {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(e);
+        }
+        tokio::fs::rename(&temp, &path).await
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        tokio::fs::read(self.path(key)?).await
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        tokio::fs::try_exists(self.path(key)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[tokio::test]
+    async fn test_put_get_exists() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalBlobStore::new(dir.path().join("blobs"));
+
+        assert!(!store.exists(KEY).await.unwrap());
+        store.put(KEY, b"test").await.unwrap();
+        assert!(store.exists(KEY).await.unwrap());
+        assert_eq!(store.get(KEY).await.unwrap(), b"test");
+        assert!(dir.path().join("blobs").join("9f").join(KEY).is_file());
+
+        // Writing the same key again replaces the blob, leaving no temporary file behind
+        store.put(KEY, b"test").await.unwrap();
+        let files = std::fs::read_dir(dir.path().join("blobs").join("9f"))
+            .unwrap()
+            .count();
+        assert_eq!(files, 1);
+
+        let missing = store.get(&KEY.replace('9', "a")).await.unwrap_err();
+        assert_eq!(missing.kind(), ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalBlobStore::new(dir.path().join("blobs"));
+        std::fs::write(dir.path().join("secret"), b"secret").unwrap();
+
+        for key in [
+            "",
+            "ab",
+            "not-hex-at-all",
+            "../secret",
+            "ab/../../secret",
+            "ab\\cd",
+        ] {
+            let err = store.put(key, b"test").await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{}", key);
+            let err = store.get(key).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{}", key);
+            let err = store.exists(key).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{}", key);
+        }
+        assert!(!dir.path().join("blobs").exists());
+    }
+}
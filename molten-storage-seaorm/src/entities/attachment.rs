@@ -0,0 +1,41 @@
+//! This module provides the SeaORM entity definition for Attachments.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Represents the metadata of an uploaded file stored in the database.
+///
+/// The file contents are kept in a blob store under `sha256`, so several rows may share
+/// the same contents.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "attachments")]
+pub struct Model {
+    /// The unique identifier for the attachment. This is a UUID string.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// The name of the file, as uploaded.
+    pub file_name: String,
+
+    /// The MIME type of the file.
+    pub mime_type: String,
+
+    /// The size of the file, in bytes.
+    pub size: i64,
+
+    /// The SHA-256 digest of the file contents, as lowercase hex.
+    #[sea_orm(index)]
+    pub sha256: String,
+
+    /// The ID of the actor who uploaded the file.
+    pub uploaded_by: String,
+
+    /// The timestamp of the upload.
+    pub created_at: DateTimeUtc,
+}
+
+/// Defines relationships for the attachment entity.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -18,6 +18,9 @@ pub struct Model {
     #[sea_orm(index)]
     pub form_id: String,
 
+    /// The version of the form definition the document is validated against.
+    pub form_version: i32,
+
     /// The foreign key linking to the associated workflow definition.
     #[sea_orm(index)]
     pub workflow_id: String,
@@ -31,6 +34,10 @@ pub struct Model {
     #[sea_orm(column_type = "JsonBinary")]
     pub data: Json,
 
+    /// The version of the document, incremented on every update.
+    /// Updates are conditional on this value to detect concurrent edits.
+    pub version: i32,
+
     /// The timestamp when the document was created.
     pub created_at: DateTimeUtc,
     /// The timestamp when the document was last updated.
@@ -43,8 +50,8 @@ pub enum Relation {
     /// Establishes a many-to-one relationship with the `Form` entity.
     #[sea_orm(
         belongs_to = "super::form::Entity",
-        from = "Column::FormId",
-        to = "super::form::Column::Id",
+        from = "(Column::FormId, Column::FormVersion)",
+        to = "(super::form::Column::Id, super::form::Column::Version)",
         on_update = "Cascade",
         on_delete = "Restrict"
     )]
@@ -59,6 +66,20 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     Workflow,
+
+    /// Establishes a one-to-many relationship with `DocumentTransition` entities.
+    #[sea_orm(has_many = "super::document_transition::Entity")]
+    Transitions,
+
+    /// Establishes a one-to-many relationship with the `DocumentReference` entities
+    /// held by this document.
+    #[sea_orm(has_many = "super::document_reference::Entity")]
+    References,
+
+    /// Establishes a one-to-many relationship with the `DocumentAssignee` entities
+    /// naming who this document is assigned to.
+    #[sea_orm(has_many = "super::document_assignee::Entity")]
+    Assignees,
 }
 
 impl Related<super::form::Entity> for Entity {
@@ -73,4 +94,22 @@ impl Related<super::workflow::Entity> for Entity {
     }
 }
 
+impl Related<super::document_transition::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Transitions.def()
+    }
+}
+
+impl Related<super::document_assignee::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Assignees.def()
+    }
+}
+
+impl Related<super::document_reference::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::References.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
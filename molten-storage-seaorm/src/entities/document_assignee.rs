@@ -0,0 +1,43 @@
+//! This module provides the SeaORM entity definition for Document Assignees.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Represents a user a document belongs to while in its current phase.
+///
+/// Rows are derived from the document's data and the assignee field of its current
+/// phase, and rewritten whenever the document is saved, so that documents can be looked
+/// up by who they are assigned to.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "document_assignees")]
+pub struct Model {
+    /// The foreign key linking to the assigned document.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub document_id: String,
+
+    /// The ID of the actor the document is assigned to.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub assignee: String,
+}
+
+/// Defines relationships for the document assignee entity.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// Establishes a many-to-one relationship with the assigned `Document` entity.
+    #[sea_orm(
+        belongs_to = "super::document::Entity",
+        from = "Column::DocumentId",
+        to = "super::document::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Document,
+}
+
+impl Related<super::document::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Document.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,49 @@
+//! This module provides the SeaORM entity definition for Document References.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Represents a link from a `Reference` field of one document to another document.
+///
+/// Rows are derived from the referencing document's data and rewritten whenever that
+/// document is saved, so that documents can be looked up by what references them.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "document_references")]
+pub struct Model {
+    /// The foreign key linking to the referencing document.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub document_id: String,
+
+    /// The path of the referencing field within the document's data.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub field_id: String,
+
+    /// The ID of the referenced document.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub target_id: String,
+
+    /// The form of the referenced document.
+    pub target_form_id: String,
+}
+
+/// Defines relationships for the document reference entity.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// Establishes a many-to-one relationship with the referencing `Document` entity.
+    #[sea_orm(
+        belongs_to = "super::document::Entity",
+        from = "Column::DocumentId",
+        to = "super::document::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Document,
+}
+
+impl Related<super::document::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Document.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
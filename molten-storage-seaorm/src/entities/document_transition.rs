@@ -0,0 +1,61 @@
+//! This module provides the SeaORM entity definition for Document Transitions.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Represents one entry of a document's transition history stored in the database.
+///
+/// Rows in this table are append-only and form the audit trail of who moved a
+/// document between phases, when, and why.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "document_transitions")]
+pub struct Model {
+    /// The unique identifier for the history entry. This is a UUID string.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// The foreign key linking to the document that changed phase.
+    #[sea_orm(index)]
+    pub document_id: String,
+
+    /// The phase the document left. Null when the document was created.
+    pub from_phase: Option<String>,
+
+    /// The phase the document entered.
+    pub to_phase: String,
+
+    /// The name of the transition that was taken. Null when the document was created.
+    pub transition_name: Option<String>,
+
+    /// The ID of the actor who moved the document.
+    pub actor: String,
+
+    /// An optional comment supplied with the transition.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub comment: Option<String>,
+
+    /// The timestamp when the document changed phase.
+    pub created_at: DateTimeUtc,
+}
+
+/// Defines relationships for the document transition entity.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    /// Establishes a many-to-one relationship with the `Document` entity.
+    #[sea_orm(
+        belongs_to = "super::document::Entity",
+        from = "Column::DocumentId",
+        to = "super::document::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Document,
+}
+
+impl Related<super::document::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Document.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
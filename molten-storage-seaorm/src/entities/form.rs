@@ -3,10 +3,11 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-/// Represents a form definition entity stored in the database.
+/// Represents one published version of a form definition stored in the database.
 ///
 /// This struct defines the database model for a form, including its unique identifier,
-/// name, version, and the full schema as a JSON document.
+/// name, version, and the full schema as a JSON document. Rows are keyed by
+/// `(id, version)` and never modified once written.
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "forms")]
 pub struct Model {
@@ -19,6 +20,8 @@ pub struct Model {
     pub name: String,
 
     /// The version of the form definition, duplicated from the schema for fast version checks.
+    /// Together with `id`, forms the primary key.
+    #[sea_orm(primary_key, auto_increment = false)]
     pub version: i32,
 
     /// The complete `FormDefinition` struct serialized as a JSON object.
@@ -29,6 +32,7 @@ pub struct Model {
     /// The timestamp when the form definition was created.
     pub created_at: DateTimeUtc,
     /// The timestamp when the form definition was last updated.
+    /// Equal to `created_at`, as published versions are immutable.
     pub updated_at: DateTimeUtc,
 }
 
@@ -4,7 +4,11 @@
 //! such as documents, forms, and workflows. These entities map directly to database tables
 //! and are used by the repositories for persistence operations.
 
+pub mod attachment;
 pub mod document;
+pub mod document_assignee;
+pub mod document_reference;
+pub mod document_transition;
 pub mod form;
 pub mod prelude;
 pub mod workflow;
@@ -3,6 +3,10 @@
 //! This module provides a convenient way to import all defined entities
 //! within the `molten-storage-seaorm` crate using a single `use` statement.
 
+pub use super::attachment::Entity as Attachment;
 pub use super::document::Entity as Document;
+pub use super::document_assignee::Entity as DocumentAssignee;
+pub use super::document_reference::Entity as DocumentReference;
+pub use super::document_transition::Entity as DocumentTransition;
 pub use super::form::Entity as Form;
 pub use super::workflow::Entity as Workflow;
@@ -2,12 +2,15 @@
 //!
 //! `molten-storage-seaorm` provides SeaORM-based database persistence implementation for the
 //! Molten Document and Workflow Management system. This crate implements the repository traits
-//! defined in `molten-core` using SeaORM.
+//! defined in `molten-core` using SeaORM,
+//! along with a local filesystem store for the contents of attachments.
 //!
 //! This crate is under active development and is not yet stable.
 //! If this crate has been abandoned, please message me and we can discuss ownership transfer.
 
 #![warn(missing_docs)]
+/// Provides a filesystem implementation of the `BlobStore` trait for attachment contents.
+pub mod blob;
 /// Provides SeaORM entity definitions for various Molten data structures.
 pub mod entities;
 /// Provides repository implementations for interacting with Molten entities in the database.
@@ -0,0 +1,100 @@
+//! Repository implementation for interacting with Attachment entities in the database.
+
+use crate::entities::attachment;
+use crate::entities::attachment::Entity as AttachmentEntity;
+use anyhow::Result;
+use molten_core::attachment::Attachment;
+use sea_orm::sea_query::LockType;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QuerySelect, Set,
+};
+use std::collections::HashMap;
+
+/// Repository for `Attachment` metadata. The file contents are kept by a `BlobStore`.
+pub struct AttachmentRepository;
+
+impl AttachmentRepository {
+    /// Creates a new attachment entry in the database.
+    ///
+    /// # Arguments
+    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `attachment` - A reference to the `Attachment` metadata to be stored.
+    ///
+    /// # Returns
+    /// `Result<()>` indicating success or failure.
+    pub async fn create(db: &DatabaseConnection, attachment: &Attachment) -> Result<()> {
+        let active_model = attachment::ActiveModel {
+            id: Set(attachment.id.clone()),
+            file_name: Set(attachment.file_name.clone()),
+            mime_type: Set(attachment.mime_type.clone()),
+            size: Set(i64::try_from(attachment.size)?),
+            sha256: Set(attachment.sha256.clone()),
+            uploaded_by: Set(attachment.uploaded_by.clone()),
+            created_at: Set(attachment.created_at),
+        };
+
+        active_model.insert(db).await?;
+        Ok(())
+    }
+
+    /// Finds an attachment by its unique ID.
+    ///
+    /// # Arguments
+    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `id` - The ID of the attachment to find.
+    ///
+    /// # Returns
+    /// `Result<Option<Attachment>>` the attachment if found, or `None` if no attachment
+    /// matches the ID.
+    pub async fn find_by_id(db: &DatabaseConnection, id: &str) -> Result<Option<Attachment>> {
+        let model = AttachmentEntity::find_by_id(id).one(db).await?;
+
+        model.map(to_domain).transpose()
+    }
+
+    /// Finds the attachments with the given IDs.
+    ///
+    /// Accepts any connection so that attachments can be checked within the database
+    /// transaction of the document holding them. The rows found are locked against
+    /// deletion until the end of that transaction.
+    ///
+    /// # Arguments
+    /// * `db` - A database connection or transaction.
+    /// * `ids` - The IDs of the attachments to find.
+    ///
+    /// # Returns
+    /// `Result<HashMap<String, Attachment>>` the attachments that exist, keyed by ID.
+    pub async fn find_by_ids<C: ConnectionTrait>(
+        db: &C,
+        ids: &[String],
+    ) -> Result<HashMap<String, Attachment>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let models = AttachmentEntity::find()
+            .filter(attachment::Column::Id.is_in(ids.iter().cloned()))
+            .lock(LockType::KeyShare)
+            .all(db)
+            .await?;
+
+        models
+            .into_iter()
+            .map(|model| to_domain(model).map(|a| (a.id.clone(), a)))
+            .collect()
+    }
+}
+
+/// Converts a database model into an `Attachment` domain model.
+fn to_domain(model: attachment::Model) -> Result<Attachment> {
+    Ok(Attachment {
+        id: model.id,
+        file_name: model.file_name,
+        mime_type: model.mime_type,
+        size: u64::try_from(model.size)?,
+        sha256: model.sha256,
+        uploaded_by: model.uploaded_by,
+        created_at: model.created_at,
+    })
+}
@@ -1,10 +1,18 @@
 //! Repository implementation for interacting with Document entities in the database.
 
-use crate::entities::document;
 use crate::entities::document::Entity as DocumentEntity;
+use crate::entities::{document, document_assignee};
 use anyhow::Result;
 use molten_core::document::Document;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use molten_core::query::{
+    Cursor, DataFilter, DocumentPage, DocumentQuery, FilterOp, FilterValue, SortDirection,
+    SortField,
+};
+use sea_orm::sea_query::{Expr, LockType};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
 use serde_json::Value;
 use std::collections::HashMap; // Using anyhow for simplified error handling in storage layer
 
@@ -18,20 +26,22 @@ impl DocumentRepository {
     /// Inserts a new `Document` domain model into the database.
     ///
     /// # Arguments
-    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `db` - A database connection or transaction.
     /// * `doc` - A reference to the `Document` domain model to be created.
     ///
     /// # Returns
     /// `Result<()>` indicating success or failure.
-    pub async fn create(db: &DatabaseConnection, doc: &Document) -> Result<()> {
+    pub async fn create<C: ConnectionTrait>(db: &C, doc: &Document) -> Result<()> {
         // Convert Domain Model -> ActiveModel
         let active_model = document::ActiveModel {
             id: Set(doc.id.clone()),
             form_id: Set(doc.form_id.clone()),
+            form_version: Set(doc.form_version as i32),
             workflow_id: Set(doc.workflow_id.clone()),
             current_phase: Set(doc.current_phase.clone()),
             // Serialize the HashMap into a JSON Value
             data: Set(serde_json::to_value(&doc.data)?),
+            version: Set(doc.version),
             created_at: Set(doc.created_at),
             updated_at: Set(doc.updated_at),
         };
@@ -52,44 +62,78 @@ impl DocumentRepository {
     pub async fn find_by_id(db: &DatabaseConnection, id: &str) -> Result<Option<Document>> {
         let model = DocumentEntity::find_by_id(id).one(db).await?;
 
-        match model {
-            Some(m) => {
-                // Convert DB Model -> Domain Model
-                let data_map: HashMap<String, Value> = serde_json::from_value(m.data)?;
-
-                Ok(Some(Document {
-                    id: m.id,
-                    form_id: m.form_id,
-                    workflow_id: m.workflow_id,
-                    current_phase: m.current_phase,
-                    data: data_map,
-                    created_at: m.created_at,
-                    updated_at: m.updated_at,
-                }))
-            }
-            None => Ok(None),
-        }
+        model.map(to_domain).transpose()
     }
 
-    /// Updates an existing document's `current_phase` and `data` fields in the database.
+    /// Updates an existing document's `current_phase`, `data`, `version` and `updated_at`
+    /// fields in the database, provided it is still at `expected_version`.
+    ///
+    /// The write is a single conditional `UPDATE`, so if another writer saved the
+    /// document since it was read, nothing is written.
     ///
     /// # Arguments
-    /// * `db` - A reference to the `DatabaseConnection`.
-    /// * `doc` - A reference to the `Document` domain model containing the updated fields.
+    /// * `db` - A database connection or transaction.
+    /// * `doc` - A reference to the `Document` domain model containing the updated fields,
+    ///   including its new `version`.
+    /// * `expected_version` - The version the document had when it was read.
     ///
     /// # Returns
-    /// `Result<()>` indicating success or failure.
-    pub async fn update(db: &DatabaseConnection, doc: &Document) -> Result<()> {
+    /// `Result<bool>` which is `true` if the document was updated, or `false` if it does
+    /// not exist or is no longer at `expected_version`.
+    pub async fn update<C: ConnectionTrait>(
+        db: &C,
+        doc: &Document,
+        expected_version: i32,
+    ) -> Result<bool> {
         let active_model = document::ActiveModel {
-            id: Set(doc.id.clone()), // Primary key determines which row to update
             current_phase: Set(doc.current_phase.clone()),
             data: Set(serde_json::to_value(&doc.data)?),
-            updated_at: Set(chrono::Utc::now()),
-            ..Default::default() // Don't touch other fields (form_id, created_at)
+            version: Set(doc.version),
+            updated_at: Set(doc.updated_at),
+            ..Default::default() // Don't touch other fields (form_id, form_version, created_at)
         };
 
-        active_model.update(db).await?;
-        Ok(())
+        let result = DocumentEntity::update_many()
+            .set(active_model)
+            .filter(document::Column::Id.eq(doc.id.as_str()))
+            .filter(document::Column::Version.eq(expected_version))
+            .exec(db)
+            .await?;
+
+        Ok(result.rows_affected == 1)
+    }
+
+    /// Looks up the form of each of the given documents.
+    ///
+    /// The rows found are locked against deletion until the end of the transaction `db`
+    /// belongs to, so that documents referencing them can be checked and saved together.
+    ///
+    /// # Arguments
+    /// * `db` - A database connection or transaction.
+    /// * `ids` - The IDs of the documents to look up.
+    ///
+    /// # Returns
+    /// `Result<HashMap<String, String>>` mapping the ID of every document that exists to
+    /// its form ID, or an `Err` if a database error occurs.
+    pub async fn find_form_ids<C: ConnectionTrait>(
+        db: &C,
+        ids: &[String],
+    ) -> Result<HashMap<String, String>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows: Vec<(String, String)> = DocumentEntity::find()
+            .select_only()
+            .column(document::Column::Id)
+            .column(document::Column::FormId)
+            .filter(document::Column::Id.is_in(ids.iter().cloned()))
+            .lock(LockType::KeyShare)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(rows.into_iter().collect())
     }
 
     /// Finds all documents that are currently in a specific phase.
@@ -109,20 +153,303 @@ impl DocumentRepository {
             .await?;
 
         // Map all results back to Domain Models
-        let mut docs = Vec::new();
-        for m in models {
-            let data_map: HashMap<String, Value> = serde_json::from_value(m.data)?;
-            docs.push(Document {
-                id: m.id,
-                form_id: m.form_id,
-                workflow_id: m.workflow_id,
-                current_phase: m.current_phase,
-                data: data_map,
-                created_at: m.created_at,
-                updated_at: m.updated_at,
-            });
+        models.into_iter().map(to_domain).collect()
+    }
+
+    /// Finds all documents assigned to a user in their current phase, least recently
+    /// updated first.
+    ///
+    /// This method leverages the indexed `assignee` column of the `document_assignees`
+    /// table for efficient querying.
+    ///
+    /// # Arguments
+    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `assignee` - The ID of the actor whose documents to find.
+    ///
+    /// # Returns
+    /// `Result<Vec<Document>>` a vector of `Document` domain models, or an `Err` if a database error occurs.
+    pub async fn find_by_assignee(
+        db: &DatabaseConnection,
+        assignee: &str,
+    ) -> Result<Vec<Document>> {
+        let models = DocumentEntity::find()
+            .inner_join(document_assignee::Entity)
+            .filter(document_assignee::Column::Assignee.eq(assignee))
+            .order_by_asc(document::Column::UpdatedAt)
+            .order_by_asc(document::Column::Id)
+            .all(db)
+            .await?;
+
+        models.into_iter().map(to_domain).collect()
+    }
+
+    /// Lists one page of documents matching a `DocumentQuery`.
+    ///
+    /// Pagination is keyset-based: the query's cursor holds the sort key and ID of the
+    /// last document of the previous page, and this page starts strictly after it.
+    /// Documents inserted concurrently therefore never shift or duplicate entries
+    /// across pages.
+    ///
+    /// # Arguments
+    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `query` - The filters, sort order, page size and cursor to apply.
+    ///
+    /// # Returns
+    /// `Result<DocumentPage>` holding up to `query.limit` documents and the cursor of the
+    /// next page, or an `Err` if a database error occurs.
+    pub async fn find_page(db: &DatabaseConnection, query: &DocumentQuery) -> Result<DocumentPage> {
+        let sort_column = match query.sort.field {
+            SortField::CreatedAt => document::Column::CreatedAt,
+            SortField::UpdatedAt => document::Column::UpdatedAt,
+        };
+        let order = match query.sort.direction {
+            SortDirection::Asc => Order::Asc,
+            SortDirection::Desc => Order::Desc,
+        };
+
+        let mut select = DocumentEntity::find().filter(filter_condition(query));
+
+        if let Some(cursor) = &query.after {
+            select = select.filter(after_cursor(sort_column, query.sort.direction, cursor));
         }
 
-        Ok(docs)
+        // Fetch one extra row to learn whether there is a next page
+        let models = select
+            .order_by(sort_column, order.clone())
+            .order_by(document::Column::Id, order)
+            .limit(query.limit + 1)
+            .all(db)
+            .await?;
+
+        let mut items = models
+            .into_iter()
+            .map(to_domain)
+            .collect::<Result<Vec<_>>>()?;
+
+        let next_cursor = if items.len() as u64 > query.limit {
+            items.truncate(query.limit as usize);
+            items
+                .last()
+                .map(|doc| Cursor::after(doc, query.sort.field).encode())
+        } else {
+            None
+        };
+
+        Ok(DocumentPage { items, next_cursor })
+    }
+
+    /// Counts the documents matching the filters of a `DocumentQuery`.
+    ///
+    /// The query's sort order, page size and cursor are ignored.
+    ///
+    /// # Arguments
+    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `query` - The filters to apply.
+    ///
+    /// # Returns
+    /// `Result<u64>` with the number of matching documents, or an `Err` if a database
+    /// error occurs.
+    pub async fn count(db: &DatabaseConnection, query: &DocumentQuery) -> Result<u64> {
+        let count = DocumentEntity::find()
+            .filter(filter_condition(query))
+            .count(db)
+            .await?;
+
+        Ok(count)
     }
 }
+
+/// Builds the `WHERE` clause for the metadata and data filters of a `DocumentQuery`.
+fn filter_condition(query: &DocumentQuery) -> Condition {
+    let data = query.data.iter().fold(Condition::all(), |cond, filter| {
+        cond.add(data_condition(filter))
+    });
+
+    Condition::all()
+        .add(data)
+        .add_option(
+            query
+                .form_id
+                .as_deref()
+                .map(|id| document::Column::FormId.eq(id)),
+        )
+        .add_option(
+            query
+                .form_version
+                .map(|version| document::Column::FormVersion.eq(version as i32)),
+        )
+        .add_option(
+            query
+                .workflow_id
+                .as_deref()
+                .map(|id| document::Column::WorkflowId.eq(id)),
+        )
+        .add_option(
+            query
+                .current_phase
+                .as_deref()
+                .map(|phase| document::Column::CurrentPhase.eq(phase)),
+        )
+        .add_option(
+            query
+                .created_from
+                .map(|t| document::Column::CreatedAt.gte(t)),
+        )
+        .add_option(query.created_to.map(|t| document::Column::CreatedAt.lt(t)))
+        .add_option(
+            query
+                .updated_from
+                .map(|t| document::Column::UpdatedAt.gte(t)),
+        )
+        .add_option(query.updated_to.map(|t| document::Column::UpdatedAt.lt(t)))
+}
+
+/// Compiles a `DataFilter` into a Postgres JSONB expression on the `data` column.
+///
+/// JSON values are compared as `jsonb`, which orders numbers numerically, while
+/// date-times are cast to `timestamptz` so that offsets are taken into account.
+/// The field ID and value are always bound as parameters.
+///
+/// Stored values are cast with the `molten_try_*` functions, which yield `NULL` for
+/// values that do not parse as the type (e.g. free text kept from an older form
+/// version), so such documents simply do not match instead of failing the query.
+/// Range filters on JSON values likewise skip stored values of another JSON type.
+fn data_condition(filter: &DataFilter) -> Expr {
+    let field = sea_orm::Value::from(filter.field.as_str());
+
+    match &filter.value {
+        FilterValue::DateTime(instant) => {
+            let sql = format!(
+                r#"molten_try_timestamptz("data" ->> $1) {} $2"#,
+                comparison(filter.op)
+            );
+            Expr::cust_with_values(sql, [field, sea_orm::Value::from(*instant)])
+        }
+        // Decimal fields may hold JSON numbers or strings, both cast exactly
+        FilterValue::Decimal(number) => cast_condition(filter, "numeric", number.to_string()),
+        FilterValue::Date(date) => cast_condition(filter, "date", date.to_string()),
+        FilterValue::Time(time) => cast_condition(filter, "time", time.to_string()),
+        // Postgres reads ISO 8601 durations as intervals
+        FilterValue::Duration(duration) => cast_condition(filter, "interval", duration.to_string()),
+        FilterValue::Json(value) => match filter.op {
+            FilterOp::Contains => {
+                let options = serde_json::Value::Array(vec![value.clone()]).to_string();
+                Expr::cust_with_values(
+                    r#""data" -> $1 @> CAST($2 AS jsonb)"#,
+                    [field, sea_orm::Value::from(options)],
+                )
+            }
+            FilterOp::Prefix => {
+                let text = value.as_str().unwrap_or_default();
+                let pattern = format!("{}%", escape_like(text));
+                Expr::cust_with_values(
+                    r#""data" ->> $1 LIKE $2"#,
+                    [field, sea_orm::Value::from(pattern)],
+                )
+            }
+            FilterOp::ILike => {
+                let pattern = value.as_str().unwrap_or_default().to_string();
+                Expr::cust_with_values(
+                    r#""data" ->> $1 ILIKE $2"#,
+                    [field, sea_orm::Value::from(pattern)],
+                )
+            }
+            // jsonb orders values of different types by type (strings sort below numbers),
+            // so ranges only compare stored values of the filter value's own type
+            op @ (FilterOp::Lt | FilterOp::Lte | FilterOp::Gt | FilterOp::Gte) => {
+                let sql = format!(
+                    r#"jsonb_typeof("data" -> $1) = jsonb_typeof(CAST($2 AS jsonb)) AND "data" -> $1 {} CAST($2 AS jsonb)"#,
+                    comparison(op)
+                );
+                Expr::cust_with_values(sql, [field, sea_orm::Value::from(value.to_string())])
+            }
+            op => {
+                let sql = format!(r#""data" -> $1 {} CAST($2 AS jsonb)"#, comparison(op));
+                Expr::cust_with_values(sql, [field, sea_orm::Value::from(value.to_string())])
+            }
+        },
+    }
+}
+
+/// Compares a field of the document data with a value, after casting both to `sql_type`.
+///
+/// The stored value goes through `molten_try_{sql_type}`, so values that do not parse
+/// compare as `NULL`. The filter value was already parsed, but goes through the same
+/// function, as Postgres cannot cast negative ISO 8601 durations directly.
+fn cast_condition(filter: &DataFilter, sql_type: &str, value: String) -> Expr {
+    let sql = format!(
+        r#"molten_try_{sql_type}("data" ->> $1) {} molten_try_{sql_type}($2)"#,
+        comparison(filter.op)
+    );
+    Expr::cust_with_values(
+        sql,
+        [
+            sea_orm::Value::from(filter.field.as_str()),
+            sea_orm::Value::from(value),
+        ],
+    )
+}
+
+/// Returns the SQL operator for a comparison `FilterOp`.
+///
+/// `ne` uses `IS DISTINCT FROM` so that documents without the field also match.
+fn comparison(op: FilterOp) -> &'static str {
+    match op {
+        FilterOp::Eq => "=",
+        FilterOp::Ne => "IS DISTINCT FROM",
+        FilterOp::Lt => "<",
+        FilterOp::Lte => "<=",
+        FilterOp::Gt => ">",
+        FilterOp::Gte => ">=",
+        // Pattern and containment operators are compiled separately
+        FilterOp::Contains | FilterOp::Prefix | FilterOp::ILike => "=",
+    }
+}
+
+/// Escapes the `LIKE` wildcards in `text` so that it matches literally.
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Builds the keyset condition selecting the rows that sort strictly after `cursor`.
+///
+/// Equivalent to the row comparison `(sort_column, id) > (value, id)` (or `<` when
+/// sorting in descending order).
+fn after_cursor(
+    sort_column: document::Column,
+    direction: SortDirection,
+    cursor: &Cursor,
+) -> Condition {
+    let id = cursor.id.as_str();
+    match direction {
+        SortDirection::Asc => Condition::any().add(sort_column.gt(cursor.value)).add(
+            Condition::all()
+                .add(sort_column.eq(cursor.value))
+                .add(document::Column::Id.gt(id)),
+        ),
+        SortDirection::Desc => Condition::any().add(sort_column.lt(cursor.value)).add(
+            Condition::all()
+                .add(sort_column.eq(cursor.value))
+                .add(document::Column::Id.lt(id)),
+        ),
+    }
+}
+
+/// Converts a database row into a `Document` domain model.
+fn to_domain(m: document::Model) -> Result<Document> {
+    let data_map: HashMap<String, Value> = serde_json::from_value(m.data)?;
+
+    Ok(Document {
+        id: m.id,
+        form_id: m.form_id,
+        form_version: m.form_version as u32,
+        workflow_id: m.workflow_id,
+        current_phase: m.current_phase,
+        data: data_map,
+        version: m.version,
+        created_at: m.created_at,
+        updated_at: m.updated_at,
+    })
+}
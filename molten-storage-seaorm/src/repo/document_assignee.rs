@@ -0,0 +1,50 @@
+//! Repository implementation for interacting with Document Assignee entities in the database.
+
+use crate::entities::document_assignee;
+use crate::entities::document_assignee::Entity as DocumentAssigneeEntity;
+use anyhow::Result;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set};
+
+/// Repository for the users documents are assigned to in their current phase.
+///
+/// Documents are looked up by assignee through `DocumentRepository::find_by_assignee`.
+pub struct DocumentAssigneeRepository;
+
+impl DocumentAssigneeRepository {
+    /// Replaces the users a document is assigned to.
+    ///
+    /// Accepts any connection so that the assignees can be written in the same database
+    /// transaction as the document they were derived from.
+    ///
+    /// # Arguments
+    /// * `db` - A database connection or transaction.
+    /// * `document_id` - The ID of the assigned document.
+    /// * `assignees` - The IDs of every actor the document is now assigned to.
+    ///
+    /// # Returns
+    /// `Result<()>` indicating success or failure.
+    pub async fn replace<C: ConnectionTrait>(
+        db: &C,
+        document_id: &str,
+        assignees: &[String],
+    ) -> Result<()> {
+        DocumentAssigneeEntity::delete_many()
+            .filter(document_assignee::Column::DocumentId.eq(document_id))
+            .exec(db)
+            .await?;
+
+        if assignees.is_empty() {
+            return Ok(());
+        }
+
+        let models = assignees.iter().map(|a| document_assignee::ActiveModel {
+            document_id: Set(document_id.to_string()),
+            assignee: Set(a.clone()),
+        });
+        DocumentAssigneeEntity::insert_many(models)
+            .on_conflict_do_nothing()
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+//! Repository implementation for interacting with Document Reference entities in the database.
+
+use crate::entities::document_reference;
+use crate::entities::document_reference::Entity as DocumentReferenceEntity;
+use anyhow::Result;
+use molten_core::document::DocumentReference;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+
+/// Repository for `DocumentReference` entries, the links held by documents' `Reference` fields.
+pub struct DocumentReferenceRepository;
+
+impl DocumentReferenceRepository {
+    /// Replaces the references held by a document.
+    ///
+    /// Accepts any connection so that the references can be written in the same database
+    /// transaction as the document they were collected from.
+    ///
+    /// # Arguments
+    /// * `db` - A database connection or transaction.
+    /// * `document_id` - The ID of the referencing document.
+    /// * `references` - Every reference the document now holds.
+    ///
+    /// # Returns
+    /// `Result<()>` indicating success or failure.
+    pub async fn replace<C: ConnectionTrait>(
+        db: &C,
+        document_id: &str,
+        references: &[DocumentReference],
+    ) -> Result<()> {
+        DocumentReferenceEntity::delete_many()
+            .filter(document_reference::Column::DocumentId.eq(document_id))
+            .exec(db)
+            .await?;
+
+        if references.is_empty() {
+            return Ok(());
+        }
+
+        let models = references.iter().map(|r| document_reference::ActiveModel {
+            document_id: Set(r.document_id.clone()),
+            field_id: Set(r.field_id.clone()),
+            target_id: Set(r.target_id.clone()),
+            target_form_id: Set(r.target_form_id.clone()),
+        });
+        // The same document may be listed twice under one field
+        DocumentReferenceEntity::insert_many(models)
+            .on_conflict_do_nothing()
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+
+    /// Retrieves the references held by a document, in field order.
+    ///
+    /// # Arguments
+    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `document_id` - The ID of the referencing document.
+    ///
+    /// # Returns
+    /// `Result<Vec<DocumentReference>>` the references, or an `Err` if a database error occurs.
+    pub async fn find_by_document(
+        db: &DatabaseConnection,
+        document_id: &str,
+    ) -> Result<Vec<DocumentReference>> {
+        let models = DocumentReferenceEntity::find()
+            .filter(document_reference::Column::DocumentId.eq(document_id))
+            .order_by_asc(document_reference::Column::FieldId)
+            .order_by_asc(document_reference::Column::TargetId)
+            .all(db)
+            .await?;
+
+        Ok(models.into_iter().map(to_domain).collect())
+    }
+
+    /// Retrieves the references to a document held by other documents.
+    ///
+    /// This method leverages the indexed `target_id` column for efficient querying.
+    ///
+    /// # Arguments
+    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `target_id` - The ID of the referenced document.
+    ///
+    /// # Returns
+    /// `Result<Vec<DocumentReference>>` the references, or an `Err` if a database error occurs.
+    pub async fn find_by_target(
+        db: &DatabaseConnection,
+        target_id: &str,
+    ) -> Result<Vec<DocumentReference>> {
+        let models = DocumentReferenceEntity::find()
+            .filter(document_reference::Column::TargetId.eq(target_id))
+            .order_by_asc(document_reference::Column::DocumentId)
+            .order_by_asc(document_reference::Column::FieldId)
+            .all(db)
+            .await?;
+
+        Ok(models.into_iter().map(to_domain).collect())
+    }
+}
+
+/// Converts a database model into a `DocumentReference` domain model.
+fn to_domain(model: document_reference::Model) -> DocumentReference {
+    DocumentReference {
+        document_id: model.document_id,
+        field_id: model.field_id,
+        target_id: model.target_id,
+        target_form_id: model.target_form_id,
+    }
+}
@@ -0,0 +1,77 @@
+//! Repository implementation for interacting with Document Transition entities in the database.
+
+use crate::entities::document_transition;
+use crate::entities::document_transition::Entity as DocumentTransitionEntity;
+use anyhow::Result;
+use molten_core::history::TransitionRecord;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+
+/// Repository for `TransitionRecord` entries, the append-only history of document phase changes.
+pub struct DocumentTransitionRepository;
+
+impl DocumentTransitionRepository {
+    /// Appends a `TransitionRecord` to a document's history.
+    ///
+    /// Accepts any connection so that the record can be written in the same database
+    /// transaction as the document update it describes.
+    ///
+    /// # Arguments
+    /// * `db` - A database connection or transaction.
+    /// * `record` - A reference to the `TransitionRecord` to be stored.
+    ///
+    /// # Returns
+    /// `Result<()>` indicating success or failure.
+    pub async fn create<C: ConnectionTrait>(db: &C, record: &TransitionRecord) -> Result<()> {
+        let active_model = document_transition::ActiveModel {
+            id: Set(record.id.clone()),
+            document_id: Set(record.document_id.clone()),
+            from_phase: Set(record.from_phase.clone()),
+            to_phase: Set(record.to_phase.clone()),
+            transition_name: Set(record.transition.clone()),
+            actor: Set(record.actor.clone()),
+            comment: Set(record.comment.clone()),
+            created_at: Set(record.created_at),
+        };
+
+        active_model.insert(db).await?;
+        Ok(())
+    }
+
+    /// Retrieves the full transition history of a document, oldest entry first.
+    ///
+    /// # Arguments
+    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `document_id` - The ID of the document whose history to retrieve.
+    ///
+    /// # Returns
+    /// `Result<Vec<TransitionRecord>>` the history entries in chronological order, or an
+    /// `Err` if a database error occurs.
+    pub async fn find_by_document(
+        db: &DatabaseConnection,
+        document_id: &str,
+    ) -> Result<Vec<TransitionRecord>> {
+        let models = DocumentTransitionEntity::find()
+            .filter(document_transition::Column::DocumentId.eq(document_id))
+            .order_by_asc(document_transition::Column::CreatedAt)
+            .order_by_asc(document_transition::Column::Id)
+            .all(db)
+            .await?;
+
+        Ok(models
+            .into_iter()
+            .map(|m| TransitionRecord {
+                id: m.id,
+                document_id: m.document_id,
+                from_phase: m.from_phase,
+                to_phase: m.to_phase,
+                transition: m.transition_name,
+                actor: m.actor,
+                comment: m.comment,
+                created_at: m.created_at,
+            })
+            .collect())
+    }
+}
@@ -4,57 +4,53 @@ use crate::entities::form;
 use crate::entities::form::Entity as FormEntity;
 use anyhow::Result;
 use molten_core::form::FormDefinition;
-use sea_orm::{DatabaseConnection, EntityTrait, Set};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
 
 /// Repository for `FormDefinition` entities, providing CRUD operations.
 ///
 /// This struct acts as a data access layer for form definitions, abstracting the underlying
-/// SeaORM implementation.
+/// SeaORM implementation. Each version of a form is stored as its own immutable row.
 pub struct FormRepository;
 
 impl FormRepository {
-    /// Saves a `FormDefinition` to the database.
+    /// Inserts a new version of a `FormDefinition` into the database.
     ///
-    /// If a form with the same ID already exists, it will be updated.
+    /// Published versions are immutable: if this version of the form already exists,
+    /// nothing is written.
     ///
     /// # Arguments
     /// * `db` - A reference to the `DatabaseConnection`.
     /// * `def` - A reference to the `FormDefinition` domain model to be saved.
     ///
     /// # Returns
-    /// `Result<()>` indicating success or failure.
-    pub async fn save(db: &DatabaseConnection, def: &FormDefinition) -> Result<()> {
+    /// `Result<bool>` which is `true` if the version was inserted, or `false` if it
+    /// already existed.
+    pub async fn insert(db: &DatabaseConnection, def: &FormDefinition) -> Result<bool> {
         // We store the *entire* definition as JSON, but also pull out
         // name/version for SQL columns.
+        let now = chrono::Utc::now();
         let active_model = form::ActiveModel {
             id: Set(def.id().to_string()),
             name: Set(def.name().to_string()),
             version: Set(def.version() as i32),
             schema: Set(serde_json::to_value(def)?),
-            created_at: Set(chrono::Utc::now()),
-            updated_at: Set(chrono::Utc::now()),
+            created_at: Set(now),
+            updated_at: Set(now),
         };
 
-        // TODO: Separate 'insert' vs 'update' logic here.
-        // For now, we'll assume simple insertion of new versions.
-        form::Entity::insert(active_model)
+        let inserted = form::Entity::insert(active_model)
             .on_conflict(
-                sea_orm::sea_query::OnConflict::column(form::Column::Id)
-                    .update_columns([
-                        form::Column::Name,
-                        form::Column::Version,
-                        form::Column::Schema,
-                        form::Column::UpdatedAt,
-                    ])
+                sea_orm::sea_query::OnConflict::columns([form::Column::Id, form::Column::Version])
+                    .do_nothing()
                     .to_owned(),
             )
-            .exec(db)
+            .exec_without_returning(db)
             .await?;
 
-        Ok(())
+        Ok(inserted == 1)
     }
 
-    /// Retrieves a `FormDefinition` by its ID.
+    /// Retrieves the latest version of a `FormDefinition` by its ID.
     ///
     /// # Arguments
     /// * `db` - A reference to the `DatabaseConnection`.
@@ -64,15 +60,59 @@ impl FormRepository {
     /// `Result<Option<FormDefinition>>` where `Some(FormDefinition)` is returned if found,
     /// `None` if not found, or an `Err` if a database error occurs.
     pub async fn find_by_id(db: &DatabaseConnection, id: &str) -> Result<Option<FormDefinition>> {
-        let model = FormEntity::find_by_id(id).one(db).await?;
+        let model = FormEntity::find()
+            .filter(form::Column::Id.eq(id))
+            .order_by_desc(form::Column::Version)
+            .one(db)
+            .await?;
+
+        model.map(to_domain).transpose()
+    }
+
+    /// Retrieves a specific version of a `FormDefinition`.
+    ///
+    /// # Arguments
+    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `id` - The ID of the form definition to retrieve.
+    /// * `version` - The version to retrieve.
+    ///
+    /// # Returns
+    /// `Result<Option<FormDefinition>>` where `Some(FormDefinition)` is returned if found,
+    /// `None` if not found, or an `Err` if a database error occurs.
+    pub async fn find_version(
+        db: &DatabaseConnection,
+        id: &str,
+        version: u32,
+    ) -> Result<Option<FormDefinition>> {
+        let model = FormEntity::find_by_id((id.to_string(), version as i32))
+            .one(db)
+            .await?;
+
+        model.map(to_domain).transpose()
+    }
+
+    /// Retrieves every published version of a `FormDefinition`, oldest first.
+    ///
+    /// # Arguments
+    /// * `db` - A reference to the `DatabaseConnection`.
+    /// * `id` - The ID of the form definition.
+    ///
+    /// # Returns
+    /// `Result<Vec<FormDefinition>>` the versions in ascending order (empty if the form
+    /// does not exist), or an `Err` if a database error occurs.
+    pub async fn find_versions(db: &DatabaseConnection, id: &str) -> Result<Vec<FormDefinition>> {
+        let models = FormEntity::find()
+            .filter(form::Column::Id.eq(id))
+            .order_by_asc(form::Column::Version)
+            .all(db)
+            .await?;
 
-        match model {
-            Some(m) => {
-                // We deserializing primarily from the JSON column
-                let def: FormDefinition = serde_json::from_value(m.schema)?;
-                Ok(Some(def))
-            }
-            None => Ok(None),
-        }
+        models.into_iter().map(to_domain).collect()
     }
 }
+
+/// Converts a database row into a `FormDefinition` domain model.
+fn to_domain(m: form::Model) -> Result<FormDefinition> {
+    // We deserialize primarily from the JSON column
+    Ok(serde_json::from_value(m.schema)?)
+}
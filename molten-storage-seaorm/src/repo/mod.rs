@@ -1,13 +1,22 @@
 //! Repository implementations for interacting with Molten entities in the database.
 //!
 //! This module provides concrete implementations of the repository traits, using SeaORM
-//! to perform CRUD operations for documents, forms, and workflows.
+//! to perform CRUD operations for documents, their transition history, references and
+//! assignees, attachments, forms, and workflows.
 
+pub mod attachment;
 pub mod document;
+pub mod document_assignee;
+pub mod document_reference;
+pub mod document_transition;
 pub mod form;
 pub mod workflow;
 
 // Re-export for easier access
+pub use attachment::AttachmentRepository;
 pub use document::DocumentRepository;
+pub use document_assignee::DocumentAssigneeRepository;
+pub use document_reference::DocumentReferenceRepository;
+pub use document_transition::DocumentTransitionRepository;
 pub use form::FormRepository;
 pub use workflow::WorkflowRepository;
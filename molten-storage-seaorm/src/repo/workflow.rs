@@ -3,7 +3,7 @@
 use crate::entities::workflow;
 use crate::entities::workflow::Entity as WorkflowEntity;
 use anyhow::Result;
-use molten_core::workflow::WorkflowDefinition;
+use molten_core::workflow::{WorkflowBuilder, WorkflowDefinition};
 use sea_orm::{DatabaseConnection, EntityTrait, Set};
 
 /// Repository for `WorkflowDefinition` entities, providing CRUD operations.
@@ -50,6 +50,9 @@ impl WorkflowRepository {
 
     /// Retrieves a `WorkflowDefinition` by its ID.
     ///
+    /// Stored definitions are not revalidated, so that workflows saved before a
+    /// validation rule was introduced keep working.
+    ///
     /// # Arguments
     /// * `db` - A reference to the `DatabaseConnection`.
     /// * `id` - The ID of the workflow definition to retrieve.
@@ -65,8 +68,8 @@ impl WorkflowRepository {
 
         match model {
             Some(m) => {
-                let def: WorkflowDefinition = serde_json::from_value(m.graph)?;
-                Ok(Some(def))
+                let builder: WorkflowBuilder = serde_json::from_value(m.graph)?;
+                Ok(Some(builder.build_lenient()))
             }
             None => Ok(None),
         }
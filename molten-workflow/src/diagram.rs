@@ -0,0 +1,211 @@
+//! This module renders a `WorkflowDefinition` as a diagram.
+//!
+//! Two text formats are supported: Mermaid `stateDiagram-v2`, which renders inline in
+//! Markdown, and Graphviz DOT. In both, phases are styled by their `PhaseType` so that
+//! the entry point and terminal states stand out, and transitions are labelled with
+//! their name.
+use molten_core::workflow::{PhaseType, WorkflowDefinition};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// The text formats a workflow diagram can be rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiagramFormat {
+    /// Mermaid `stateDiagram-v2` syntax.
+    #[default]
+    Mermaid,
+    /// Graphviz DOT syntax.
+    Dot,
+}
+
+impl FromStr for DiagramFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mermaid" => Ok(Self::Mermaid),
+            "dot" | "graphviz" => Ok(Self::Dot),
+            other => Err(format!(
+                "Unknown diagram format '{}', expected 'mermaid' or 'dot'",
+                other
+            )),
+        }
+    }
+}
+
+/// Renders a workflow in the requested format.
+pub fn render(workflow: &WorkflowDefinition, format: DiagramFormat) -> String {
+    match format {
+        DiagramFormat::Mermaid => to_mermaid(workflow),
+        DiagramFormat::Dot => to_dot(workflow),
+    }
+}
+
+/// Renders a workflow as a Mermaid `stateDiagram-v2`.
+///
+/// Phase IDs are replaced by positional aliases (`p0`, `p1`, ...) because Mermaid only
+/// accepts plain identifiers, while phase labels are kept as the state descriptions.
+/// The Start phase is entered from `[*]` and End phases lead to `[*]`.
+pub fn to_mermaid(workflow: &WorkflowDefinition) -> String {
+    let alias = |phase_id: &str| {
+        workflow
+            .phases()
+            .iter()
+            .position(|p| p.id == phase_id)
+            .map(|i| format!("p{}", i))
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "---\ntitle: {}\n---", mermaid_text(workflow.name()));
+    out.push_str("stateDiagram-v2\n");
+    out.push_str("    classDef start fill:#d5f5e3,stroke:#1e8449\n");
+    out.push_str("    classDef end fill:#eaecee,stroke:#2c3e50,stroke-width:3px\n");
+
+    for (i, phase) in workflow.phases().iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "    state \"{}\" as p{}",
+            mermaid_text(&phase.label),
+            i
+        );
+    }
+
+    for (i, phase) in workflow.phases().iter().enumerate() {
+        match phase.phase_type {
+            PhaseType::Start => {
+                let _ = writeln!(out, "    [*] --> p{}", i);
+            }
+            PhaseType::End => {
+                let _ = writeln!(out, "    p{} --> [*]", i);
+            }
+            PhaseType::Normal => {}
+        }
+    }
+
+    for transition in workflow.transitions() {
+        if let (Some(from), Some(to)) = (alias(&transition.from), alias(&transition.to)) {
+            let _ = writeln!(
+                out,
+                "    {} --> {} : {}",
+                from,
+                to,
+                mermaid_text(&transition.name)
+            );
+        }
+    }
+
+    for (i, phase) in workflow.phases().iter().enumerate() {
+        match phase.phase_type {
+            PhaseType::Start => {
+                let _ = writeln!(out, "    class p{} start", i);
+            }
+            PhaseType::End => {
+                let _ = writeln!(out, "    class p{} end", i);
+            }
+            PhaseType::Normal => {}
+        }
+    }
+
+    out
+}
+
+/// Renders a workflow as a Graphviz DOT `digraph`.
+///
+/// The Start phase is drawn filled, End phases with a double border.
+pub fn to_dot(workflow: &WorkflowDefinition) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "digraph \"{}\" {{", dot_text(workflow.id()));
+    let _ = writeln!(out, "    label=\"{}\";", dot_text(workflow.name()));
+    out.push_str("    rankdir=LR;\n");
+    out.push_str("    node [shape=box, style=rounded];\n");
+
+    for phase in workflow.phases() {
+        let style = match phase.phase_type {
+            PhaseType::Start => ", style=\"rounded,filled\", fillcolor=\"#d5f5e3\"",
+            PhaseType::Normal => "",
+            PhaseType::End => ", peripheries=2",
+        };
+        let _ = writeln!(
+            out,
+            "    \"{}\" [label=\"{}\"{}];",
+            dot_text(&phase.id),
+            dot_text(&phase.label),
+            style
+        );
+    }
+
+    for transition in workflow.transitions() {
+        let _ = writeln!(
+            out,
+            "    \"{}\" -> \"{}\" [label=\"{}\"];",
+            dot_text(&transition.from),
+            dot_text(&transition.to),
+            dot_text(&transition.name)
+        );
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Escapes text for use in a Mermaid label.
+fn mermaid_text(text: &str) -> String {
+    text.replace('"', "#quot;")
+        .replace(':', "#58;")
+        .replace(['\n', '\r'], " ")
+}
+
+/// Escapes text for use in a DOT quoted string.
+fn dot_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use molten_core::workflow::{Phase, Transition, WorkflowBuilder};
+
+    fn create_workflow() -> WorkflowDefinition {
+        WorkflowBuilder::new("wf_ticket", "Ticket Workflow")
+            .add_phase(Phase::new("draft", "Draft", PhaseType::Start))
+            .add_phase(Phase::new("review", "Under \"Review\"", PhaseType::Normal))
+            .add_phase(Phase::new("closed", "Closed", PhaseType::End))
+            .add_transition(Transition::new("submit", "draft", "review"))
+            .add_transition(Transition::new("approve", "review", "closed"))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_mermaid() {
+        let diagram = to_mermaid(&create_workflow());
+
+        assert!(diagram.contains("stateDiagram-v2"));
+        assert!(diagram.contains("state \"Under #quot;Review#quot;\" as p1"));
+        assert!(diagram.contains("[*] --> p0"));
+        assert!(diagram.contains("p2 --> [*]"));
+        assert!(diagram.contains("p0 --> p1 : submit"));
+        assert!(diagram.contains("class p0 start"));
+        assert!(diagram.contains("class p2 end"));
+    }
+
+    #[test]
+    fn test_dot() {
+        let diagram = to_dot(&create_workflow());
+
+        assert!(diagram.starts_with("digraph \"wf_ticket\" {"));
+        assert!(diagram.contains("\"review\" [label=\"Under \\\"Review\\\"\"];"));
+        assert!(diagram.contains("\"closed\" [label=\"Closed\", peripheries=2];"));
+        assert!(diagram.contains("\"review\" -> \"closed\" [label=\"approve\"];"));
+        assert!(diagram.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("Mermaid".parse(), Ok(DiagramFormat::Mermaid));
+        assert_eq!("dot".parse(), Ok(DiagramFormat::Dot));
+        assert!("svg".parse::<DiagramFormat>().is_err());
+    }
+}
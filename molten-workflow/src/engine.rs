@@ -1,8 +1,12 @@
 //! This module provides the functions which implement the core workflow engine logic.
 
 use crate::error::WorkflowError;
+use molten_core::actor::Actor;
 use molten_core::document::Document;
-use molten_core::workflow::{WorkflowDefinition, WorkflowGraph};
+use molten_core::form::FormDefinition;
+use molten_core::workflow::{Transition, WorkflowDefinition, WorkflowGraph};
+use molten_document::validate_field_value;
+use serde_json::Value;
 
 /// Attempts to transition a document from its current phase to a specified target phase
 /// according to the rules defined in the provided workflow.
@@ -16,23 +20,30 @@ use molten_core::workflow::{WorkflowDefinition, WorkflowGraph};
 /// 3. Handles initial transitions for new documents (those with an empty `current_phase`),
 ///    only allowing them to transition to the workflow's designated "Start" phase.
 /// 4. Validates that a direct transition path exists from the document's `current_phase`
-///    to the `target_phase_id` within the `workflow` graph.
+///    to the `target_phase_id` within the `workflow` graph, and that the document satisfies
+///    the guards of at least one such transition (see [`check_guards`]).
 ///
 /// # Arguments
 /// * `doc` - A mutable reference to the `Document` to be transitioned.
 /// * `workflow` - The `WorkflowDefinition` that defines the valid phases and transitions
 ///   for this document.
+/// * `form` - The `FormDefinition` of the document, used to check required field values
+///   and to evaluate transition conditions.
 /// * `target_phase_id` - The `id` of the phase to which the document should transition.
+/// * `actor` - The `Actor` requesting the transition, checked against the transition's roles.
 ///
 /// # Returns
-/// * `Ok(())` if the transition was successful and the document's phase was updated.
+/// * `Ok(Some(transition))` with the transition that was taken, once the document's phase
+///   was updated, or `Ok(None)` when a new document entered the "Start" phase.
 /// * `Err(WorkflowError)` if any of the validation checks fail (e.g., workflow mismatch,
-///   unknown phase, invalid transition, or no current phase on a non-new document).
-pub fn transition(
+///   unknown phase, invalid transition, unmet guards, or no current phase on a non-new document).
+pub fn transition<'w>(
     doc: &mut Document,
-    workflow: &WorkflowDefinition,
+    workflow: &'w WorkflowDefinition,
+    form: &FormDefinition,
     target_phase_id: &str,
-) -> Result<(), WorkflowError> {
+    actor: &Actor,
+) -> Result<Option<&'w Transition>, WorkflowError> {
     // 1. Sanity Check: Does the document belong to this workflow?
     if doc.workflow_id != workflow.id() {
         return Err(WorkflowError::WorkflowMismatch {
@@ -52,7 +63,7 @@ pub fn transition(
         if let Some(start_phase) = workflow.get_start_phase() {
             if start_phase.id == target_phase_id {
                 doc.current_phase = target_phase_id.to_string();
-                return Ok(());
+                return Ok(None);
             } else {
                 return Err(WorkflowError::InvalidTransition {
                     current: "WAITING_TO_START".to_string(),
@@ -68,27 +79,210 @@ pub fn transition(
     }
 
     // 4. Validate the Edge (The Transition Rule)
-    // We delegate this check to the WorkflowGraph trait we defined in Core.
-    if !workflow.can_transition(&doc.current_phase, target_phase_id) {
+    // Several transitions may connect the same pair of phases. The move is allowed if any
+    // of them passes its guards; otherwise we report why the first one failed.
+    let edges: Vec<&Transition> = workflow
+        .available_transitions(&doc.current_phase)
+        .into_iter()
+        .filter(|t| t.to == target_phase_id)
+        .collect();
+
+    if edges.is_empty() {
         return Err(WorkflowError::InvalidTransition {
             current: doc.current_phase.clone(),
             target: target_phase_id.to_string(),
         });
     }
 
+    let mut first_err = None;
+    for edge in edges {
+        if let Err(e) = check_guards(doc, form, edge, actor) {
+            first_err.get_or_insert(e);
+            continue;
+        }
+
+        // 5. Apply the Change
+        doc.current_phase = target_phase_id.to_string();
+
+        // Note: In a real system, you might trigger "Side Effects" here
+        // (e.g., sending emails), but that belongs in `molten-service`.
+        return Ok(Some(edge));
+    }
+
+    Err(first_err.expect("edges is non-empty, so at least one guard failed"))
+}
+
+/// Fires the named transition on a document, moving it to that transition's target phase.
+///
+/// Unlike [`transition`], the caller names the action (e.g. "approve") rather than the
+/// destination phase. This lets several transitions connect the same pair of phases while
+/// carrying different meaning.
+///
+/// # Arguments
+/// * `doc` - A mutable reference to the `Document` to be transitioned.
+/// * `workflow` - The `WorkflowDefinition` that defines the valid phases and transitions
+///   for this document.
+/// * `form` - The `FormDefinition` of the document, used to check required field values
+///   and to evaluate transition conditions.
+/// * `transition_name` - The `name` of a transition leaving the document's current phase.
+/// * `actor` - The `Actor` firing the transition, checked against the transition's roles.
+///
+/// # Returns
+/// * `Ok(transition)` with the fired transition, once the document's phase was updated.
+/// * `Err(WorkflowError)` if the workflow does not match, the document has no current
+///   phase, no transition with that name leaves the current phase, or the transition's
+///   guards are not satisfied.
+pub fn fire<'w>(
+    doc: &mut Document,
+    workflow: &'w WorkflowDefinition,
+    form: &FormDefinition,
+    transition_name: &str,
+    actor: &Actor,
+) -> Result<&'w Transition, WorkflowError> {
+    // 1. Sanity Check: Does the document belong to this workflow?
+    if doc.workflow_id != workflow.id() {
+        return Err(WorkflowError::WorkflowMismatch {
+            doc_wf: doc.workflow_id.clone(),
+            provided_wf: workflow.id().to_string(),
+        });
+    }
+
+    // 2. Named transitions only exist between phases, so a new document cannot fire one.
+    if doc.current_phase.is_empty() {
+        return Err(WorkflowError::NoCurrentPhase);
+    }
+
+    // 3. Resolve the Edge by name
+    let edge = workflow
+        .find_transition(&doc.current_phase, transition_name)
+        .ok_or_else(|| WorkflowError::UnknownTransition {
+            current: doc.current_phase.clone(),
+            name: transition_name.to_string(),
+        })?;
+
+    // 4. Evaluate the transition's guards
+    check_guards(doc, form, edge, actor)?;
+
     // 5. Apply the Change
-    doc.current_phase = target_phase_id.to_string();
+    doc.current_phase = edge.to.clone();
+
+    Ok(edge)
+}
+
+/// Checks that an actor may fire a transition and that the document satisfies its guards.
+///
+/// The guards are evaluated in order:
+/// 1. The actor must hold one of the transition's `allowed_roles` (if any are declared).
+/// 2. Each of the transition's `required_fields` must hold a value. A field counts as
+///    missing when it is absent, `null`, an empty string or an empty array.
+/// 3. Each required field's value must be valid for its definition in the form (e.g. a
+///    `Number` field must hold a number within its bounds).
+/// 4. The transition's `condition` (if any) must evaluate to true against the document data.
+///
+/// # Arguments
+/// * `doc` - The `Document` about to be transitioned.
+/// * `form` - The `FormDefinition` of the document, which defines the fields the guards read.
+/// * `edge` - The `Transition` that is about to fire.
+/// * `actor` - The `Actor` attempting to fire it.
+///
+/// # Returns
+/// * `Ok(())` if every guard passes.
+/// * `Err(WorkflowError::Forbidden)` if the actor lacks the required role.
+/// * `Err(WorkflowError::MissingRequiredFields)` listing every missing field.
+/// * `Err(WorkflowError::InvalidRequiredFields)` listing why each invalid field failed.
+/// * `Err(WorkflowError::ConditionNotMet)` if the condition evaluates to false.
+/// * `Err(WorkflowError::InvalidCondition)` if the condition cannot be evaluated.
+pub fn check_guards(
+    doc: &Document,
+    form: &FormDefinition,
+    edge: &Transition,
+    actor: &Actor,
+) -> Result<(), WorkflowError> {
+    if !actor.has_any_role(&edge.allowed_roles) {
+        return Err(WorkflowError::Forbidden {
+            transition: edge.name.clone(),
+            actor: actor.id.clone(),
+        });
+    }
+
+    let missing: Vec<String> = edge
+        .required_fields
+        .iter()
+        .filter(|field_id| !has_value(doc.get_value(field_id)))
+        .cloned()
+        .collect();
+
+    if !missing.is_empty() {
+        return Err(WorkflowError::MissingRequiredFields {
+            transition: edge.name.clone(),
+            fields: missing,
+        });
+    }
+
+    let invalid: Vec<String> = edge
+        .required_fields
+        .iter()
+        .filter_map(|field_id| {
+            let field = form.fields().iter().find(|f| f.id() == field_id)?;
+            let value = doc.get_value(field_id)?;
+            validate_field_value(value, field).err()
+        })
+        .flatten()
+        .map(|e| e.to_string())
+        .collect();
 
-    // Note: In a real system, you might trigger "Side Effects" here
-    // (e.g., sending emails), but that belongs in `molten-service`.
+    if !invalid.is_empty() {
+        return Err(WorkflowError::InvalidRequiredFields {
+            transition: edge.name.clone(),
+            errors: invalid,
+        });
+    }
+
+    if let Some(condition) = &edge.condition {
+        let holds =
+            condition
+                .evaluate(&doc.data, form)
+                .map_err(|e| WorkflowError::InvalidCondition {
+                    transition: edge.name.clone(),
+                    message: e.to_string(),
+                })?;
+        if !holds {
+            return Err(WorkflowError::ConditionNotMet {
+                transition: edge.name.clone(),
+                condition: condition.to_string(),
+            });
+        }
+    }
 
     Ok(())
 }
 
+/// Returns true if a document value counts as "filled in" for a transition guard.
+fn has_value(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::String(s)) => !s.trim().is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(_) => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use molten_core::workflow::{Phase, PhaseType, Transition, WorkflowBuilder};
+    use molten_core::expression::Expression;
+    use molten_core::field::{FieldBuilder, FieldType};
+    use molten_core::form::FormBuilder;
+    use molten_core::workflow::{Phase, PhaseType, WorkflowBuilder};
+    use serde_json::json;
+
+    fn form() -> FormDefinition {
+        FormBuilder::new("form_ticket", "Ticket").build().unwrap()
+    }
+
+    fn actor() -> Actor {
+        Actor::new("alice", &["author"])
+    }
 
     fn create_simple_workflow() -> WorkflowDefinition {
         WorkflowBuilder::new("wf_ticket", "Ticket Workflow")
@@ -110,15 +304,15 @@ mod tests {
         let mut doc = Document::new("doc1", "form_ticket", "wf_ticket");
 
         // 1. Initialize (Empty -> Start)
-        assert!(transition(&mut doc, &wf, "draft").is_ok());
+        assert!(transition(&mut doc, &wf, &form(), "draft", &actor()).is_ok());
         assert_eq!(doc.current_phase, "draft");
 
         // 2. Draft -> Review
-        assert!(transition(&mut doc, &wf, "review").is_ok());
+        assert!(transition(&mut doc, &wf, &form(), "review", &actor()).is_ok());
         assert_eq!(doc.current_phase, "review");
 
         // 3. Review -> Closed
-        assert!(transition(&mut doc, &wf, "closed").is_ok());
+        assert!(transition(&mut doc, &wf, &form(), "closed", &actor()).is_ok());
         assert_eq!(doc.current_phase, "closed");
     }
 
@@ -128,10 +322,10 @@ mod tests {
         let mut doc = Document::new("doc1", "doc_ticket", "wf_ticket");
 
         // Initialize
-        let _ = transition(&mut doc, &wf, "draft");
+        let _ = transition(&mut doc, &wf, &form(), "draft", &actor());
 
         // Try to skip Review (Draft -> Closed)
-        let res = transition(&mut doc, &wf, "closed");
+        let res = transition(&mut doc, &wf, &form(), "closed", &actor());
         assert!(res.is_err());
         assert!(matches!(
             res.unwrap_err(),
@@ -142,12 +336,247 @@ mod tests {
         assert_eq!(doc.current_phase, "draft");
     }
 
+    #[test]
+    fn test_fire_named_transitions() {
+        let wf = create_simple_workflow();
+        let mut doc = Document::new("doc1", "form_ticket", "wf_ticket");
+        let _ = transition(&mut doc, &wf, &form(), "draft", &actor());
+
+        // Draft -> Review -> Draft -> Review -> Closed, by action name
+        assert!(fire(&mut doc, &wf, &form(), "submit", &actor()).is_ok());
+        assert_eq!(doc.current_phase, "review");
+        assert!(fire(&mut doc, &wf, &form(), "reject", &actor()).is_ok());
+        assert_eq!(doc.current_phase, "draft");
+        assert!(fire(&mut doc, &wf, &form(), "submit", &actor()).is_ok());
+        assert!(fire(&mut doc, &wf, &form(), "approve", &actor()).is_ok());
+        assert_eq!(doc.current_phase, "closed");
+    }
+
+    #[test]
+    fn test_fire_unknown_transition() {
+        let wf = create_simple_workflow();
+        let mut doc = Document::new("doc1", "form_ticket", "wf_ticket");
+        let _ = transition(&mut doc, &wf, &form(), "draft", &actor());
+
+        // "approve" exists, but does not leave "draft"
+        let res = fire(&mut doc, &wf, &form(), "approve", &actor());
+        assert!(matches!(
+            res.unwrap_err(),
+            WorkflowError::UnknownTransition { .. }
+        ));
+        assert_eq!(doc.current_phase, "draft");
+    }
+
+    #[test]
+    fn test_fire_requires_current_phase() {
+        let wf = create_simple_workflow();
+        let mut doc = Document::new("doc1", "form_ticket", "wf_ticket");
+
+        let res = fire(&mut doc, &wf, &form(), "submit", &actor());
+        assert_eq!(res.unwrap_err(), WorkflowError::NoCurrentPhase);
+    }
+
+    fn create_qa_workflow() -> WorkflowDefinition {
+        WorkflowBuilder::new("wf_qa", "QA Workflow")
+            .add_phase(Phase::new("open", "Open", PhaseType::Start))
+            .add_phase(Phase::new("closed", "Closed", PhaseType::End))
+            .add_transition(
+                Transition::new("close", "open", "closed")
+                    .with_required_fields(&["root_cause", "actions"]),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_required_fields_block_transition() {
+        let wf = create_qa_workflow();
+        let mut doc = Document::new("doc1", "form_qa", "wf_qa");
+        let _ = transition(&mut doc, &wf, &form(), "open", &actor());
+        doc.set_value("root_cause", json!("  "));
+
+        let res = fire(&mut doc, &wf, &form(), "close", &actor());
+        assert_eq!(
+            res.unwrap_err(),
+            WorkflowError::MissingRequiredFields {
+                transition: "close".to_string(),
+                fields: vec!["root_cause".to_string(), "actions".to_string()],
+            }
+        );
+        assert_eq!(doc.current_phase, "open");
+
+        // Target-phase transitions run the same guards
+        let res = transition(&mut doc, &wf, &form(), "closed", &actor());
+        assert!(matches!(
+            res.unwrap_err(),
+            WorkflowError::MissingRequiredFields { .. }
+        ));
+    }
+
+    #[test]
+    fn test_required_fields_satisfied() {
+        let wf = create_qa_workflow();
+        let mut doc = Document::new("doc1", "form_qa", "wf_qa");
+        let _ = transition(&mut doc, &wf, &form(), "open", &actor());
+        doc.set_value("root_cause", json!("Loose bolt"));
+        doc.set_value("actions", json!(["Retorque"]));
+
+        assert!(fire(&mut doc, &wf, &form(), "close", &actor()).is_ok());
+        assert_eq!(doc.current_phase, "closed");
+    }
+
+    #[test]
+    fn test_required_fields_must_be_valid() {
+        let form = FormBuilder::new("form_qa", "QA")
+            .add_field(
+                FieldBuilder::new(
+                    "cost",
+                    "Cost",
+                    FieldType::Number {
+                        min: Some(0.0),
+                        max: None,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let wf = WorkflowBuilder::new("wf_qa", "QA Workflow")
+            .add_phase(Phase::new("open", "Open", PhaseType::Start))
+            .add_phase(Phase::new("closed", "Closed", PhaseType::End))
+            .add_transition(
+                Transition::new("close", "open", "closed").with_required_fields(&["cost"]),
+            )
+            .build()
+            .unwrap();
+        let mut doc = Document::new("doc1", "form_qa", "wf_qa");
+        let _ = transition(&mut doc, &wf, &form, "open", &actor());
+
+        // Present, but text in a Number field
+        doc.set_value("cost", json!("lots"));
+        let res = fire(&mut doc, &wf, &form, "close", &actor());
+        assert!(matches!(
+            res.unwrap_err(),
+            WorkflowError::InvalidRequiredFields { ref errors, .. } if errors.len() == 1
+        ));
+        assert_eq!(doc.current_phase, "open");
+
+        // A number outside the field's bounds is just as invalid
+        doc.set_value("cost", json!(-5));
+        let res = transition(&mut doc, &wf, &form, "closed", &actor());
+        assert!(matches!(
+            res.unwrap_err(),
+            WorkflowError::InvalidRequiredFields { .. }
+        ));
+
+        doc.set_value("cost", json!(120.5));
+        assert!(fire(&mut doc, &wf, &form, "close", &actor()).is_ok());
+        assert_eq!(doc.current_phase, "closed");
+    }
+
+    #[test]
+    fn test_forbidden_role() {
+        let wf = WorkflowBuilder::new("wf_incident", "Incident Workflow")
+            .add_phase(Phase::new("review", "Review", PhaseType::Start))
+            .add_phase(Phase::new("approved", "Approved", PhaseType::End))
+            .add_transition(
+                Transition::new("approve", "review", "approved")
+                    .with_allowed_roles(&["qa_manager"]),
+            )
+            .build()
+            .unwrap();
+        let mut doc = Document::new("doc1", "form_incident", "wf_incident");
+        let _ = transition(&mut doc, &wf, &form(), "review", &actor());
+
+        let res = fire(&mut doc, &wf, &form(), "approve", &actor());
+        assert_eq!(
+            res.unwrap_err(),
+            WorkflowError::Forbidden {
+                transition: "approve".to_string(),
+                actor: "alice".to_string(),
+            }
+        );
+        assert_eq!(doc.current_phase, "review");
+
+        let manager = Actor::new("bob", &["qa_manager"]);
+        let taken = transition(&mut doc, &wf, &form(), "approved", &manager).unwrap();
+        assert_eq!(taken.unwrap().name, "approve");
+        assert_eq!(doc.current_phase, "approved");
+    }
+
+    #[test]
+    fn test_condition_guard() {
+        let form = FormBuilder::new("form_incident", "Incident")
+            .add_field(
+                FieldBuilder::new(
+                    "severity",
+                    "Severity",
+                    FieldType::Number {
+                        min: None,
+                        max: None,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .add_field(
+                FieldBuilder::new(
+                    "category",
+                    "Category",
+                    FieldType::Text {
+                        min_length: None,
+                        max_length: None,
+                        pattern: None,
+                    },
+                )
+                .build()
+                .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let wf = WorkflowBuilder::new("wf_incident", "Incident Workflow")
+            .add_phase(Phase::new("triage", "Triage", PhaseType::Start))
+            .add_phase(Phase::new("escalated", "Escalated", PhaseType::End))
+            .add_transition(
+                Transition::new("escalate", "triage", "escalated").with_condition(
+                    Expression::parse(r#"severity >= 4 && category == "Safety""#).unwrap(),
+                ),
+            )
+            .for_form(&form)
+            .build()
+            .unwrap();
+        let mut doc = Document::new("doc1", "form_incident", "wf_incident");
+        let _ = transition(&mut doc, &wf, &form, "triage", &actor());
+        doc.set_value("severity", json!(5));
+        doc.set_value("category", json!("Quality"));
+
+        let res = fire(&mut doc, &wf, &form, "escalate", &actor());
+        assert!(matches!(
+            res.unwrap_err(),
+            WorkflowError::ConditionNotMet { .. }
+        ));
+
+        // Data that does not match the form cannot be evaluated
+        doc.set_value("severity", json!("five"));
+        let res = fire(&mut doc, &wf, &form, "escalate", &actor());
+        assert!(matches!(
+            res.unwrap_err(),
+            WorkflowError::InvalidCondition { .. }
+        ));
+
+        doc.set_value("severity", json!(5));
+        doc.set_value("category", json!("Safety"));
+        assert!(fire(&mut doc, &wf, &form, "escalate", &actor()).is_ok());
+        assert_eq!(doc.current_phase, "escalated");
+    }
+
     #[test]
     fn test_workflow_mismatch() {
         let wf = create_simple_workflow(); // ID: wf_ticket
         let mut doc = Document::new("doc1", "doc_ticket", "other_workflow_id");
 
-        let res = transition(&mut doc, &wf, "draft");
+        let res = transition(&mut doc, &wf, &form(), "draft", &actor());
         assert!(matches!(
             res.unwrap_err(),
             WorkflowError::WorkflowMismatch { .. }
@@ -19,6 +19,17 @@ pub enum WorkflowError {
         provided_wf: String,
     },
 
+    /// Occurs when a document's form is not the form its workflow is bound to.
+    #[error("Form mismatch: Workflow '{workflow}' governs '{bound_form}' documents, not '{form}'")]
+    FormMismatch {
+        /// Workflow ID
+        workflow: String,
+        /// ID of the form the workflow is bound to
+        bound_form: String,
+        /// ID of the document's form
+        form: String,
+    },
+
     /// Occurs when a referenced phase ID does not exist within the workflow definition.
     #[error("Phase '{0}' does not exist in this workflow")]
     UnknownPhase(String),
@@ -32,6 +43,60 @@ pub enum WorkflowError {
         target: String,
     },
 
+    /// Occurs when no transition with the given name leaves the document's current phase.
+    #[error("Unknown transition: No transition named '{name}' leaves '{current}'")]
+    UnknownTransition {
+        /// Current phase
+        current: String,
+        /// Requested transition name
+        name: String,
+    },
+
+    /// Occurs when the actor does not hold any of the roles allowed to fire a transition.
+    #[error("Forbidden: '{actor}' is not allowed to fire transition '{transition}'")]
+    Forbidden {
+        /// Name of the transition that was attempted
+        transition: String,
+        /// ID of the actor that attempted it
+        actor: String,
+    },
+
+    /// Occurs when a transition's required fields are missing or empty in the document.
+    #[error("Transition '{transition}' requires fields that are missing: {fields:?}")]
+    MissingRequiredFields {
+        /// Name of the transition that was attempted
+        transition: String,
+        /// IDs of the fields that must be filled in first
+        fields: Vec<String>,
+    },
+
+    /// Occurs when a transition's required fields hold values that are not valid for the form.
+    #[error("Transition '{transition}' requires fields that are invalid: {}", .errors.join("; "))]
+    InvalidRequiredFields {
+        /// Name of the transition that was attempted
+        transition: String,
+        /// Why each invalid field failed validation
+        errors: Vec<String>,
+    },
+
+    /// Occurs when a transition's condition evaluates to false for the document data.
+    #[error("Transition '{transition}' is not allowed: condition '{condition}' is not met")]
+    ConditionNotMet {
+        /// Name of the transition that was attempted
+        transition: String,
+        /// Source text of the condition
+        condition: String,
+    },
+
+    /// Occurs when a transition's condition cannot be evaluated against the document data.
+    #[error("Transition '{transition}' has a condition that could not be evaluated: {message}")]
+    InvalidCondition {
+        /// Name of the transition that was attempted
+        transition: String,
+        /// Why the evaluation failed
+        message: String,
+    },
+
     /// Occurs when a document does not have a `current_phase` defined, which is required for workflow operations.
     #[error("Document has no current phase (is it a new document?)")]
     NoCurrentPhase,
@@ -7,12 +7,16 @@
 //! If this crate has been abandoned, please message me and we can discuss ownership transfer.
 
 #![warn(missing_docs)]
-/// Provides the core workflow engine logic, including the `transition` function.
+/// Provides renderers that turn workflow definitions into Mermaid and Graphviz DOT diagrams.
+pub mod diagram;
+/// Provides the core workflow engine logic, including the `transition` and `fire` functions.
 pub mod engine;
 /// Defines custom error types specific to workflow operations.
 pub mod error;
 
-/// Re-exports the main workflow transition function from the `engine` module.
-pub use engine::transition;
+/// Re-exports the diagram renderers from the `diagram` module.
+pub use diagram::{DiagramFormat, render, to_dot, to_mermaid};
+/// Re-exports the main workflow transition functions from the `engine` module.
+pub use engine::{fire, transition};
 /// Re-exports the `WorkflowError` enum from the `error` module.
 pub use error::WorkflowError;
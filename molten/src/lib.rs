@@ -27,3 +27,14 @@ pub use molten_document::*;
 pub use molten_service::*;
 pub use molten_storage_seaorm::*;
 pub use molten_workflow::*;
+
+// The document crate's `attachment` module is reachable through the functions above.
+pub use molten_core::attachment;
+
+/// The error types of the Molten crates, each of which has its own `error` module.
+pub mod error {
+    pub use molten_config::ConfigError;
+    pub use molten_document::DocumentValidationError;
+    pub use molten_service::ServiceError;
+    pub use molten_workflow::WorkflowError;
+}
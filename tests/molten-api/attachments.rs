@@ -0,0 +1,254 @@
+use crate::helpers::{TestApp, document, first_error, spawn_app};
+use reqwest::Method;
+use reqwest::header::{CONTENT_DISPOSITION, CONTENT_TYPE, ETAG, X_CONTENT_TYPE_OPTIONS};
+use reqwest::multipart::{Form, Part};
+use serde_json::{Value, json};
+use std::path::Path;
+
+/// The SHA-256 digest of `b"hello"`.
+const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+/// Uploads a file on behalf of alice, asserting success and returning its metadata.
+async fn upload(app: &TestApp, file_name: &str, mime_type: &str, contents: &[u8]) -> Value {
+    let part = Part::bytes(contents.to_vec())
+        .file_name(file_name.to_string())
+        .mime_str(mime_type)
+        .unwrap();
+    let response = app
+        .request_as(Method::POST, "/attachments", "alice", &[])
+        .multipart(Form::new().part("file", part))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+    response.json().await.unwrap()
+}
+
+/// Counts the files below `dir`, recursively.
+fn count_files(dir: &Path) -> usize {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            if path.is_dir() { count_files(&path) } else { 1 }
+        })
+        .sum()
+}
+
+#[tokio::test]
+async fn attachments_round_trip() {
+    let app = spawn_app().await;
+
+    let attachment = upload(&app, "report.pdf", "application/pdf", b"hello").await;
+    assert_eq!(attachment["file_name"], "report.pdf");
+    assert_eq!(attachment["mime_type"], "application/pdf");
+    assert_eq!(attachment["size"], 5);
+    assert_eq!(attachment["sha256"], HELLO_SHA256);
+    assert_eq!(attachment["uploaded_by"], "alice");
+    let id = attachment["id"].as_str().unwrap();
+
+    let metadata: Value = app
+        .request(Method::GET, &format!("/attachments/{}", id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    for key in [
+        "id",
+        "file_name",
+        "mime_type",
+        "size",
+        "sha256",
+        "uploaded_by",
+    ] {
+        assert_eq!(metadata[key], attachment[key], "{}", key);
+    }
+
+    let response = app
+        .request(Method::GET, &format!("/attachments/{}/content", id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let headers = response.headers();
+    assert_eq!(headers[CONTENT_TYPE], "application/pdf");
+    assert_eq!(headers[X_CONTENT_TYPE_OPTIONS], "nosniff");
+    assert_eq!(
+        headers[CONTENT_DISPOSITION],
+        "attachment; filename=\"report.pdf\""
+    );
+    assert_eq!(headers[ETAG], format!("\"{}\"", HELLO_SHA256).as_str());
+    assert_eq!(response.bytes().await.unwrap().as_ref(), b"hello");
+}
+
+#[tokio::test]
+async fn identical_uploads_share_their_contents() {
+    let app = spawn_app().await;
+
+    let first = upload(&app, "a.txt", "text/plain", b"hello").await;
+    let second = upload(&app, "b.txt", "text/plain", b"hello").await;
+    let other = upload(&app, "c.txt", "text/plain", b"world").await;
+
+    // Each upload is an attachment of its own, but the contents are stored once
+    assert_ne!(first["id"], second["id"]);
+    assert_eq!(first["sha256"], second["sha256"]);
+    assert_ne!(first["sha256"], other["sha256"]);
+    assert_eq!(count_files(app.attachments_dir()), 2);
+
+    for attachment in [&first, &second] {
+        let contents = app
+            .request(
+                Method::GET,
+                &format!(
+                    "/attachments/{}/content",
+                    attachment["id"].as_str().unwrap()
+                ),
+            )
+            .send()
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        assert_eq!(contents.as_ref(), b"hello");
+    }
+}
+
+#[tokio::test]
+async fn downloads_do_not_serve_unlisted_types() {
+    let app = spawn_app().await;
+
+    for (mime_type, served) in [
+        ("text/html", "application/octet-stream"),
+        ("image/svg+xml", "application/octet-stream"),
+        ("text/plain; charset=utf-8", "text/plain; charset=utf-8"),
+        ("image/png", "image/png"),
+    ] {
+        let attachment = upload(&app, "file", mime_type, b"<script>alert(1)</script>").await;
+        let response = app
+            .request(
+                Method::GET,
+                &format!(
+                    "/attachments/{}/content",
+                    attachment["id"].as_str().unwrap()
+                ),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[CONTENT_TYPE], served, "{}", mime_type);
+        assert_eq!(response.headers()[X_CONTENT_TYPE_OPTIONS], "nosniff");
+    }
+}
+
+#[tokio::test]
+async fn attachment_errors() {
+    let app = spawn_app().await;
+
+    // Uploads need an actor and a `file` part
+    let response = app
+        .request(Method::POST, "/attachments")
+        .multipart(Form::new().part("file", Part::bytes(b"hello".to_vec())))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+    let response = app
+        .request_as(Method::POST, "/attachments", "alice", &[])
+        .multipart(Form::new().part("other", Part::bytes(b"hello".to_vec())))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    for path in ["/attachments/missing", "/attachments/missing/content"] {
+        let response = app.request(Method::GET, path).send().await.unwrap();
+        assert_eq!(response.status(), 404, "{}", path);
+    }
+}
+
+#[tokio::test]
+async fn documents_reject_attachments_that_do_not_fit_their_field() {
+    let app = spawn_app().await;
+    app.create_form(json!({
+        "id": "inspection",
+        "name": "Inspection",
+        "fields": [{
+            "id": "photo",
+            "label": "Photo",
+            "field_type": {
+                "kind": "attachment",
+                "config": { "max_size": 8, "allowed_mime_types": ["image/*"] }
+            }
+        }]
+    }))
+    .await;
+    app.create_workflow(
+        "inspection",
+        json!({
+            "id": "wf_inspection",
+            "name": "Inspection Flow",
+            "phases": [
+                { "id": "open", "label": "Open", "type": "start" },
+                { "id": "done", "label": "Done", "type": "end" }
+            ],
+            "transitions": [{ "name": "finish", "from": "open", "to": "done" }]
+        }),
+    )
+    .await;
+    let create = |photo: Value| {
+        app.post_document(
+            "alice",
+            document("inspection", "wf_inspection", json!({ "photo": photo })),
+        )
+    };
+
+    let large = upload(&app, "large.png", "image/png", b"far too large").await;
+    let response = create(large["id"].clone()).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(
+        body["details"],
+        json!([{
+            "AttachmentTooLarge": {
+                "field_id": "photo", "attachment_id": large["id"], "size": 13, "max_size": 8
+            }
+        }])
+    );
+
+    let pdf = upload(&app, "small.pdf", "application/pdf", b"hello").await;
+    let response = create(pdf["id"].clone()).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(
+        body["details"],
+        json!([{
+            "AttachmentTypeNotAllowed": {
+                "field_id": "photo", "attachment_id": pdf["id"],
+                "mime_type": "application/pdf", "allowed": ["image/*"]
+            }
+        }])
+    );
+
+    let error = first_error(create(json!("missing")).await).await;
+    assert_eq!(error["AttachmentNotFound"]["attachment_id"], "missing");
+
+    let photo = upload(&app, "small.png", "image/png", b"hello").await;
+    let response = create(photo["id"].clone()).await;
+    assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+    let doc: Value = response.json().await.unwrap();
+
+    // The same checks apply when the document is updated
+    let response = app
+        .request(
+            Method::PATCH,
+            &format!("/documents/{}", doc["id"].as_str().unwrap()),
+        )
+        .json(&json!({ "photo": large["id"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+}
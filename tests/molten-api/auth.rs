@@ -0,0 +1,56 @@
+use crate::helpers::{PROXY_SECRET, spawn_app};
+use molten_api::auth::{ACTOR_HEADER, PROXY_SECRET_HEADER, ROLES_HEADER};
+use reqwest::Method;
+
+#[tokio::test]
+async fn trusted_actor_headers_are_accepted() {
+    let app = spawn_app().await;
+
+    let response = app
+        .request_as(Method::GET, "/me/queue", "alice", &["admin"])
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn forged_actor_headers_are_rejected() {
+    let app = spawn_app().await;
+
+    // No proxy secret at all
+    let response = app
+        .request(Method::GET, "/me/queue")
+        .header(ACTOR_HEADER, "mallory")
+        .header(ROLES_HEADER, "admin")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    // A guessed proxy secret
+    let response = app
+        .request(Method::GET, "/me/queue")
+        .header(PROXY_SECRET_HEADER, format!("{}x", PROXY_SECRET))
+        .header(ACTOR_HEADER, "mallory")
+        .header(ROLES_HEADER, "admin")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn missing_actor_header_is_rejected() {
+    let app = spawn_app().await;
+
+    let response = app
+        .request(Method::GET, "/me/queue")
+        .header(PROXY_SECRET_HEADER, PROXY_SECRET)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 401);
+}
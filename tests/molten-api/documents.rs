@@ -0,0 +1,225 @@
+use crate::helpers::{create_ticket, new_ticket, setup_ticket, spawn_app};
+use molten_api::auth::ACTOR_HEADER;
+use reqwest::Method;
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MATCH};
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn create_document_without_actor_falls_back_to_system() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+
+    let response = app
+        .request(Method::POST, "/documents")
+        .json(&new_ticket())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let doc: Value = response.json().await.unwrap();
+
+    let history: Value = app
+        .request(
+            Method::GET,
+            &format!("/documents/{}/history", doc["id"].as_str().unwrap()),
+        )
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(history[0]["actor"], "system");
+}
+
+#[tokio::test]
+async fn create_document_records_the_actor() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+
+    let response = app.post_document("alice", new_ticket()).await;
+    assert_eq!(response.status(), 200);
+    let doc: Value = response.json().await.unwrap();
+
+    let history: Value = app
+        .request(
+            Method::GET,
+            &format!("/documents/{}/history", doc["id"].as_str().unwrap()),
+        )
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(history[0]["actor"], "alice");
+}
+
+#[tokio::test]
+async fn create_document_rejects_forged_actor() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+
+    let response = app
+        .request(Method::POST, "/documents")
+        .header(ACTOR_HEADER, "mallory")
+        .json(&new_ticket())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn patch_document_applies_a_raw_merge_patch() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    let doc = create_ticket(&app, json!({ "title": "Leak", "notes": "Valve 3" })).await;
+
+    let response = app
+        .request(
+            Method::PATCH,
+            &format!("/documents/{}", doc["id"].as_str().unwrap()),
+        )
+        .header(CONTENT_TYPE, "application/merge-patch+json")
+        .body(json!({ "title": "Major leak", "notes": null }).to_string())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let patched: Value = response.json().await.unwrap();
+    assert_eq!(patched["data"], json!({ "title": "Major leak" }));
+}
+
+#[tokio::test]
+async fn patch_document_rejects_non_object_patches() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    let doc = create_ticket(&app, json!({ "title": "Leak" })).await;
+
+    let response = app
+        .request(
+            Method::PATCH,
+            &format!("/documents/{}", doc["id"].as_str().unwrap()),
+        )
+        .header(CONTENT_TYPE, "application/merge-patch+json")
+        .body("[\"title\"]")
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_client_error());
+}
+
+#[tokio::test]
+async fn update_document_honours_if_match() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    let doc = create_ticket(&app, json!({ "title": "Leak" })).await;
+    let path = format!("/documents/{}", doc["id"].as_str().unwrap());
+
+    let response = app.request(Method::GET, &path).send().await.unwrap();
+    let etag = response.headers()[ETAG].to_str().unwrap().to_string();
+    assert_eq!(etag, "\"1\"");
+
+    // A matching precondition succeeds and returns the new ETag
+    let response = app
+        .request(Method::PUT, &path)
+        .header(IF_MATCH, &etag)
+        .json(&json!({ "data": { "title": "Major leak" } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()[ETAG], "\"2\"");
+
+    // A stale one fails, for both PUT and PATCH
+    let response = app
+        .request(Method::PUT, &path)
+        .header(IF_MATCH, &etag)
+        .json(&json!({ "data": { "title": "Minor leak" } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 412);
+    let response = app
+        .request(Method::PATCH, &path)
+        .header(IF_MATCH, &etag)
+        .json(&json!({ "title": "Minor leak" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 412);
+
+    // Weak tags never match, even at the current version
+    let response = app
+        .request(Method::PUT, &path)
+        .header(IF_MATCH, "W/\"2\"")
+        .json(&json!({ "data": { "title": "Minor leak" } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 412);
+
+    // Nor do well-formed tags that are no document version
+    let response = app
+        .request(Method::PUT, &path)
+        .header(IF_MATCH, "\"abc\"")
+        .json(&json!({ "data": { "title": "Minor leak" } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 412);
+
+    // A list matches if any of its strong tags does
+    let response = app
+        .request(Method::PUT, &path)
+        .header(IF_MATCH, "\"1\", W/\"2\"")
+        .json(&json!({ "data": { "title": "Minor leak" } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 412);
+    let response = app
+        .request(Method::PATCH, &path)
+        .header(IF_MATCH, "\"1\", \"2\"")
+        .json(&json!({ "title": "Minor leak" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()[ETAG], "\"3\"");
+
+    // A malformed one is rejected
+    let response = app
+        .request(Method::PUT, &path)
+        .header(IF_MATCH, "version-2")
+        .json(&json!({ "data": { "title": "Minor leak" } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn transitions_return_the_new_etag() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    let doc = create_ticket(&app, json!({ "title": "Leak" })).await;
+
+    let response = app
+        .request_as(
+            Method::POST,
+            &format!(
+                "/documents/{}/transitions/close",
+                doc["id"].as_str().unwrap()
+            ),
+            "alice",
+            &[],
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()[ETAG], "\"2\"");
+}
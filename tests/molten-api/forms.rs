@@ -0,0 +1,167 @@
+use crate::helpers::{create_ticket, setup_ticket, spawn_app};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+/// The ticket form with an extra field, as a new version.
+fn ticket_v2(extra: Value) -> Value {
+    json!({
+        "id": "ticket",
+        "name": "Ticket",
+        "fields": [
+            { "id": "title", "label": "Title", "field_type": { "kind": "text" } },
+            { "id": "notes", "label": "Notes", "field_type": { "kind": "text" } },
+            extra
+        ]
+    })
+}
+
+#[tokio::test]
+async fn publishing_a_version_keeps_the_previous_ones() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+
+    let response = app
+        .request(Method::POST, "/forms/ticket/versions")
+        .json(&ticket_v2(
+            json!({ "id": "site", "label": "Site", "field_type": { "kind": "text" } }),
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let published: Value = response.json().await.unwrap();
+    assert_eq!(published["version"], 2);
+
+    let versions: Value = app
+        .request(Method::GET, "/forms/ticket/versions")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(versions.as_array().unwrap().len(), 2);
+
+    let v1: Value = app
+        .request(Method::GET, "/forms/ticket/versions/1")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(v1["fields"].as_array().unwrap().len(), 2);
+
+    let latest: Value = app
+        .request(Method::GET, "/forms/ticket")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(latest["version"], 2);
+}
+
+#[tokio::test]
+async fn documents_stay_on_the_version_they_were_created_with() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    let doc = create_ticket(&app, json!({ "title": "Leak" })).await;
+    assert_eq!(doc["form_version"], 1);
+
+    let required_site = json!({
+        "id": "site", "label": "Site", "required": true, "field_type": { "kind": "text" }
+    });
+    let response = app
+        .request(Method::POST, "/forms/ticket/versions?force=true")
+        .json(&ticket_v2(required_site))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // The document is still validated against version 1, which has no `site`
+    let response = app
+        .request(
+            Method::PATCH,
+            &format!("/documents/{}", doc["id"].as_str().unwrap()),
+        )
+        .json(&json!({ "notes": "Valve 3" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // New documents are created with version 2
+    let response = app
+        .post_document(
+            "alice",
+            json!({ "form_id": "ticket", "workflow_id": "wf_ticket", "data": { "title": "Leak" } }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn breaking_versions_need_to_be_forced() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    let required_site = json!({
+        "id": "site", "label": "Site", "required": true, "field_type": { "kind": "text" }
+    });
+
+    let response = app
+        .request(Method::POST, "/forms/ticket/compatibility")
+        .json(&ticket_v2(required_site.clone()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let report: Value = response.json().await.unwrap();
+    assert!(!report["changes"].as_array().unwrap().is_empty());
+
+    let response = app
+        .request(Method::POST, "/forms/ticket/versions")
+        .json(&ticket_v2(required_site))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+}
+
+#[tokio::test]
+async fn form_errors() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+
+    // Forms are created once, then versioned
+    let response = app
+        .request(Method::POST, "/forms")
+        .json(&ticket_v2(
+            json!({ "id": "site", "label": "Site", "field_type": { "kind": "text" } }),
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    for path in [
+        "/forms/missing",
+        "/forms/ticket/versions/7",
+        "/forms/missing/versions",
+    ] {
+        let response = app.request(Method::GET, path).send().await.unwrap();
+        assert_eq!(response.status(), 404, "{}", path);
+    }
+
+    let response = app
+        .request(Method::POST, "/forms/missing/versions")
+        .json(&ticket_v2(
+            json!({ "id": "site", "label": "Site", "field_type": { "kind": "text" } }),
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
@@ -1,2 +1,230 @@
 // Helper to start the app connected to a test DB
+use molten_api::auth::{ACTOR_HEADER, PROXY_SECRET_HEADER, ROLES_HEADER};
+use molten_api::startup::Application;
+use molten_config::settings_parser::{Settings, get_configuration_from};
+use molten_migration::{Migrator, MigratorTrait};
+use molten_storage_seaorm::sea_orm::{ConnectionTrait, Database};
+use secrecy::SecretString;
+use serde_json::{Value, json};
+use std::path::Path;
+use tempfile::TempDir;
+use uuid::Uuid;
 
+/// The proxy secret every test app is configured with.
+pub const PROXY_SECRET: &str = "test-proxy-secret";
+
+/// A running instance of the API, backed by its own freshly migrated database.
+pub struct TestApp {
+    pub address: String,
+    pub client: reqwest::Client,
+    // Kept alive for as long as the app runs
+    attachments: TempDir,
+}
+
+impl TestApp {
+    /// Starts a request to `path`, without any authentication headers.
+    pub fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.address, path))
+    }
+
+    /// Starts a request to `path` on behalf of `actor`, as the trusted proxy would.
+    pub fn request_as(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        actor: &str,
+        roles: &[&str],
+    ) -> reqwest::RequestBuilder {
+        self.request(method, path)
+            .header(PROXY_SECRET_HEADER, PROXY_SECRET)
+            .header(ACTOR_HEADER, actor)
+            .header(ROLES_HEADER, roles.join(","))
+    }
+
+    /// The directory the app stores attachment contents in.
+    pub fn attachments_dir(&self) -> &Path {
+        self.attachments.path()
+    }
+
+    /// Creates a form from its JSON definition, asserting success.
+    pub async fn create_form(&self, form: serde_json::Value) {
+        let response = self
+            .request(reqwest::Method::POST, "/forms")
+            .json(&form)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+    }
+
+    /// Creates a workflow bound to `form_id` from its JSON definition, returning the response.
+    pub async fn post_workflow(
+        &self,
+        form_id: &str,
+        mut workflow: serde_json::Value,
+    ) -> reqwest::Response {
+        workflow["form_id"] = serde_json::Value::from(form_id);
+        self.request(reqwest::Method::POST, "/workflows")
+            .json(&workflow)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    /// Creates a workflow bound to `form_id` from its JSON definition, asserting success.
+    pub async fn create_workflow(&self, form_id: &str, workflow: serde_json::Value) {
+        let response = self.post_workflow(form_id, workflow).await;
+        assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+    }
+
+    /// Creates a document on behalf of `actor`, returning the response.
+    pub async fn post_document(&self, actor: &str, body: serde_json::Value) -> reqwest::Response {
+        self.request_as(reqwest::Method::POST, "/documents", actor, &[])
+            .json(&body)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    /// Creates a document on behalf of alice, asserting success, and returns it.
+    pub async fn create_document(&self, form_id: &str, workflow_id: &str, data: Value) -> Value {
+        let response = self
+            .post_document("alice", document(form_id, workflow_id, data))
+            .await;
+        assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+        response.json().await.unwrap()
+    }
+
+    /// Fires a transition on a document on behalf of alice, asserting success.
+    pub async fn fire(&self, document_id: &str, transition: &str) {
+        let path = format!("/documents/{}/transitions/{}", document_id, transition);
+        let response = self
+            .request_as(reqwest::Method::POST, &path, "alice", &[])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+    }
+
+    /// Fetches a JSON resource, asserting success.
+    pub async fn get_json(&self, path: &str) -> Value {
+        let response = self
+            .request(reqwest::Method::GET, path)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+        response.json().await.unwrap()
+    }
+}
+
+/// The request body creating a document of `form_id`, governed by `workflow_id`.
+pub fn document(form_id: &str, workflow_id: &str, data: Value) -> Value {
+    json!({ "form_id": form_id, "workflow_id": workflow_id, "data": data })
+}
+
+/// Returns the first validation error of a rejected document.
+pub async fn first_error(response: reqwest::Response) -> Value {
+    assert_eq!(response.status(), 400);
+    let body: Value = response.json().await.unwrap();
+    body["details"][0].clone()
+}
+
+/// Loads the settings from the workspace's `config` directory.
+fn configuration() -> Settings {
+    let config_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../config");
+    get_configuration_from(&config_dir).expect("Failed to read configuration")
+}
+
+/// Creates a new database with a random name and runs every migration against it.
+async fn configure_database(config: &mut Settings) {
+    let database_name = Uuid::new_v4().to_string();
+
+    let mut server = config.database.clone();
+    server.database_name = "postgres".to_string();
+    let connection = Database::connect(server.get_connect_options())
+        .await
+        .expect("Failed to connect to Postgres");
+    connection
+        .execute_unprepared(&format!(r#"CREATE DATABASE "{}""#, database_name))
+        .await
+        .expect("Failed to create test database");
+
+    config.database.database_name = database_name;
+    let url = config.database.get_connect_options().get_url().to_string();
+    let db = molten_migration::sea_orm::Database::connect(url)
+        .await
+        .expect("Failed to connect to test database");
+    Migrator::up(&db, None)
+        .await
+        .expect("Failed to migrate test database");
+}
+
+/// Starts the API on a random port, backed by a new database and attachment directory.
+pub async fn spawn_app() -> TestApp {
+    let attachments = TempDir::new().unwrap();
+
+    let mut config = configuration();
+    config.application.port = 0;
+    config.attachments.path = attachments.path().to_str().unwrap().to_string();
+    config.auth.proxy_secret = Some(SecretString::from(PROXY_SECRET));
+    configure_database(&mut config).await;
+
+    let app = Application::build(config)
+        .await
+        .expect("Failed to build application");
+    let address = format!("http://127.0.0.1:{}", app.port());
+    tokio::spawn(app.run());
+
+    TestApp {
+        address,
+        client: reqwest::Client::new(),
+        attachments,
+    }
+}
+
+/// Creates the `ticket` form and its `wf_ticket` workflow.
+pub async fn setup_ticket(app: &TestApp) {
+    app.create_form(json!({
+        "id": "ticket",
+        "name": "Ticket",
+        "fields": [
+            { "id": "title", "label": "Title", "field_type": { "kind": "text" } },
+            { "id": "notes", "label": "Notes", "field_type": { "kind": "text" } }
+        ]
+    }))
+    .await;
+    app.create_workflow(
+        "ticket",
+        json!({
+            "id": "wf_ticket",
+            "name": "Ticket Flow",
+            "phases": [
+                { "id": "open", "label": "Open", "type": "start" },
+                { "id": "closed", "label": "Closed", "type": "end" }
+            ],
+            "transitions": [
+                { "name": "close", "from": "open", "to": "closed" }
+            ]
+        }),
+    )
+    .await;
+}
+
+/// The request body creating a ticket titled "Leak".
+pub fn new_ticket() -> Value {
+    json!({ "form_id": "ticket", "workflow_id": "wf_ticket", "data": { "title": "Leak" } })
+}
+
+/// Creates a ticket on behalf of alice, returning it.
+pub async fn create_ticket(app: &TestApp, data: Value) -> Value {
+    let response = app
+        .post_document(
+            "alice",
+            json!({ "form_id": "ticket", "workflow_id": "wf_ticket", "data": data }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
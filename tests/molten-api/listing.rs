@@ -0,0 +1,351 @@
+use crate::helpers::{TestApp, create_ticket, setup_ticket, spawn_app};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+/// Fetches one page of the document listing, asserting success.
+async fn list(app: &TestApp, query: &str) -> Value {
+    let response = app
+        .request(Method::GET, &format!("/documents?{}", query))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+    response.json().await.unwrap()
+}
+
+fn ids(page: &Value) -> Vec<String> {
+    page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|doc| doc["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn list_documents_pages_with_cursors() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    let mut created = Vec::new();
+    for i in 0..5 {
+        let doc = create_ticket(&app, json!({ "title": format!("Ticket {}", i) })).await;
+        created.push(doc["id"].as_str().unwrap().to_string());
+    }
+
+    let first = list(&app, "limit=2&sort=created_at").await;
+    assert_eq!(ids(&first), created[0..2]);
+    let cursor = first["next_cursor"].as_str().unwrap();
+
+    // Documents created while paging do not shift the following pages
+    create_ticket(&app, json!({ "title": "Late" })).await;
+
+    let second = list(&app, &format!("limit=2&sort=created_at&cursor={}", cursor)).await;
+    assert_eq!(ids(&second), created[2..4]);
+
+    let newest_first = list(&app, "limit=200").await;
+    assert_eq!(ids(&newest_first).len(), 6);
+    assert!(newest_first["next_cursor"].is_null());
+    assert_eq!(ids(&newest_first)[5], created[0]);
+}
+
+#[tokio::test]
+async fn list_documents_filters_by_phase() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    let open = create_ticket(&app, json!({ "title": "Open" })).await;
+    let closed = create_ticket(&app, json!({ "title": "Closed" })).await;
+    let response = app
+        .request_as(
+            Method::POST,
+            &format!(
+                "/documents/{}/transitions/close",
+                closed["id"].as_str().unwrap()
+            ),
+            "alice",
+            &[],
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let page = list(&app, "current_phase=open").await;
+    assert_eq!(ids(&page), vec![open["id"].as_str().unwrap()]);
+}
+
+#[tokio::test]
+async fn list_documents_rejects_invalid_paging() {
+    let app = spawn_app().await;
+
+    for query in ["limit=0", "limit=201", "sort=title", "cursor=not-a-cursor"] {
+        let response = app
+            .request(Method::GET, &format!("/documents?{}", query))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400, "{}", query);
+    }
+}
+
+#[tokio::test]
+async fn list_documents_rejects_cursor_of_another_sort() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    for _ in 0..2 {
+        create_ticket(&app, json!({ "title": "Leak" })).await;
+    }
+
+    let first = list(&app, "limit=1&sort=created_at").await;
+    let cursor = first["next_cursor"].as_str().unwrap();
+
+    let response = app
+        .request(
+            Method::GET,
+            &format!("/documents?limit=1&sort=updated_at&cursor={}", cursor),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+}
+
+/// Creates the `inspection` form with text fields, then publishes a second version
+/// typing them as a date and a decimal, leaving documents with free text behind.
+async fn setup_retyped_inspection(app: &TestApp) -> (String, String) {
+    let form = |due: Value, cost: Value| {
+        json!({
+            "id": "inspection",
+            "name": "Inspection",
+            "fields": [
+                { "id": "due", "label": "Due", "field_type": due },
+                { "id": "cost", "label": "Cost", "field_type": cost }
+            ]
+        })
+    };
+    let text = json!({ "kind": "text" });
+    app.create_form(form(text.clone(), text)).await;
+    app.create_workflow(
+        "inspection",
+        json!({
+            "id": "wf_inspection",
+            "name": "Inspection Flow",
+            "phases": [
+                { "id": "open", "label": "Open", "type": "start" },
+                { "id": "done", "label": "Done", "type": "end" }
+            ],
+            "transitions": [{ "name": "finish", "from": "open", "to": "done" }]
+        }),
+    )
+    .await;
+
+    let create = |data: Value| async move {
+        let response = app
+            .post_document(
+                "alice",
+                json!({ "form_id": "inspection", "workflow_id": "wf_inspection", "data": data }),
+            )
+            .await;
+        assert_eq!(response.status(), 200);
+        let doc: Value = response.json().await.unwrap();
+        doc["id"].as_str().unwrap().to_string()
+    };
+
+    let legacy = create(json!({ "due": "next week", "cost": "a few hundred" })).await;
+    // Shaped like a date, but not one
+    create(json!({ "due": "2024-02-30", "cost": "1.2.3" })).await;
+
+    let response = app
+        .request(Method::POST, "/forms/inspection/versions?force=true")
+        .json(&form(
+            json!({ "kind": "date", "config": {} }),
+            json!({ "kind": "decimal", "config": { "precision": 10, "scale": 2 } }),
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+
+    let typed = create(json!({ "due": "2024-06-01", "cost": "250.00" })).await;
+    (legacy, typed)
+}
+
+#[tokio::test]
+async fn list_documents_filters_skip_values_of_another_type() {
+    let app = spawn_app().await;
+    let (_, typed) = setup_retyped_inspection(&app).await;
+
+    for filter in ["due:gte:2024-01-01", "cost:lt:1000"] {
+        let page = list(&app, &format!("form_id=inspection&filter={}", filter)).await;
+        assert_eq!(ids(&page), vec![typed.clone()], "{}", filter);
+    }
+}
+
+/// Creates a form with a single `severity` field typed as `from` and a document for each
+/// of the `legacy` values, then publishes a second version typing the field as `to`.
+/// Returns the ID of a document created afterwards with the severity `typed`.
+async fn setup_retyped_severity(
+    app: &TestApp,
+    form_id: &str,
+    from: Value,
+    legacy: &[Value],
+    to: Value,
+    typed: Value,
+) -> String {
+    let form = |field_type: Value| {
+        json!({
+            "id": form_id,
+            "name": "Incident",
+            "fields": [{ "id": "severity", "label": "Severity", "field_type": field_type }]
+        })
+    };
+    let workflow_id = format!("wf_{}", form_id);
+    app.create_form(form(from)).await;
+    app.create_workflow(
+        form_id,
+        json!({
+            "id": workflow_id,
+            "name": "Incident Flow",
+            "phases": [
+                { "id": "open", "label": "Open", "type": "start" },
+                { "id": "done", "label": "Done", "type": "end" }
+            ],
+            "transitions": [{ "name": "finish", "from": "open", "to": "done" }]
+        }),
+    )
+    .await;
+
+    let create = |severity: Value| {
+        let body = json!({
+            "form_id": form_id,
+            "workflow_id": workflow_id,
+            "data": { "severity": severity }
+        });
+        async move {
+            let response = app.post_document("alice", body).await;
+            assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+            let doc: Value = response.json().await.unwrap();
+            doc["id"].as_str().unwrap().to_string()
+        }
+    };
+
+    for value in legacy {
+        create(value.clone()).await;
+    }
+
+    let response = app
+        .request(
+            Method::POST,
+            &format!("/forms/{}/versions?force=true", form_id),
+        )
+        .json(&form(to))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+
+    create(typed).await
+}
+
+#[tokio::test]
+async fn list_documents_number_filters_skip_values_of_another_type() {
+    let app = spawn_app().await;
+    let typed = setup_retyped_severity(
+        &app,
+        "incident",
+        json!({ "kind": "text" }),
+        &[json!("high"), json!("2")],
+        json!({ "kind": "number", "config": {} }),
+        json!(3),
+    )
+    .await;
+
+    // jsonb sorts strings below numbers, so "high" must not pass for a low severity
+    for filter in [
+        "severity:lt:4",
+        "severity:lte:3",
+        "severity:gt:0",
+        "severity:gte:3",
+    ] {
+        let page = list(&app, &format!("form_id=incident&filter={}", filter)).await;
+        assert_eq!(ids(&page), vec![typed.clone()], "{}", filter);
+    }
+}
+
+#[tokio::test]
+async fn list_documents_integer_filters_skip_values_of_another_type() {
+    let app = spawn_app().await;
+    let from_text = setup_retyped_severity(
+        &app,
+        "incident",
+        json!({ "kind": "text" }),
+        &[json!("high"), json!("2")],
+        json!({ "kind": "integer", "config": {} }),
+        json!(3),
+    )
+    .await;
+    let from_boolean = setup_retyped_severity(
+        &app,
+        "near_miss",
+        json!({ "kind": "boolean" }),
+        &[json!(true), json!(false)],
+        json!({ "kind": "integer", "config": {} }),
+        json!(3),
+    )
+    .await;
+
+    // Integers compare numerically: 3 < 10, although "3" > "10" as text
+    for filter in ["severity:lt:10", "severity:gte:3", "severity:gt:-1"] {
+        let page = list(&app, &format!("form_id=incident&filter={}", filter)).await;
+        assert_eq!(ids(&page), vec![from_text.clone()], "{}", filter);
+
+        // jsonb sorts booleans above numbers, so they must not pass either
+        let page = list(&app, &format!("form_id=near_miss&filter={}", filter)).await;
+        assert_eq!(ids(&page), vec![from_boolean.clone()], "{}", filter);
+    }
+    for filter in ["severity:gt:3", "severity:lt:3"] {
+        for form_id in ["incident", "near_miss"] {
+            let page = list(&app, &format!("form_id={}&filter={}", form_id, filter)).await;
+            assert!(ids(&page).is_empty(), "{} {}", form_id, filter);
+        }
+    }
+}
+
+#[tokio::test]
+async fn list_documents_ne_filter_keeps_values_of_another_type() {
+    let app = spawn_app().await;
+    let (legacy, _) = setup_retyped_inspection(&app).await;
+
+    let page = list(&app, "form_id=inspection&filter=due:ne:2024-06-01").await;
+    let found = ids(&page);
+    assert_eq!(found.len(), 2);
+    assert!(found.contains(&legacy));
+}
+
+#[tokio::test]
+async fn list_documents_rejects_invalid_filters() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+
+    for query in [
+        // Filters need a form to type them
+        "filter=title:eq:Leak",
+        "form_id=ticket&filter=title",
+        "form_id=ticket&filter=title:between:a",
+        "form_id=ticket&filter=severity:eq:1",
+        "form_id=ticket&filter=title:gte:a",
+    ] {
+        let response = app
+            .request(Method::GET, &format!("/documents?{}", query))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400, "{}", query);
+    }
+
+    let response = app
+        .request(Method::GET, "/documents?form_id=missing&filter=title:eq:a")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
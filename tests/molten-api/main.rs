@@ -1 +1,10 @@
-mod helpers;
\ No newline at end of file
+mod attachments;
+mod auth;
+mod documents;
+mod forms;
+mod helpers;
+mod listing;
+mod queue;
+mod references;
+mod revalidations;
+mod workflows;
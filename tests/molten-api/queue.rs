@@ -0,0 +1,123 @@
+use crate::helpers::{TestApp, spawn_app};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+/// Creates the `task` form and its `wf_task` workflow, whose `open` phase is assigned to
+/// the task's `owner` and whose `review` phase is assigned to its `reviewers`.
+async fn setup_task(app: &TestApp) {
+    app.create_form(json!({
+        "id": "task",
+        "name": "Task",
+        "fields": [
+            { "id": "title", "label": "Title", "field_type": { "kind": "text" } },
+            { "id": "owner", "label": "Owner", "field_type": { "kind": "user", "config": {} } },
+            {
+                "id": "reviewers", "label": "Reviewers",
+                "field_type": { "kind": "user", "config": { "allow_multiple": true } }
+            }
+        ]
+    }))
+    .await;
+    app.create_workflow(
+        "task",
+        json!({
+            "id": "wf_task",
+            "name": "Task Flow",
+            "phases": [
+                { "id": "open", "label": "Open", "type": "start", "assignee": "owner" },
+                { "id": "review", "label": "Review", "type": "normal", "assignee": "reviewers" },
+                { "id": "done", "label": "Done", "type": "end" }
+            ],
+            "transitions": [
+                { "name": "submit", "from": "open", "to": "review" },
+                { "name": "approve", "from": "review", "to": "done" }
+            ]
+        }),
+    )
+    .await;
+}
+
+/// Creates a task on behalf of alice, returning its ID.
+async fn create_task(app: &TestApp, data: Value) -> String {
+    let task = app.create_document("task", "wf_task", data).await;
+    task["id"].as_str().unwrap().to_string()
+}
+
+/// Returns the IDs of the documents in a user's work queue, in queue order.
+async fn queue(app: &TestApp, user: &str) -> Vec<String> {
+    let response = app
+        .request_as(Method::GET, "/me/queue", user, &[])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let docs: Value = response.json().await.unwrap();
+    docs.as_array()
+        .unwrap()
+        .iter()
+        .map(|doc| doc["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn documents_follow_their_assignees_through_the_workflow() {
+    let app = spawn_app().await;
+    setup_task(&app).await;
+    let task = create_task(
+        &app,
+        json!({ "title": "Fix valve", "owner": "bob", "reviewers": ["carol", "dave"] }),
+    )
+    .await;
+
+    // The start phase is assigned to the owner only
+    assert_eq!(queue(&app, "bob").await, vec![task.clone()]);
+    assert!(queue(&app, "carol").await.is_empty());
+    assert!(queue(&app, "alice").await.is_empty());
+
+    // Changing the owner moves the task to the new owner's queue
+    let response = app
+        .request(Method::PUT, &format!("/documents/{}", task))
+        .json(&json!({
+            "data": { "title": "Fix valve", "owner": "erin", "reviewers": ["carol", "dave"] }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+    assert!(queue(&app, "bob").await.is_empty());
+    assert_eq!(queue(&app, "erin").await, vec![task.clone()]);
+
+    // Entering review, the task leaves the owner's queue for every reviewer's
+    app.fire(&task, "submit").await;
+    assert!(queue(&app, "erin").await.is_empty());
+    assert_eq!(queue(&app, "carol").await, vec![task.clone()]);
+    assert_eq!(queue(&app, "dave").await, vec![task.clone()]);
+
+    // The end phase has no assignee
+    app.fire(&task, "approve").await;
+    assert!(queue(&app, "carol").await.is_empty());
+    assert!(queue(&app, "dave").await.is_empty());
+}
+
+#[tokio::test]
+async fn queue_lists_the_least_recently_updated_first() {
+    let app = spawn_app().await;
+    setup_task(&app).await;
+    let first = create_task(&app, json!({ "title": "First", "owner": "bob" })).await;
+    let second = create_task(&app, json!({ "title": "Second", "owner": "bob" })).await;
+    create_task(&app, json!({ "title": "Unassigned" })).await;
+
+    assert_eq!(
+        queue(&app, "bob").await,
+        vec![first.clone(), second.clone()]
+    );
+
+    let response = app
+        .request(Method::PATCH, &format!("/documents/{}", first))
+        .json(&json!({ "title": "First, revised" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(queue(&app, "bob").await, vec![second, first]);
+}
@@ -0,0 +1,195 @@
+use crate::helpers::{TestApp, create_ticket, document, first_error, setup_ticket, spawn_app};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+/// Creates the `action` form, whose `ticket` field references a ticket and whose
+/// `related` field references any number of them, and its `wf_action` workflow.
+async fn setup_action(app: &TestApp) {
+    app.create_form(json!({
+        "id": "action",
+        "name": "Corrective Action",
+        "fields": [
+            { "id": "summary", "label": "Summary", "field_type": { "kind": "text" } },
+            {
+                "id": "ticket", "label": "Ticket",
+                "field_type": { "kind": "reference", "config": { "form_id": "ticket" } }
+            },
+            {
+                "id": "related", "label": "Related",
+                "field_type": {
+                    "kind": "reference",
+                    "config": { "form_id": "ticket", "allow_multiple": true }
+                }
+            }
+        ]
+    }))
+    .await;
+    app.create_workflow(
+        "action",
+        json!({
+            "id": "wf_action",
+            "name": "Action Flow",
+            "phases": [
+                { "id": "open", "label": "Open", "type": "start" },
+                { "id": "done", "label": "Done", "type": "end" }
+            ],
+            "transitions": [{ "name": "finish", "from": "open", "to": "done" }]
+        }),
+    )
+    .await;
+}
+
+/// Creates an action with the given data, returning the response.
+async fn post_action(app: &TestApp, data: Value) -> reqwest::Response {
+    app.post_document("alice", document("action", "wf_action", data))
+        .await
+}
+
+#[tokio::test]
+async fn references_are_listed_both_ways() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    setup_action(&app).await;
+    let leak = create_ticket(&app, json!({ "title": "Leak" })).await;
+    let crack = create_ticket(&app, json!({ "title": "Crack" })).await;
+    let (leak_id, crack_id) = (leak["id"].as_str().unwrap(), crack["id"].as_str().unwrap());
+
+    let action = app
+        .create_document(
+            "action",
+            "wf_action",
+            json!({ "summary": "Replace valve", "ticket": leak_id, "related": [crack_id] }),
+        )
+        .await;
+    let action_id = action["id"].as_str().unwrap();
+
+    let references = app
+        .get_json(&format!("/documents/{}/references", action_id))
+        .await;
+    let mut references = references.as_array().unwrap().clone();
+    references.sort_by_key(|r| r["field_id"].as_str().unwrap().to_string());
+    assert_eq!(
+        references,
+        vec![
+            json!({
+                "document_id": action_id, "field_id": "related",
+                "target_id": crack_id, "target_form_id": "ticket"
+            }),
+            json!({
+                "document_id": action_id, "field_id": "ticket",
+                "target_id": leak_id, "target_form_id": "ticket"
+            }),
+        ]
+    );
+
+    let referenced_by = app
+        .get_json(&format!("/documents/{}/referenced-by", leak_id))
+        .await;
+    assert_eq!(referenced_by.as_array().unwrap().len(), 1);
+    assert_eq!(referenced_by[0]["document_id"], action_id);
+    assert_eq!(referenced_by[0]["field_id"], "ticket");
+
+    // Replacing the references replaces what is listed
+    let response = app
+        .request(Method::PUT, &format!("/documents/{}", action_id))
+        .json(&json!({ "data": { "summary": "Replace valve", "ticket": crack_id } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+
+    let referenced_by = app
+        .get_json(&format!("/documents/{}/referenced-by", leak_id))
+        .await;
+    assert_eq!(referenced_by, json!([]));
+    let referenced_by = app
+        .get_json(&format!("/documents/{}/referenced-by", crack_id))
+        .await;
+    assert_eq!(referenced_by[0]["document_id"], action_id);
+    let references = app
+        .get_json(&format!("/documents/{}/references", action_id))
+        .await;
+    assert_eq!(references.as_array().unwrap().len(), 1);
+
+    for path in [
+        "/documents/missing/references",
+        "/documents/missing/referenced-by",
+    ] {
+        let response = app.request(Method::GET, path).send().await.unwrap();
+        assert_eq!(response.status(), 404, "{}", path);
+    }
+}
+
+#[tokio::test]
+async fn references_must_exist() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    setup_action(&app).await;
+
+    let error = first_error(post_action(&app, json!({ "ticket": "missing" })).await).await;
+    assert_eq!(
+        error,
+        json!({ "ReferenceNotFound": { "field_id": "ticket", "document_id": "missing" } })
+    );
+
+    let action = app
+        .create_document("action", "wf_action", json!({ "summary": "Replace valve" }))
+        .await;
+    let path = format!("/documents/{}", action["id"].as_str().unwrap());
+
+    let response = app
+        .request(Method::PUT, &path)
+        .json(&json!({ "data": { "related": ["missing"] } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        first_error(response).await,
+        json!({ "ReferenceNotFound": { "field_id": "related", "document_id": "missing" } })
+    );
+
+    let response = app
+        .request(Method::PATCH, &path)
+        .json(&json!({ "ticket": "missing" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        first_error(response).await,
+        json!({ "ReferenceNotFound": { "field_id": "ticket", "document_id": "missing" } })
+    );
+}
+
+#[tokio::test]
+async fn references_must_name_a_document_of_the_field_form() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    setup_action(&app).await;
+
+    let action = app
+        .create_document("action", "wf_action", json!({ "summary": "Replace valve" }))
+        .await;
+    let action_id = action["id"].as_str().unwrap();
+    let mismatch = |field_id: &str| {
+        json!({
+            "ReferenceFormMismatch": {
+                "field_id": field_id,
+                "document_id": action_id,
+                "expected_form": "ticket",
+                "actual_form": "action"
+            }
+        })
+    };
+
+    // An action is not a ticket
+    let error = first_error(post_action(&app, json!({ "ticket": action_id })).await).await;
+    assert_eq!(error, mismatch("ticket"));
+
+    let response = app
+        .request(Method::PUT, &format!("/documents/{}", action_id))
+        .json(&json!({ "data": { "related": [action_id] } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(first_error(response).await, mismatch("related"));
+}
@@ -0,0 +1,120 @@
+use crate::helpers::{TestApp, create_ticket, setup_ticket, spawn_app};
+use reqwest::Method;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+/// Polls a revalidation job until it is no longer pending or running, returning it.
+async fn wait_for_job(app: &TestApp, job_id: &str) -> Value {
+    for _ in 0..100 {
+        let response = app
+            .request(Method::GET, &format!("/revalidations/{}", job_id))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let job: Value = response.json().await.unwrap();
+        if job["status"] != "pending" && job["status"] != "running" {
+            return job;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("Revalidation job '{}' did not finish", job_id);
+}
+
+#[tokio::test]
+async fn revalidation_reports_documents_broken_by_a_forced_version() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+    let with_site = create_ticket(&app, json!({ "title": "Leak", "notes": "Valve 3" })).await;
+    let mut without_site = Vec::new();
+    for title in ["Crack", "Spill", "Noise"] {
+        let doc = create_ticket(&app, json!({ "title": title })).await;
+        without_site.push(doc["id"].as_str().unwrap().to_string());
+    }
+
+    // Version 2 requires notes, which only the first ticket has
+    let response = app
+        .request(Method::POST, "/forms/ticket/versions?force=true")
+        .json(&json!({
+            "id": "ticket",
+            "name": "Ticket",
+            "fields": [
+                { "id": "title", "label": "Title", "field_type": { "kind": "text" } },
+                { "id": "notes", "label": "Notes", "required": true, "field_type": { "kind": "text" } }
+            ]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200, "{}", response.text().await.unwrap());
+
+    let response = app
+        .request(Method::POST, "/forms/ticket/revalidations")
+        .json(&json!({ "batch_size": 2 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+    let job: Value = response.json().await.unwrap();
+    assert_eq!(job["form_version"], 2);
+    assert_eq!(job["total"], 4);
+
+    let job = wait_for_job(&app, job["id"].as_str().unwrap()).await;
+    assert_eq!(job["status"], "completed");
+    assert_eq!(job["processed"], 4);
+    assert!(job["finished_at"].is_string());
+
+    let invalid: Vec<&str> = job["invalid"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|doc| doc["document_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(invalid, without_site);
+    assert_eq!(job["invalid_count"], without_site.len());
+    assert!(!invalid.contains(&with_site["id"].as_str().unwrap()));
+    assert_eq!(job["invalid"][0]["form_version"], 1);
+    assert_eq!(
+        job["invalid"][0]["errors"],
+        json!([{ "MissingRequiredField": "notes" }])
+    );
+
+    // Against version 1, every document is still valid
+    let response = app
+        .request(Method::POST, "/forms/ticket/revalidations")
+        .json(&json!({ "form_version": 1 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+    let job: Value = response.json().await.unwrap();
+    let job = wait_for_job(&app, job["id"].as_str().unwrap()).await;
+    assert_eq!(job["status"], "completed");
+    assert_eq!(job["invalid"], json!([]));
+}
+
+#[tokio::test]
+async fn revalidation_errors() {
+    let app = spawn_app().await;
+    setup_ticket(&app).await;
+
+    let response = app
+        .request(Method::GET, "/revalidations/not-a-job")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    for (path, body) in [
+        ("/forms/missing/revalidations", json!({})),
+        ("/forms/ticket/revalidations", json!({ "form_version": 7 })),
+    ] {
+        let response = app
+            .request(Method::POST, path)
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404, "{}", path);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::helpers::spawn_app;
+use reqwest::Method;
+use serde_json::{Value, json};
+
+fn incident_form(id: &str) -> Value {
+    json!({
+        "id": id,
+        "name": "Incident",
+        "fields": [
+            { "id": "severity", "label": "Severity", "field_type": { "kind": "number", "config": {} } }
+        ]
+    })
+}
+
+fn incident_workflow(condition: &str) -> Value {
+    json!({
+        "id": "wf_incident",
+        "name": "Incident Flow",
+        "phases": [
+            { "id": "open", "label": "Open", "type": "start" },
+            { "id": "closed", "label": "Closed", "type": "end" }
+        ],
+        "transitions": [
+            { "name": "close", "from": "open", "to": "closed", "condition": condition }
+        ]
+    })
+}
+
+#[tokio::test]
+async fn workflow_is_bound_to_its_form() {
+    let app = spawn_app().await;
+    app.create_form(incident_form("incident")).await;
+
+    app.create_workflow("incident", incident_workflow("severity >= 4"))
+        .await;
+
+    let workflow: Value = app
+        .request(Method::GET, "/workflows/wf_incident")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(workflow["form_id"], "incident");
+}
+
+#[tokio::test]
+async fn workflow_form_must_exist() {
+    let app = spawn_app().await;
+
+    let response = app
+        .post_workflow("missing", incident_workflow("severity >= 4"))
+        .await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn unbound_workflows_only_have_their_conditions_parsed() {
+    let app = spawn_app().await;
+    app.create_form(incident_form("incident")).await;
+
+    // Without a form, fields the condition names cannot be checked
+    let response = app
+        .request(Method::POST, "/workflows")
+        .json(&incident_workflow("category == 'Safety'"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let workflow: Value = response.json().await.unwrap();
+    assert!(workflow.get("form_id").is_none());
+
+    // Its documents may use any form
+    let response = app
+        .post_document(
+            "alice",
+            json!({ "form_id": "incident", "workflow_id": "wf_incident", "data": {} }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    // Malformed conditions are still rejected
+    let mut workflow = incident_workflow("severity >=");
+    workflow["id"] = json!("wf_broken");
+    let response = app
+        .request(Method::POST, "/workflows")
+        .json(&workflow)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn workflow_conditions_are_checked_against_the_form() {
+    let app = spawn_app().await;
+    app.create_form(incident_form("incident")).await;
+
+    let response = app
+        .post_workflow("incident", incident_workflow("category == 'Safety'"))
+        .await;
+
+    assert_eq!(response.status(), 400);
+    assert!(
+        response
+            .text()
+            .await
+            .unwrap()
+            .contains("invalid_transition_condition")
+    );
+}
+
+#[tokio::test]
+async fn documents_must_use_the_form_of_their_workflow() {
+    let app = spawn_app().await;
+    app.create_form(incident_form("incident")).await;
+    app.create_form(incident_form("near_miss")).await;
+    app.create_workflow("incident", incident_workflow("severity >= 4"))
+        .await;
+
+    let response = app
+        .post_document(
+            "alice",
+            json!({ "form_id": "near_miss", "workflow_id": "wf_incident", "data": {} }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let response = app
+        .post_document(
+            "alice",
+            json!({ "form_id": "incident", "workflow_id": "wf_incident", "data": {} }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+}